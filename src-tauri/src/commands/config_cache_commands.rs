@@ -1,11 +1,13 @@
 //! 配置缓存命令模块
 
 use crate::services::config_cache_service;
-use crate::services::config_cache_service::{ConfigChange, ConfigSnapshot};
+use crate::services::config_cache_service::{ConfigChange, ConfigSnapshot, ExternalChangeError};
 use crate::services::config_service;
+use crate::services::import_export_service;
 use crate::services::preset_service;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashSet;
 use std::fs;
 
 #[tauri::command]
@@ -38,6 +40,17 @@ pub fn load_config_snapshot() -> Result<Option<ConfigSnapshot>, String> {
     Ok(config_cache_service::load_config_snapshot())
 }
 
+/// 列出历史快照的时间戳（从新到旧）
+#[tauri::command]
+pub fn list_config_snapshots() -> Result<Vec<u64>, String> {
+    config_cache_service::list_config_snapshots()
+}
+
+#[tauri::command]
+pub fn load_config_snapshot_at(cached_at: u64) -> Result<Option<ConfigSnapshot>, String> {
+    Ok(config_cache_service::load_config_snapshot_at(cached_at))
+}
+
 #[tauri::command]
 pub async fn compare_with_snapshot() -> Result<Vec<ConfigChange>, String> {
     tokio::task::spawn_blocking(|| {
@@ -45,9 +58,10 @@ pub async fn compare_with_snapshot() -> Result<Vec<ConfigChange>, String> {
         let snapshot = config_cache_service::load_config_snapshot();
 
         match snapshot {
-            Some(snap) => Ok(config_cache_service::compare_configs(
+            Some(snap) => Ok(config_cache_service::compare_configs_ignoring(
                 &snap.config,
                 &current_config,
+                &config_cache_service::load_snapshot_ignored_paths(),
             )),
             None => {
                 // 快照不存在时，自动创建初始快照并返回空变更列表
@@ -60,20 +74,44 @@ pub async fn compare_with_snapshot() -> Result<Vec<ConfigChange>, String> {
     .map_err(|e| format!("比较配置失败: {}", e))?
 }
 
+/// 获取快照对比时忽略的字段路径列表
+#[tauri::command]
+pub fn get_snapshot_ignored_paths() -> Result<Vec<String>, String> {
+    Ok(config_cache_service::load_snapshot_ignored_paths())
+}
+
+/// 设置快照对比时忽略的字段路径列表
+#[tauri::command]
+pub fn set_snapshot_ignored_paths(paths: Vec<String>) -> Result<(), String> {
+    config_cache_service::save_snapshot_ignored_paths(&paths)
+}
+
+/// 合并快照与当前配置并保存
+///
+/// deleted_paths 为本地已显式删除的字段路径（如 "agents.metis"），
+/// 这些字段不会因快照中仍存在而被补回
 #[tauri::command]
-pub fn merge_and_save() -> Result<Value, String> {
-    let current_config = config_service::read_omo_config()?;
-    let snapshot = config_cache_service::load_config_snapshot();
+pub fn merge_and_save(deleted_paths: Option<Vec<String>>) -> Result<Value, String> {
+    config_service::with_config_lock(|| {
+        let current_config = config_service::read_omo_config()?;
+        let snapshot = config_cache_service::load_config_snapshot();
+        let deleted_paths: HashSet<String> =
+            deleted_paths.unwrap_or_default().into_iter().collect();
 
-    let merged_config = match snapshot {
-        Some(snap) => config_cache_service::merge_configs(&snap.config, &current_config),
-        None => current_config,
-    };
+        let merged_config = match snapshot {
+            Some(snap) => config_cache_service::merge_configs_with_deletions(
+                &snap.config,
+                &current_config,
+                &deleted_paths,
+            ),
+            None => current_config,
+        };
 
-    config_service::validate_config(&merged_config)?;
-    config_service::write_omo_config(&merged_config)?;
+        config_service::validate_config(&merged_config)?;
+        config_service::write_omo_config(&merged_config)?;
 
-    Ok(merged_config)
+        Ok(merged_config)
+    })
 }
 
 #[tauri::command]
@@ -113,28 +151,24 @@ pub struct AcceptExternalChangesResult {
 /// 2. 更新配置快照（避免重复弹出“外部修改”提示）
 /// 3. 若当前激活的是用户预设，则同步该预设到最新配置
 /// 4. 返回最终配置给前端用于即时刷新 UI
+///
+/// 读取或校验失败时返回结构化指引（是否可恢复、建议操作及可用备份路径）
 #[tauri::command]
-pub async fn accept_external_changes() -> Result<AcceptExternalChangesResult, String> {
+pub async fn accept_external_changes() -> Result<AcceptExternalChangesResult, ExternalChangeError> {
     tokio::task::spawn_blocking(|| {
-        let config = config_service::read_omo_config()?;
-        config_service::validate_config(&config)?;
-        config_cache_service::save_config_snapshot(&config)?;
-
-        let mut active_preset = preset_service::get_active_preset();
+        let config = config_service::read_omo_config()
+            .and_then(|config| config_service::validate_config(&config).map(|_| config))
+            .map_err(|e| {
+                ExternalChangeError::new(e, import_export_service::find_latest_valid_backup())
+            })?;
+        config_cache_service::save_config_snapshot(&config)
+            .map_err(|e| ExternalChangeError::new(e, None))?;
+
+        // 兼容旧版本遗留的内置预设标识
+        let active_preset = preset_service::migrate_active_preset_marker();
         let mut preset_synced = false;
         let mut preset_sync_error: Option<String> = None;
 
-        if let Some(name) = active_preset.as_ref() {
-            // 兼容旧版本遗留的内置预设标识，统一回退到 default
-            if name.starts_with("__builtin__") {
-                if let Err(err) = preset_service::set_active_preset("default") {
-                    preset_sync_error = Some(err);
-                } else {
-                    active_preset = Some("default".to_string());
-                }
-            }
-        }
-
         if let Some(name) = active_preset.as_ref() {
             if let Err(err) = preset_service::update_preset(name) {
                 preset_sync_error = Some(err);
@@ -151,5 +185,5 @@ pub async fn accept_external_changes() -> Result<AcceptExternalChangesResult, St
         })
     })
     .await
-    .map_err(|e| format!("接受外部变更失败: {}", e))?
+    .map_err(|e| ExternalChangeError::new(format!("接受外部变更失败: {}", e), None))?
 }
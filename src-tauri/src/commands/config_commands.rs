@@ -1,7 +1,8 @@
 use chrono::{DateTime, Local};
 use serde::Serialize;
-use crate::services::config_service;
+use crate::services::{config_cache_service, config_service, model_service, provider_store};
 use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,6 +48,12 @@ pub fn write_omo_config(config: Value) -> Result<(), String> {
     config_service::write_omo_config(&config)
 }
 
+/// 配置文件不存在时初始化最小配置，返回是否新建
+#[tauri::command]
+pub fn ensure_omo_config_exists() -> Result<bool, String> {
+    config_service::ensure_omo_config_exists()
+}
+
 #[tauri::command]
 pub fn validate_config(config: Value) -> Result<(), String> {
     config_service::validate_config(&config)
@@ -56,56 +63,103 @@ pub fn validate_config(config: Value) -> Result<(), String> {
 /// 返回 ~/.cache/oh-my-opencode/
 #[tauri::command]
 pub fn get_omo_cache_dir() -> Result<String, String> {
-    // 获取系统缓存目录
-    let cache_dir = dirs::cache_dir().ok_or_else(|| "无法获取系统缓存目录".to_string())?;
-
-    // 拼接 oh-my-opencode 子目录
-    let omo_cache = cache_dir.join("oh-my-opencode");
-    // 返回绝对路径，供 reveal/open 等 API 直接使用
+    // 与各服务共用同一解析逻辑，返回绝对路径供 reveal/open 等 API 直接使用
+    let omo_cache = provider_store::omo_cache_dir()?;
     Ok(omo_cache.to_string_lossy().to_string())
 }
 
+/// 各子系统实际读写的缓存位置
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedCachePath {
+    pub subsystem: String,
+    pub path: String,
+    pub exists: bool,
+}
+
+/// 诊断：列出各子系统当前使用的缓存路径
+/// 额外列出平台缓存目录（dirs::cache_dir），便于发现旧版本遗留在其他位置的缓存
+#[tauri::command]
+pub fn get_resolved_cache_dirs() -> Result<Vec<ResolvedCachePath>, String> {
+    let mut entries = vec![
+        ("omo_cache_dir", provider_store::omo_cache_dir()?),
+        ("provider_models", provider_store::get_provider_models_path()?),
+        ("connected_providers", provider_store::get_connected_providers_path()?),
+        ("verified_models", model_service::get_verified_models_path()?),
+        ("config_snapshot", config_cache_service::get_snapshot_path()?),
+        ("config_snapshot_history", config_cache_service::get_snapshot_history_dir()?),
+        ("provider_icons", provider_store::get_provider_icon_cache_dir()?),
+    ];
+    if let Some(path) = model_service::get_models_dev_cache_path() {
+        entries.push(("models_dev_cache", path));
+    }
+    if let Some(platform_cache) = dirs::cache_dir() {
+        entries.push(("platform_cache_dir", platform_cache.join("oh-my-opencode")));
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(subsystem, path)| ResolvedCachePath {
+            subsystem: subsystem.to_string(),
+            exists: path.exists(),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect())
+}
+
+/// 为所有已配置模型的 category 批量设置 variant，返回更新数量
+#[tauri::command]
+pub fn set_category_variants(variant: String) -> Result<usize, String> {
+    config_service::set_category_variants(&variant)
+}
+
+/// 将使用 from_provider 的 agent/category 按 model_map 切换到 to_provider 的等价模型
+#[tauri::command]
+pub fn remap_provider(
+    from_provider: String,
+    to_provider: String,
+    model_map: HashMap<String, String>,
+) -> Result<config_service::ProviderRemapResult, String> {
+    config_service::remap_provider(&from_provider, &to_provider, &model_map)
+}
+
+/// 找出被多个 agent/category 共用的模型
+#[tauri::command]
+pub fn find_agents_sharing_model() -> Result<HashMap<String, Vec<String>>, String> {
+    config_service::find_agents_sharing_model()
+}
+
+/// 对比当前配置与应用内显示名表中的 agent 名称
+#[tauri::command]
+pub fn reconcile_agent_sources() -> Result<config_service::AgentSourceReconciliation, String> {
+    let app_agents = crate::tray::app_agent_names();
+    config_service::reconcile_agent_sources(&app_agents)
+}
+
 #[tauri::command]
 pub fn update_agent_model(
     agent_name: String,
     model: String,
     variant: Option<String>,
 ) -> Result<Value, String> {
-    let mut config = config_service::read_omo_config()?;
-
-    // 尝试在 agents 中更新
-    if let Some(agents) = config.get_mut("agents").and_then(|a| a.as_object_mut()) {
-        if let Some(agent) = agents.get_mut(&agent_name) {
-            if let Some(obj) = agent.as_object_mut() {
-                obj.insert("model".to_string(), Value::String(model.clone()));
-                if let Some(v) = &variant {
-                    if v != "none" {
-                        obj.insert("variant".to_string(), Value::String(v.clone()));
-                    } else {
-                        obj.remove("variant");
-                    }
-                }
-            }
+    let config = config_service::with_config_lock(|| {
+        let mut config = config_service::read_omo_config()?;
+
+        // agents 与 categories 都未命中时不写盘，避免调用方误以为修改成功
+        if !apply_model_update(&mut config, &agent_name, &model, variant.as_deref()) {
+            return Err(format!("未找到 agent/category: {}", agent_name));
         }
-    }
 
-    // 也尝试在 categories 中更新
-    if let Some(categories) = config.get_mut("categories").and_then(|c| c.as_object_mut()) {
-        if let Some(category) = categories.get_mut(&agent_name) {
-            if let Some(obj) = category.as_object_mut() {
-                obj.insert("model".to_string(), Value::String(model.clone()));
-                if let Some(v) = &variant {
-                    if v != "none" {
-                        obj.insert("variant".to_string(), Value::String(v.clone()));
-                    } else {
-                        obj.remove("variant");
-                    }
-                }
-            }
+        config_service::write_omo_config(&config)?;
+        Ok(config)
+    })?;
+
+    // 托盘按最近使用排序模型，记录失败不影响本次更新
+    if let Some((provider, model_id)) = model.split_once('/') {
+        if let Err(err) = model_service::record_recent_model(provider, model_id) {
+            eprintln!("记录最近使用模型失败: {}", err);
         }
     }
-
-    config_service::write_omo_config(&config)?;
     Ok(config)
 }
 
@@ -118,58 +172,174 @@ pub struct AgentUpdateRequest {
     pub variant: Option<String>,
 }
 
-/// 批量更新多个 agent/category 的模型配置
-/// 一次性写入配置文件，避免多次 IO 操作
-#[tauri::command]
-pub fn update_agents_batch(
-    updates: Vec<AgentUpdateRequest>,
-) -> Result<Value, String> {
-    let mut config = config_service::read_omo_config()?;
+/// 批量更新结果
+/// applied 为命中的 agent/category 名称，skipped 为在 agents 与 categories 中都不存在的名称
+#[derive(Debug, Serialize)]
+pub struct BatchUpdateResult {
+    pub config: Value,
+    pub applied: Vec<String>,
+    pub skipped: Vec<String>,
+}
 
-    for update in updates {
-        // 更新 agents
-        if let Some(agents) = config.get_mut("agents").and_then(|a| a.as_object_mut()) {
-            if let Some(agent) = agents.get_mut(&update.agent_name) {
-                if let Some(obj) = agent.as_object_mut() {
-                    obj.insert("model".to_string(), Value::String(update.model.clone()));
-                    if let Some(ref v) = update.variant {
-                        if v != "none" {
-                            obj.insert("variant".to_string(), Value::String(v.clone()));
-                        } else {
-                            obj.remove("variant");
-                        }
-                    }
-                }
-            }
+/// 更新单个条目的 model/variant，variant 为 "none" 时移除该字段
+fn apply_model_to_entry(entry: &mut Value, model: &str, variant: Option<&str>) -> bool {
+    let Some(obj) = entry.as_object_mut() else {
+        return false;
+    };
+    obj.insert("model".to_string(), Value::String(model.to_string()));
+    match variant {
+        Some("none") => {
+            obj.remove("variant");
         }
+        Some(v) => {
+            obj.insert("variant".to_string(), Value::String(v.to_string()));
+        }
+        None => {}
+    }
+    true
+}
 
-        // 更新 categories
-        if let Some(categories) = config.get_mut("categories").and_then(|c| c.as_object_mut()) {
-            if let Some(category) = categories.get_mut(&update.agent_name) {
-                if let Some(obj) = category.as_object_mut() {
-                    obj.insert("model".to_string(), Value::String(update.model.clone()));
-                    if let Some(ref v) = update.variant {
-                        if v != "none" {
-                            obj.insert("variant".to_string(), Value::String(v.clone()));
-                        } else {
-                            obj.remove("variant");
-                        }
-                    }
-                }
-            }
+/// 更新 agents 与 categories 中的同名条目，返回是否命中
+fn apply_model_update(config: &mut Value, name: &str, model: &str, variant: Option<&str>) -> bool {
+    let mut hit = false;
+    for section in ["agents", "categories"] {
+        if let Some(entry) = config
+            .get_mut(section)
+            .and_then(|s| s.as_object_mut())
+            .and_then(|s| s.get_mut(name))
+        {
+            hit |= apply_model_to_entry(entry, model, variant);
         }
     }
+    hit
+}
 
-    // 只写入一次配置文件
-    config_service::write_omo_config(&config)?;
-    Ok(config)
+/// 将批量更新应用到配置，返回 (applied, skipped)
+fn apply_agent_updates(
+    config: &mut Value,
+    updates: &[AgentUpdateRequest],
+) -> (Vec<String>, Vec<String>) {
+    let mut applied = Vec::new();
+    let mut skipped = Vec::new();
+
+    for update in updates {
+        let hit = apply_model_update(
+            config,
+            &update.agent_name,
+            &update.model,
+            update.variant.as_deref(),
+        );
+        if hit {
+            applied.push(update.agent_name.clone());
+        } else {
+            skipped.push(update.agent_name.clone());
+        }
+    }
+
+    (applied, skipped)
+}
+
+/// 批量更新多个 agent/category 的模型配置
+/// 一次性写入配置文件，避免多次 IO 操作；未命中的名称在 skipped 中返回
+#[tauri::command]
+pub fn update_agents_batch(updates: Vec<AgentUpdateRequest>) -> Result<BatchUpdateResult, String> {
+    // 读取-修改-写入全程持有配置锁，避免与托盘等并发写入互相覆盖
+    config_service::with_config_lock(|| {
+        let mut config = config_service::read_omo_config()?;
+        let (applied, skipped) = apply_agent_updates(&mut config, &updates);
+
+        // 只写入一次配置文件
+        config_service::write_omo_config(&config)?;
+        Ok(BatchUpdateResult {
+            config,
+            applied,
+            skipped,
+        })
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use serial_test::serial;
 
+    #[test]
+    fn test_apply_agent_updates_reports_skipped_names() {
+        let mut config = json!({
+            "agents": { "sisyphus": { "model": "old/a", "variant": "high" } },
+            "categories": { "quick": { "model": "old/b" } }
+        });
+        let updates = vec![
+            AgentUpdateRequest {
+                agent_name: "sisyphus".to_string(),
+                model: "new/a".to_string(),
+                variant: Some("none".to_string()),
+            },
+            AgentUpdateRequest {
+                agent_name: "ghost".to_string(),
+                model: "new/x".to_string(),
+                variant: None,
+            },
+            AgentUpdateRequest {
+                agent_name: "quick".to_string(),
+                model: "new/b".to_string(),
+                variant: Some("low".to_string()),
+            },
+        ];
+
+        let (applied, skipped) = apply_agent_updates(&mut config, &updates);
+
+        assert_eq!(applied, vec!["sisyphus", "quick"]);
+        assert_eq!(skipped, vec!["ghost"]);
+        assert_eq!(config["agents"]["sisyphus"], json!({ "model": "new/a" }));
+        assert_eq!(
+            config["categories"]["quick"],
+            json!({ "model": "new/b", "variant": "low" })
+        );
+        assert!(config["agents"].get("ghost").is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_update_agent_model_rejects_unknown_name() {
+        let temp_dir = std::env::temp_dir().join("omo-update-agent-model-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let config_path = config_dir.join("oh-my-openagent.json");
+        let original =
+            r#"{"agents":{"sisyphus":{"model":"old/a"}},"categories":{"quick":{"model":"old/b"}}}"#;
+        std::fs::write(&config_path, original).unwrap();
+
+        let missing = update_agent_model("ghost".to_string(), "new/x".to_string(), None);
+        let content_after_missing = std::fs::read_to_string(&config_path).unwrap();
+        let category_only = update_agent_model("quick".to_string(), "new/b".to_string(), None);
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(missing.unwrap_err(), "未找到 agent/category: ghost");
+        assert_eq!(content_after_missing, original);
+        let config = category_only.unwrap();
+        assert_eq!(config["categories"]["quick"]["model"], "new/b");
+        assert_eq!(config["agents"]["sisyphus"]["model"], "old/a");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
     #[test]
     #[serial]
     fn test_get_config_metadata_reads_existing_file() {
@@ -8,6 +8,8 @@ pub fn get_locale() -> String {
 
 #[command]
 pub fn set_locale(locale: String) -> Result<(), String> {
-    i18n::set_locale(&locale);
+    i18n::set_locale(&locale)?;
+    // 切换语言时重新读取外部翻译文件，便于调试社区译文
+    i18n::reload_translation_overrides();
     Ok(())
 }
@@ -1,21 +1,72 @@
 use crate::services::import_export_service::{
     clear_backup_history as clear_backup_history_service,
+    clear_backup_history_keeping as clear_backup_history_keeping_service,
     delete_backup_entry,
+    export_app_bundle as export_app_bundle_service,
     export_backup_entry,
+    export_config_report as export_config_report_service,
     export_config_with_history,
+    export_config_redacted as export_config_redacted_service,
+    export_minimal_config as export_minimal_config_service,
+    get_compress_backups,
     get_max_backup_records,
     get_backup_history,
+    get_backup_history_filtered,
+    import_app_bundle as import_app_bundle_service,
     import_config,
+    import_config_merge,
     restore_from_backup,
+    set_backup_note as set_backup_note_service,
+    set_compress_backups,
     set_max_backup_records,
     validate_import_file,
+    AppBundleImportResult,
     BackupInfo,
+    RestoreError,
 };
 use serde_json::Value;
 
 #[tauri::command]
-pub fn export_omo_config(path: String, record_history: Option<bool>) -> Result<(), String> {
-    export_config_with_history(&path, record_history.unwrap_or(false))
+pub fn export_omo_config(
+    path: String,
+    record_history: Option<bool>,
+    redact: Option<bool>,
+) -> Result<(), String> {
+    export_config_with_history(
+        &path,
+        record_history.unwrap_or(false),
+        redact.unwrap_or(false),
+    )
+}
+
+/// 生成仅包含已配置模型条目的精简配置（只读，不写入文件）
+#[tauri::command]
+pub fn export_minimal_config(strip_extra: Option<bool>) -> Result<Value, String> {
+    export_minimal_config_service(strip_extra.unwrap_or(false))
+}
+
+/// 生成脱敏配置，用于公开分享（只读，不写入文件）
+#[tauri::command]
+pub fn export_config_redacted() -> Result<Value, String> {
+    export_config_redacted_service()
+}
+
+/// 导出可读报告（目前支持 markdown），列出每个 agent/category 的模型与 provider 连接状态
+#[tauri::command]
+pub fn export_config_report(path: String, format: Option<String>) -> Result<(), String> {
+    export_config_report_service(&path, format.as_deref().unwrap_or("markdown"))
+}
+
+/// 导出应用迁移包（配置、预设、设置、语言），include_secrets 为 true 时附带 auth.json
+#[tauri::command]
+pub fn export_app_bundle(path: String, include_secrets: Option<bool>) -> Result<(), String> {
+    export_app_bundle_service(&path, include_secrets.unwrap_or(false))
+}
+
+/// 校验并还原应用迁移包
+#[tauri::command]
+pub fn import_app_bundle(path: String) -> Result<AppBundleImportResult, String> {
+    import_app_bundle_service(&path)
 }
 
 #[tauri::command]
@@ -23,6 +74,12 @@ pub fn import_omo_config(path: String) -> Result<(), String> {
     import_config(&path)
 }
 
+/// 合并导入：导入文件优先，保留当前配置中导入文件没有的条目
+#[tauri::command]
+pub fn import_omo_config_merge(path: String) -> Result<(), String> {
+    import_config_merge(&path)
+}
+
 #[tauri::command]
 pub fn validate_import(path: String) -> Result<Value, String> {
     validate_import_file(&path)
@@ -33,8 +90,18 @@ pub fn get_import_export_history() -> Result<Vec<BackupInfo>, String> {
     get_backup_history()
 }
 
+/// 按操作类型与时间区间筛选历史记录，参数全为 None 时返回全部
+#[tauri::command]
+pub fn get_import_export_history_filtered(
+    operation: Option<String>,
+    since_ts: Option<u64>,
+    until_ts: Option<u64>,
+) -> Result<Vec<BackupInfo>, String> {
+    get_backup_history_filtered(operation.as_deref(), since_ts, until_ts)
+}
+
 #[tauri::command]
-pub fn restore_backup(path: String) -> Result<(), String> {
+pub fn restore_backup(path: String) -> Result<(), RestoreError> {
     restore_from_backup(&path)
 }
 
@@ -43,6 +110,12 @@ pub fn delete_backup(path: String) -> Result<(), String> {
     delete_backup_entry(&path)
 }
 
+/// 为备份设置备注，备注为空时清除
+#[tauri::command]
+pub fn set_backup_note(path: String, note: String) -> Result<(), String> {
+    set_backup_note_service(&path, &note)
+}
+
 #[tauri::command]
 pub fn export_backup(path: String, target_path: String) -> Result<(), String> {
     export_backup_entry(&path, &target_path)
@@ -53,6 +126,12 @@ pub fn clear_backup_history() -> Result<usize, String> {
     clear_backup_history_service()
 }
 
+/// 清理旧备份，仅保留最近 keep 条，返回删除数量
+#[tauri::command]
+pub fn clear_backup_history_keeping(keep: usize) -> Result<usize, String> {
+    clear_backup_history_keeping_service(keep)
+}
+
 #[tauri::command]
 pub fn get_backup_history_limit() -> Result<usize, String> {
     Ok(get_max_backup_records())
@@ -62,3 +141,14 @@ pub fn get_backup_history_limit() -> Result<usize, String> {
 pub fn set_backup_history_limit(limit: usize) -> Result<usize, String> {
     set_max_backup_records(limit)
 }
+
+/// 获取新备份是否使用 gzip 压缩
+#[tauri::command]
+pub fn get_backup_compression() -> Result<bool, String> {
+    Ok(get_compress_backups())
+}
+
+#[tauri::command]
+pub fn set_backup_compression(enabled: bool) -> Result<bool, String> {
+    set_compress_backups(enabled)
+}
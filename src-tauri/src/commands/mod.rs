@@ -5,4 +5,5 @@ pub mod import_export_commands;
 pub mod model_commands;
 pub mod preset_commands;
 pub mod provider_commands;
+pub mod repair_commands;
 pub mod version_commands;
@@ -1,29 +1,59 @@
-use crate::services::model_service::{self, AvailableModelsWithStatus, ModelInfo};
+use crate::services::model_service::{
+    self, AvailableModelsWithStatus, ModelInfo, ModelInfoWithCurrency, ModelsDonePayload,
+};
 use std::collections::HashMap;
+use tauri::Emitter;
 
+/// exclude_disabled 为 true 时过滤掉被临时禁用的供应商
 #[tauri::command]
-pub async fn get_available_models() -> Result<HashMap<String, Vec<String>>, String> {
-    tokio::task::spawn_blocking(|| {
-        model_service::get_available_models()
+pub async fn get_available_models(
+    exclude_disabled: Option<bool>,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut models = model_service::get_available_models()?;
+        if exclude_disabled.unwrap_or(false) {
+            model_service::remove_disabled_providers(&mut models);
+        }
+        Ok(models)
     })
     .await
     .map_err(|e| format!("获取模型列表失败: {}", e))?
 }
 
+/// 按子串（不区分大小写）搜索模型 ID，空查询返回全部
+#[tauri::command]
+pub async fn search_models(query: String) -> Result<HashMap<String, Vec<String>>, String> {
+    tokio::task::spawn_blocking(move || model_service::search_models(&query))
+        .await
+        .map_err(|e| format!("搜索模型失败: {}", e))?
+}
+
 #[tauri::command]
 pub async fn get_verified_available_models() -> Result<HashMap<String, Vec<String>>, String> {
-    tokio::task::spawn_blocking(|| model_service::get_verified_available_models())
+    tokio::task::spawn_blocking(model_service::get_verified_available_models)
         .await
         .map_err(|e| format!("获取校验模型列表失败: {}", e))?
 }
 
 #[tauri::command]
 pub async fn get_available_models_with_status() -> Result<AvailableModelsWithStatus, String> {
-    tokio::task::spawn_blocking(|| model_service::get_available_models_with_status())
+    tokio::task::spawn_blocking(model_service::get_available_models_with_status)
         .await
         .map_err(|e| format!("获取模型状态失败: {}", e))?
 }
 
+/// 使 `opencode models` 内存缓存失效，供“刷新模型”按钮调用
+#[tauri::command]
+pub fn invalidate_models_cache() {
+    model_service::invalidate_models_cache();
+}
+
+/// 获取最近使用的模型（`provider/model` 格式，最近的在前）
+#[tauri::command]
+pub fn get_recent_models() -> Vec<String> {
+    model_service::get_recent_models()
+}
+
 #[tauri::command]
 pub async fn get_connected_providers() -> Result<Vec<String>, String> {
     tokio::task::spawn_blocking(|| {
@@ -33,7 +63,67 @@ pub async fn get_connected_providers() -> Result<Vec<String>, String> {
     .map_err(|e| format!("获取已连接供应商失败: {}", e))?
 }
 
+#[tauri::command]
+pub async fn get_provider_model_counts() -> Result<HashMap<String, usize>, String> {
+    tokio::task::spawn_blocking(model_service::get_provider_model_counts)
+        .await
+        .map_err(|e| format!("获取模型数量失败: {}", e))?
+}
+
 #[tauri::command]
 pub fn fetch_models_dev() -> Result<Vec<ModelInfo>, String> {
     model_service::fetch_models_dev()
 }
+
+/// 只返回指定供应商（按 id 的 provider 前缀匹配）的 models.dev 模型信息
+#[tauri::command]
+pub async fn fetch_models_dev_for(providers: Vec<String>) -> Result<Vec<ModelInfo>, String> {
+    tokio::task::spawn_blocking(move || model_service::fetch_models_dev_for(&providers))
+        .await
+        .map_err(|e| format!("获取 models.dev 模型信息失败: {}", e))?
+}
+
+/// 获取 models.dev 模型信息，并按本地汇率表附加目标货币的价格估算
+#[tauri::command]
+pub async fn fetch_models_dev_with_currency(
+    target: String,
+) -> Result<Vec<ModelInfoWithCurrency>, String> {
+    tokio::task::spawn_blocking(move || model_service::fetch_models_dev_with_currency(&target))
+        .await
+        .map_err(|e| format!("获取 models.dev 模型信息失败: {}", e))?
+}
+
+/// 忽略缓存强制重新拉取 models.dev 模型信息
+#[tauri::command]
+pub async fn refresh_models_dev() -> Result<Vec<ModelInfo>, String> {
+    tokio::task::spawn_blocking(model_service::refresh_models_dev)
+        .await
+        .map_err(|e| format!("刷新 models.dev 失败: {}", e))?
+}
+
+/// 分块推送单个供应商的模型列表，适用于模型数量很多的供应商
+/// 依次发送 `omo://models-chunk` 事件，最后发送 `omo://models-done`，返回模型总数
+#[tauri::command]
+pub async fn stream_provider_models(
+    app: tauri::AppHandle,
+    provider_id: String,
+) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let models = model_service::get_provider_models(&provider_id)?;
+        for chunk in model_service::build_model_chunks(&provider_id, &models) {
+            app.emit("omo://models-chunk", chunk)
+                .map_err(|e| format!("推送模型列表失败: {}", e))?;
+        }
+        app.emit(
+            "omo://models-done",
+            ModelsDonePayload {
+                provider_id,
+                total: models.len(),
+            },
+        )
+        .map_err(|e| format!("推送模型列表失败: {}", e))?;
+        Ok(models.len())
+    })
+    .await
+    .map_err(|e| format!("推送模型列表失败: {}", e))?
+}
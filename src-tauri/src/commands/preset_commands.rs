@@ -1,6 +1,8 @@
+use crate::services::config_cache_service::ConfigChange;
 use crate::services::preset_service;
 use crate::services::preset_service::PresetUpdateRequest;
 use crate::services::preset_service::PresetMeta;
+use crate::services::preset_service::PresetCostEstimate;
 use serde_json::Value;
 
 #[tauri::command]
@@ -33,6 +35,61 @@ pub fn rename_preset(old_name: String, new_name: String) -> Result<(), String> {
     preset_service::rename_preset(&old_name, &new_name)
 }
 
+/// 导出预设为单个 JSON 文件（不含 __meta__）
+#[tauri::command]
+pub fn export_preset(name: String, target_path: String) -> Result<(), String> {
+    preset_service::export_preset(&name, &target_path)
+}
+
+/// 从外部 JSON 文件导入为新预设
+#[tauri::command]
+pub fn import_preset(source_path: String, new_name: String) -> Result<(), String> {
+    preset_service::import_preset(&source_path, &new_name)
+}
+
+/// 复制预设为新名称（另存为）
+#[tauri::command]
+pub fn duplicate_preset(source: String, new_name: String) -> Result<(), String> {
+    preset_service::duplicate_preset(&source, &new_name)
+}
+
+/// 比较两个预设之间的差异
+#[tauri::command]
+pub fn diff_presets(a: String, b: String) -> Result<Vec<ConfigChange>, String> {
+    preset_service::diff_presets(&a, &b)
+}
+
+/// 比较预设与当前配置之间的差异
+#[tauri::command]
+pub fn diff_preset_with_current(name: String) -> Result<Vec<ConfigChange>, String> {
+    preset_service::diff_preset_with_current(&name)
+}
+
+/// 试运行应用预设，返回将产生的配置变更（不写入磁盘）
+#[tauri::command]
+pub fn preview_apply_preset(name: String) -> Result<Vec<ConfigChange>, String> {
+    preset_service::preview_apply_preset(&name)
+}
+
+/// 查找使用了指定模型的预设名称列表
+#[tauri::command]
+pub fn find_presets_using_model(model: String) -> Result<Vec<String>, String> {
+    preset_service::find_presets_using_model(&model)
+}
+
+/// 按日均 token 用量粗估预设的月成本区间（按 agent 明细 + 总计）
+#[tauri::command]
+pub async fn estimate_preset_cost(
+    name: String,
+    assumed_tokens_per_day: u64,
+) -> Result<PresetCostEstimate, String> {
+    tokio::task::spawn_blocking(move || {
+        preset_service::estimate_preset_cost(&name, assumed_tokens_per_day)
+    })
+    .await
+    .map_err(|e| format!("估算预设成本失败: {}", e))?
+}
+
 #[tauri::command]
 pub fn get_preset_info(name: String) -> Result<(usize, usize, String), String> {
     preset_service::get_preset_info(&name)
@@ -47,16 +104,34 @@ pub fn update_preset(name: String) -> Result<(), String> {
 pub fn apply_updates_to_preset(
     name: String,
     updates: Vec<PresetUpdateRequest>,
-) -> Result<(), String> {
+) -> Result<Vec<String>, String> {
     preset_service::apply_updates_to_preset(&name, &updates)
 }
 
+/// 列出预设的历史版本时间戳（从新到旧）
+#[tauri::command]
+pub fn list_preset_history(name: String) -> Result<Vec<u64>, String> {
+    preset_service::list_preset_history(&name)
+}
+
+/// 将预设恢复到指定历史版本
+#[tauri::command]
+pub fn restore_preset_version(name: String, ts: u64) -> Result<(), String> {
+    preset_service::restore_preset_version(&name, ts)
+}
+
 /// 获取预设元数据（供 UI 显示更新时间）
 #[tauri::command]
 pub fn get_preset_meta(name: String) -> Result<PresetMeta, String> {
     preset_service::get_preset_meta(&name)
 }
 
+/// 设置预设描述（传空则清除）
+#[tauri::command]
+pub fn set_preset_description(name: String, description: Option<String>) -> Result<(), String> {
+    preset_service::set_preset_description(&name, description.as_deref())
+}
+
 /// 用当前配置同步到预设（用于"忽略"操作）
 #[tauri::command]
 pub fn sync_preset_from_config(name: String) -> Result<(), String> {
@@ -73,3 +148,15 @@ pub fn set_active_preset(name: String) -> Result<(), String> {
 pub fn get_active_preset() -> Result<Option<String>, String> {
     Ok(preset_service::get_active_preset())
 }
+
+/// 清除当前激活预设标记（表示当前配置不对应任何预设）
+#[tauri::command]
+pub fn clear_active_preset() -> Result<(), String> {
+    preset_service::clear_active_preset()
+}
+
+/// 迁移旧版本遗留的 `__builtin__` 激活预设标记，返回迁移后的激活预设
+#[tauri::command]
+pub fn migrate_active_preset_marker() -> Result<Option<String>, String> {
+    Ok(preset_service::migrate_active_preset_marker())
+}
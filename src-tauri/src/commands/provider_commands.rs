@@ -3,6 +3,12 @@ use std::collections::HashMap;
 
 use crate::services::{provider_service, provider_store};
 
+/// 网络获取失败时使用的内置占位图标
+const PROVIDER_ICON_PLACEHOLDER: &[u8] = include_bytes!("../../icons/providers/placeholder.png");
+/// 图标缓存有效期，超过后下次请求会重新下载（以文件 mtime 为准）
+const PROVIDER_ICON_CACHE_TTL: std::time::Duration =
+    std::time::Duration::from_secs(30 * 24 * 60 * 60);
+
 const PROVIDER_DOMAINS: &[(&str, &str)] = &[
     ("anthropic", "anthropic.com"),
     ("openai", "openai.com"),
@@ -34,15 +40,15 @@ const PROVIDER_DOMAINS: &[(&str, &str)] = &[
 pub type ProviderInfo = provider_service::ProviderInfo;
 pub type ProviderConfigSnapshot = provider_service::ProviderConfigSnapshot;
 pub type ConnectionTestResult = provider_service::ConnectionTestResult;
+pub type ApiKeySaveResult = provider_service::ApiKeySaveResult;
+pub type EnvKeyImportResult = provider_service::EnvKeyImportResult;
+pub type ProviderDiagnostic = provider_service::ProviderDiagnostic;
 pub(crate) type AuthEntry = provider_store::AuthEntry;
 
-fn get_provider_icon_cache_path(provider_id: &str) -> Result<std::path::PathBuf, String> {
-    provider_store::get_provider_icon_cache_path(provider_id)
-}
-
+/// 获取供应商状态列表，sort 可选 "name"（默认）或 "configured_first"
 #[tauri::command]
-pub fn get_provider_status() -> Result<Vec<ProviderInfo>, String> {
-    provider_service::get_provider_status()
+pub fn get_provider_status(sort: Option<String>) -> Result<Vec<ProviderInfo>, String> {
+    provider_service::get_provider_status(sort.as_deref().unwrap_or("name"))
 }
 
 #[tauri::command]
@@ -55,8 +61,9 @@ pub fn test_provider_connection(
     npm: String,
     base_url: Option<String>,
     api_key: String,
+    timeout_secs: Option<u64>,
 ) -> Result<ConnectionTestResult, String> {
-    provider_service::test_provider_connection(npm, base_url, api_key)
+    provider_service::test_provider_connection(npm, base_url, api_key, timeout_secs)
 }
 
 #[tauri::command]
@@ -65,13 +72,40 @@ pub fn set_provider_api_key(
     api_key: String,
     base_url: Option<String>,
     provider_type: Option<String>,
-) -> Result<(), String> {
+) -> Result<ApiKeySaveResult, String> {
     provider_service::set_provider_api_key(provider_id, api_key, base_url, provider_type)
 }
 
+/// 从 `{prefix}_<PROVIDER>` 环境变量批量导入 API Key 到 auth.json
+#[tauri::command]
+pub fn import_keys_from_env(prefix: String) -> Result<EnvKeyImportResult, String> {
+    provider_service::import_keys_from_env(prefix)
+}
+
+#[tauri::command]
+pub fn delete_provider_auth(provider_id: String, remove_from_config: bool) -> Result<(), String> {
+    provider_service::delete_provider_auth(provider_id, remove_from_config)
+}
+
+#[tauri::command]
+pub fn disable_provider(provider_id: String) -> Result<(), String> {
+    provider_service::disable_provider(provider_id)
+}
+
+#[tauri::command]
+pub fn enable_provider(provider_id: String) -> Result<(), String> {
+    provider_service::enable_provider(provider_id)
+}
+
 #[tauri::command]
-pub fn delete_provider_auth(provider_id: String) -> Result<(), String> {
-    provider_service::delete_provider_auth(provider_id)
+pub fn list_disabled_providers() -> Result<Vec<String>, String> {
+    Ok(provider_service::list_disabled_providers())
+}
+
+/// 诊断各供应商在 auth.json / connected-providers.json / provider-models.json 中的状态
+#[tauri::command]
+pub fn diagnose_providers() -> Result<Vec<ProviderDiagnostic>, String> {
+    Ok(provider_service::diagnose_providers())
 }
 
 #[tauri::command]
@@ -79,8 +113,19 @@ pub fn add_custom_provider(
     name: String,
     api_key: String,
     base_url: String,
+    npm: Option<String>,
 ) -> Result<ProviderInfo, String> {
-    provider_service::add_custom_provider(name, api_key, base_url)
+    provider_service::add_custom_provider(name, api_key, base_url, npm)
+}
+
+#[tauri::command]
+pub fn update_custom_provider(
+    provider_id: String,
+    name: String,
+    api_key: String,
+    base_url: String,
+) -> Result<ProviderInfo, String> {
+    provider_service::update_custom_provider(provider_id, name, api_key, base_url)
 }
 
 #[tauri::command]
@@ -145,12 +190,79 @@ pub fn get_custom_models() -> Result<HashMap<String, Vec<String>>, String> {
     Ok(provider_store::get_custom_models())
 }
 
+/// 下载图标，返回字节内容以及响应的 Content-Type；非 200 或空响应视为失败
+fn download_provider_icon(url: &str) -> Option<(Vec<u8>, String)> {
+    use std::io::Read;
+
+    let resp = ureq::get(url)
+        .timeout(std::time::Duration::from_secs(5))
+        .call()
+        .ok()?;
+    if resp.status() != 200 {
+        return None;
+    }
+
+    let content_type = resp.content_type().to_string();
+    let mut bytes = Vec::new();
+    resp.into_reader().read_to_end(&mut bytes).ok()?;
+    (!bytes.is_empty()).then_some((bytes, content_type))
+}
+
+/// 根据文件头（SVG 额外参考 Content-Type）判断图标格式并返回缓存扩展名；
+/// 无法识别的内容（如被重定向到的 HTML 页面）返回 None
+fn detect_provider_icon_extension(bytes: &[u8], content_type: &str) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some("png");
+    }
+    if bytes.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        return Some("ico");
+    }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+    let head = head.trim_start();
+    if content_type.contains("svg") || head.starts_with("<svg") || head.starts_with("<?xml") {
+        return Some("svg");
+    }
+    None
+}
+
+/// 将内置占位图标写入缓存目录之外的固定位置并返回其路径
+fn ensure_placeholder_icon() -> Result<std::path::PathBuf, String> {
+    let path = provider_store::get_provider_icon_placeholder_path()?;
+    let is_valid = std::fs::metadata(&path).is_ok_and(|m| m.len() > 0);
+    if !is_valid {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        std::fs::write(&path, PROVIDER_ICON_PLACEHOLDER)
+            .map_err(|e| format!("写入占位图标失败: {}", e))?;
+    }
+    Ok(path)
+}
+
+/// 缓存文件的 mtime 超过有效期视为过期；无法读取 mtime 时按未过期处理
+fn is_provider_icon_stale(path: &std::path::Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| std::time::SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > PROVIDER_ICON_CACHE_TTL)
+}
+
+/// 获取供应商图标：未过期缓存 → Clearbit → 站点 favicon → 旧缓存 → 内置占位图标
 #[tauri::command]
 pub fn get_provider_icon(provider_id: String) -> Result<Option<String>, String> {
-    let cache_path = get_provider_icon_cache_path(&provider_id)?;
-    if cache_path.exists() {
+    get_provider_icon_with(&provider_id, download_provider_icon)
+}
+
+fn get_provider_icon_with<D>(provider_id: &str, download: D) -> Result<Option<String>, String>
+where
+    D: Fn(&str) -> Option<(Vec<u8>, String)>,
+{
+    let cached = provider_store::find_cached_provider_icon(provider_id)?;
+    if let Some(cache_path) = cached.as_ref().filter(|path| !is_provider_icon_stale(path)) {
         return Ok(Some(cache_path.to_string_lossy().to_string()));
     }
+    let cached = cached.map(|path| path.to_string_lossy().to_string());
 
     let domain = PROVIDER_DOMAINS
         .iter()
@@ -158,31 +270,58 @@ pub fn get_provider_icon(provider_id: String) -> Result<Option<String>, String>
         .map(|(_, domain)| *domain);
 
     let Some(domain) = domain else {
-        return Ok(None);
+        return Ok(cached);
     };
 
-    let url = format!("https://logo.clearbit.com/{}?size=64", domain);
-    let response = ureq::get(&url)
-        .timeout(std::time::Duration::from_secs(5))
-        .call();
-
-    match response {
-        Ok(resp) if resp.status() == 200 => {
-            use std::io::Read;
-            let mut bytes = Vec::new();
-            resp.into_reader()
-                .read_to_end(&mut bytes)
-                .map_err(|e| format!("读取响应失败: {}", e))?;
-
-            if let Some(parent) = cache_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-
-            std::fs::write(&cache_path, &bytes).map_err(|e| format!("写入缓存失败: {}", e))?;
-            Ok(Some(cache_path.to_string_lossy().to_string()))
+    let urls = [
+        format!("https://logo.clearbit.com/{}?size=64", domain),
+        format!("https://{}/favicon.ico", domain),
+    ];
+    let downloaded = urls.iter().find_map(|url| {
+        let (bytes, content_type) = download(url)?;
+        let ext = detect_provider_icon_extension(&bytes, &content_type)?;
+        Some((bytes, ext))
+    });
+    let Some((bytes, ext)) = downloaded else {
+        // 重新下载失败时继续沿用过期的旧缓存
+        if cached.is_some() {
+            return Ok(cached);
         }
-        _ => Ok(None),
+        let placeholder = ensure_placeholder_icon()?;
+        return Ok(Some(placeholder.to_string_lossy().to_string()));
+    };
+
+    let cache_path = provider_store::get_provider_icon_cache_path(provider_id, ext)?;
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    std::fs::write(&cache_path, &bytes).map_err(|e| format!("写入缓存失败: {}", e))?;
+    // 新图标格式变化时移除旧格式的缓存，避免下次仍命中旧文件
+    if let Some(old_path) = cached.filter(|old| *old != cache_path.to_string_lossy()) {
+        let _ = std::fs::remove_file(old_path);
     }
+    Ok(Some(cache_path.to_string_lossy().to_string()))
+}
+
+/// 并发请求所有自定义供应商的 /models 接口，并将发现的模型合并到 opencode.json
+#[tauri::command]
+pub async fn refresh_all_custom_provider_models() -> Result<HashMap<String, Vec<String>>, String> {
+    tokio::task::spawn_blocking(provider_service::refresh_all_custom_provider_models)
+        .await
+        .map_err(|e| format!("刷新自定义供应商模型失败: {}", e))?
+}
+
+/// 清空供应商图标缓存，返回删除的图标数量
+#[tauri::command]
+pub fn clear_provider_icon_cache() -> Result<usize, String> {
+    provider_store::clear_provider_icon_cache()
+}
+
+/// 获取供应商图标缓存占用的字节数
+#[tauri::command]
+pub fn get_provider_icon_cache_size() -> Result<u64, String> {
+    provider_store::get_provider_icon_cache_size()
 }
 
 #[cfg(test)]
@@ -202,6 +341,7 @@ mod tests {
             supports_base_url: true,
             supports_connection_test: true,
             can_delete_auth: true,
+            is_disabled: false,
         };
 
         let json = serde_json::to_string(&provider).unwrap();
@@ -263,6 +403,7 @@ mod tests {
             "@ai-sdk/openai".to_string(),
             Some("ftp://invalid.example.com".to_string()),
             "sk-test".to_string(),
+            None,
         )
         .unwrap();
 
@@ -276,12 +417,44 @@ mod tests {
             "@ai-sdk/openai".to_string(),
             Some("https://api.openai.com/v1".to_string()),
             "sk-test".to_string(),
+            None,
         )
         .unwrap();
 
         assert!(result.success);
     }
 
+    #[test]
+    #[serial]
+    fn test_add_custom_model_on_fresh_config() {
+        let temp_dir = std::env::temp_dir().join("omo_test_add_custom_model_fresh");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).expect("创建临时目录失败");
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let result = add_custom_model("openai".to_string(), "gpt-5-custom".to_string());
+        let custom_models = provider_store::get_custom_models();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(result.is_ok(), "全新文件追加自定义模型应成功: {:?}", result);
+        assert_eq!(
+            custom_models.get("openai").cloned(),
+            Some(vec!["gpt-5-custom".to_string()])
+        );
+    }
+
     #[test]
     #[serial]
     fn test_get_provider_status_graceful_when_auth_invalid() {
@@ -311,7 +484,7 @@ mod tests {
         std::fs::create_dir_all(&auth_dir).expect("创建 auth 目录失败");
         std::fs::write(auth_dir.join("auth.json"), "{invalid json").expect("写入 auth.json 失败");
 
-        let result = get_provider_status();
+        let result = get_provider_status(None);
 
         unsafe {
             if let Some(home) = original_home {
@@ -488,4 +661,194 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    #[serial]
+    fn test_get_provider_icon_refetches_empty_cache_file() {
+        let temp_dir = std::env::temp_dir().join("omo_test_provider_icon_empty_cache");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).expect("创建临时目录失败");
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let icon_dir = provider_store::get_provider_icon_cache_dir().unwrap();
+        std::fs::create_dir_all(&icon_dir).unwrap();
+        let empty_icon = icon_dir.join("openai.png");
+        std::fs::write(&empty_icon, []).unwrap();
+        let svg_icon = icon_dir.join("anthropic.svg");
+        std::fs::write(&svg_icon, "<svg/>").unwrap();
+
+        // 空缓存文件视为未命中，应重新下载并写回非空内容
+        let downloads = std::cell::Cell::new(0);
+        let empty_result = get_provider_icon_with("openai", |_| {
+            downloads.set(downloads.get() + 1);
+            Some((png_bytes(b"png-bytes"), "image/png".to_string()))
+        });
+        let refetched_content = std::fs::read(&empty_icon).unwrap_or_default();
+        // 有效缓存直接命中，不触发下载
+        let svg_result = get_provider_icon_with("anthropic", |_| panic!("不应重新下载"));
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(
+            empty_result.unwrap(),
+            Some(empty_icon.to_string_lossy().to_string())
+        );
+        assert_eq!(downloads.get(), 1);
+        assert_eq!(refetched_content, png_bytes(b"png-bytes"));
+        assert_eq!(
+            svg_result.unwrap(),
+            Some(svg_icon.to_string_lossy().to_string())
+        );
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_provider_icon_refreshes_stale_cache() {
+        let temp_dir = std::env::temp_dir().join("omo_test_provider_icon_stale_cache");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).expect("创建临时目录失败");
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let icon_dir = provider_store::get_provider_icon_cache_dir().unwrap();
+        std::fs::create_dir_all(&icon_dir).unwrap();
+        let icon_path = icon_dir.join("openai.png");
+        let expire = |path: &std::path::Path| {
+            let old = std::time::SystemTime::now()
+                - PROVIDER_ICON_CACHE_TTL
+                - std::time::Duration::from_secs(60);
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+        };
+        std::fs::write(&icon_path, png_bytes(b"old")).unwrap();
+
+        // 未过期的缓存直接命中，不触发下载
+        let fresh = get_provider_icon_with("openai", |_| panic!("不应重新下载"));
+        // 过期后重新下载并覆盖缓存
+        expire(&icon_path);
+        let refreshed = get_provider_icon_with("openai", |_| {
+            Some((png_bytes(b"new"), "image/png".to_string()))
+        });
+        let refreshed_content = std::fs::read(&icon_path).unwrap();
+        // 再次过期且下载失败时沿用旧文件
+        expire(&icon_path);
+        let fallback = get_provider_icon_with("openai", |_| None);
+        let fallback_content = std::fs::read(&icon_path).unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let expected = Some(icon_path.to_string_lossy().to_string());
+        assert_eq!(fresh.unwrap(), expected);
+        assert_eq!(refreshed.unwrap(), expected);
+        assert_eq!(refreshed_content, png_bytes(b"new"));
+        assert_eq!(fallback.unwrap(), expected);
+        assert_eq!(fallback_content, png_bytes(b"new"));
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_detect_provider_icon_extension() {
+        assert_eq!(
+            detect_provider_icon_extension(&png_bytes(b""), "application/octet-stream"),
+            Some("png")
+        );
+        assert_eq!(
+            detect_provider_icon_extension(&[0, 0, 1, 0, 1, 0], "image/x-icon"),
+            Some("ico")
+        );
+        assert_eq!(
+            detect_provider_icon_extension(b"  <svg xmlns=\"http://www.w3.org/2000/svg\"/>", ""),
+            Some("svg")
+        );
+        assert_eq!(
+            detect_provider_icon_extension(b"<!doctype html><html></html>", "text/html"),
+            None
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_provider_icon_keeps_favicon_format_and_placeholder_out_of_cache() {
+        let temp_dir = std::env::temp_dir().join("omo_test_provider_icon_format");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).expect("创建临时目录失败");
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let icon_dir = provider_store::get_provider_icon_cache_dir().unwrap();
+        let ico = vec![0u8, 0, 1, 0, 1, 0];
+        // Clearbit 返回无法识别的 HTML 时回退到 favicon，并以 .ico 保存
+        let ico_result = get_provider_icon_with("openai", |url| {
+            if url.ends_with("favicon.ico") {
+                Some((ico.clone(), "image/x-icon".to_string()))
+            } else {
+                Some((b"<html></html>".to_vec(), "text/html".to_string()))
+            }
+        });
+        let ico_content = std::fs::read(icon_dir.join("openai.ico")).unwrap_or_default();
+        // 全部下载失败时返回占位图标，占位图标不计入缓存统计
+        let placeholder_result = get_provider_icon_with("anthropic", |_| None);
+        let cache_size = provider_store::get_provider_icon_cache_size();
+        let cleared = provider_store::clear_provider_icon_cache();
+        let placeholder_path = provider_store::get_provider_icon_placeholder_path().unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(
+            ico_result.unwrap(),
+            Some(icon_dir.join("openai.ico").to_string_lossy().to_string())
+        );
+        assert_eq!(ico_content, ico);
+        assert!(!icon_dir.join("openai.png").exists());
+        assert_eq!(
+            placeholder_result.unwrap(),
+            Some(placeholder_path.to_string_lossy().to_string())
+        );
+        assert_eq!(cache_size.unwrap(), ico.len() as u64);
+        assert_eq!(cleared.unwrap(), 1);
+        assert!(placeholder_path.exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    fn png_bytes(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
 }
@@ -0,0 +1,21 @@
+use crate::services::repair_service::{self, HealthReport, MigrationReport, RepairReport};
+
+/// 一键检查并修复常见问题（配置初始化、variant 清理、预设标记迁移、临时文件与缓存校验）
+#[tauri::command]
+pub async fn repair_all() -> Result<RepairReport, String> {
+    tokio::task::spawn_blocking(repair_service::repair_all)
+        .await
+        .map_err(|e| format!("修复失败: {}", e))?
+}
+
+/// 将配置中已废弃的 agent 名迁移到新名，迁移前自动备份
+#[tauri::command]
+pub fn migrate_config() -> Result<MigrationReport, String> {
+    repair_service::migrate_config()
+}
+
+/// 只读的配置健康检查汇总
+#[tauri::command]
+pub fn config_health_check() -> Result<HealthReport, String> {
+    repair_service::config_health_check()
+}
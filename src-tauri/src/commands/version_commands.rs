@@ -1,4 +1,4 @@
-use crate::services::version_service::{self, VersionInfo};
+use crate::services::version_service::{self, ConfigCompatibility, VersionInfo};
 
 /// 检查所有版本信息（异步）
 /// 
@@ -12,3 +12,17 @@ pub async fn check_versions() -> Result<Vec<VersionInfo>, String> {
     .await
     .map_err(|e| format!("版本检测失败: {}", e))
 }
+
+/// 强制检查所有版本信息（跳过本地缓存）
+#[tauri::command]
+pub async fn check_versions_force() -> Result<Vec<VersionInfo>, String> {
+    tokio::task::spawn_blocking(version_service::check_all_versions_force)
+        .await
+        .map_err(|e| format!("版本检测失败: {}", e))
+}
+
+/// 检查 opencode.json 中的插件版本与 oh-my-opencode.json 的 schema 版本是否匹配
+#[tauri::command]
+pub fn check_config_compatibility() -> ConfigCompatibility {
+    version_service::check_config_compatibility()
+}
@@ -1,9 +1,21 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Mutex;
 
 // 全局语言设置（使用 Mutex 保证线程安全）
 lazy_static::lazy_static! {
     static ref CURRENT_LOCALE: Mutex<String> = Mutex::new("zh-CN".to_string());
+    // 外部覆盖翻译，按语言在首次访问时加载一次
+    static ref TRANSLATION_OVERRIDES: Mutex<HashMap<String, HashMap<String, String>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// 应用支持的语言代码
+pub const SUPPORTED_LOCALES: [&str; 7] = ["zh-CN", "zh-TW", "en", "ja", "ko", "es", "fr"];
+
+/// 是否为应用支持的语言代码
+pub fn is_supported_locale(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale)
 }
 
 /// 获取当前语言设置
@@ -17,8 +29,8 @@ pub fn get_locale() -> String {
         .clone()
 }
 
-/// 设置当前语言设置
-pub fn set_locale(locale: &str) {
+/// 仅更新内存中的语言设置，不写入磁盘
+fn apply_locale(locale: &str) {
     let mut guard = CURRENT_LOCALE.lock().unwrap_or_else(|e| {
         eprintln!("设置语言时 Mutex 中毒，恢复默认值: {}", e);
         e.into_inner()
@@ -26,6 +38,78 @@ pub fn set_locale(locale: &str) {
     *guard = locale.to_string();
 }
 
+/// 设置当前语言设置，并持久化到 ~/.config/OMO-Switch/locale 以便重启后恢复
+/// 不支持的语言代码直接拒绝，既不应用也不写入磁盘
+pub fn set_locale(locale: &str) -> Result<(), String> {
+    if !is_supported_locale(locale) {
+        return Err(format!("不支持的语言: {}", locale));
+    }
+    apply_locale(locale);
+    if let Err(e) = write_persisted_locale(locale) {
+        eprintln!("保存语言设置失败: {}", e);
+    }
+    Ok(())
+}
+
+/// 持久化语言文件: ~/.config/OMO-Switch/locale
+fn get_locale_file_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("OMO-Switch")
+            .join("locale"),
+    )
+}
+
+fn write_persisted_locale(locale: &str) -> Result<(), String> {
+    let path = get_locale_file_path().ok_or_else(|| "无法获取 HOME 环境变量".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, locale).map_err(|e| e.to_string())
+}
+
+/// 读取持久化的语言设置，文件不存在、内容为空或语言不受支持时返回 None
+fn read_persisted_locale() -> Option<String> {
+    let content = std::fs::read_to_string(get_locale_file_path()?).ok()?;
+    let locale = content.trim();
+    is_supported_locale(locale).then(|| locale.to_string())
+}
+
+/// 将 LANG 等环境变量值（如 "zh_TW.UTF-8"）映射为应用支持的语言代码
+fn locale_from_env_value(value: &str) -> Option<&'static str> {
+    let language = value.split('.').next().unwrap_or_default().to_lowercase();
+    if language.starts_with("zh") {
+        let traditional = ["zh_tw", "zh-tw", "zh_hk", "zh-hk", "zh_hant", "zh-hant"];
+        if traditional
+            .iter()
+            .any(|prefix| language.starts_with(prefix))
+        {
+            Some("zh-TW")
+        } else {
+            Some("zh-CN")
+        }
+    } else {
+        ["en", "ja", "ko", "es", "fr"]
+            .into_iter()
+            .find(|code| language.starts_with(code))
+    }
+}
+
+/// 启动时初始化语言设置：优先读取持久化文件，无文件时按系统 LANG 推断，均无结果时保持 zh-CN
+pub fn init_locale() {
+    let locale = read_persisted_locale().or_else(|| {
+        std::env::var("LANG")
+            .ok()
+            .and_then(|lang| locale_from_env_value(&lang))
+            .map(str::to_string)
+    });
+    if let Some(locale) = locale {
+        apply_locale(&locale);
+    }
+}
+
 /// 翻译错误消息
 ///
 /// # 参数
@@ -33,23 +117,27 @@ pub fn set_locale(locale: &str) {
 /// - `locale`: 语言代码（如 "zh-CN", "en", "ja", "ko", "zh-TW"）
 ///
 /// # 返回
-/// 翻译后的错误消息，如果键不存在则返回键本身
+/// 翻译后的错误消息，缺少翻译时回退到英文，英文也不存在则返回键本身
 pub fn tr(key: &str, locale: &str) -> String {
+    if let Some(value) = get_translation_override(key, locale) {
+        return value;
+    }
+
     let translations = get_translations();
 
-    if let Some(locale_map) = translations.get(locale) {
-        locale_map
-            .get(key)
-            .cloned()
-            .unwrap_or_else(|| key.to_string())
-    } else {
-        // 如果指定的语言不存在，回退到英文
-        if let Some(en_map) = translations.get("en") {
-            en_map.get(key).cloned().unwrap_or_else(|| key.to_string())
-        } else {
+    // 指定语言不存在或缺少该键时，回退到英文
+    translations
+        .get(locale)
+        .and_then(|locale_map| locale_map.get(key))
+        .or_else(|| translations.get("en").and_then(|en_map| en_map.get(key)))
+        .cloned()
+        .unwrap_or_else(|| {
+            // 开发构建中提示遗漏的翻译，避免界面静默显示机器键名
+            if cfg!(debug_assertions) {
+                eprintln!("[i18n] 缺少翻译: key={}, locale={}", key, locale);
+            }
             key.to_string()
-        }
-    }
+        })
 }
 
 /// 使用当前全局语言设置翻译错误消息
@@ -58,6 +146,60 @@ pub fn tr_current(key: &str) -> String {
     tr(key, &locale)
 }
 
+/// 外部翻译目录: ~/.config/OMO-Switch/translations/
+fn get_translations_dir() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("OMO-Switch")
+            .join("translations"),
+    )
+}
+
+/// 读取 {locale}.json 中的字符串键值，文件不存在或格式错误时返回空映射
+fn load_translation_overrides(locale: &str) -> HashMap<String, String> {
+    let Some(path) = get_translations_dir().map(|dir| dir.join(format!("{}.json", locale))) else {
+        return HashMap::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return HashMap::new();
+    };
+
+    match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content) {
+        Ok(map) => map
+            .into_iter()
+            .filter_map(|(key, value)| value.as_str().map(|v| (key, v.to_string())))
+            .collect(),
+        Err(e) => {
+            eprintln!("解析外部翻译文件 {:?} 失败，已忽略: {}", path, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// 查询外部覆盖翻译，首次访问某语言时加载并缓存
+fn get_translation_override(key: &str, locale: &str) -> Option<String> {
+    let mut overrides = TRANSLATION_OVERRIDES.lock().unwrap_or_else(|e| {
+        eprintln!("读取外部翻译时 Mutex 中毒，继续使用缓存: {}", e);
+        e.into_inner()
+    });
+    overrides
+        .entry(locale.to_string())
+        .or_insert_with(|| load_translation_overrides(locale))
+        .get(key)
+        .cloned()
+}
+
+/// 清空外部翻译缓存，下次访问时重新读取文件
+pub fn reload_translation_overrides() {
+    let mut overrides = TRANSLATION_OVERRIDES.lock().unwrap_or_else(|e| {
+        eprintln!("清空外部翻译缓存时 Mutex 中毒: {}", e);
+        e.into_inner()
+    });
+    overrides.clear();
+}
+
 /// 获取所有翻译映射
 fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
     let mut translations: HashMap<&'static str, HashMap<&'static str, String>> = HashMap::new();
@@ -116,6 +258,14 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
     zh_cn.insert("delete_preset_failed", "删除预设失败".to_string());
     zh_cn.insert("backup_config_failed", "备份配置失败".to_string());
     zh_cn.insert("json_format_error", "JSON 格式错误".to_string());
+    zh_cn.insert(
+        "config_lock_timeout",
+        "等待配置文件锁超时，可能有其他进程正在写入配置".to_string(),
+    );
+    zh_cn.insert(
+        "config_write_verify_failed",
+        "配置写入后校验失败，已回滚到写入前的内容".to_string(),
+    );
     translations.insert("zh-CN", zh_cn);
 
     // 中文繁体 (zh-TW)
@@ -172,6 +322,14 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
     zh_tw.insert("delete_preset_failed", "刪除預設失敗".to_string());
     zh_tw.insert("backup_config_failed", "備份設定失敗".to_string());
     zh_tw.insert("json_format_error", "JSON 格式錯誤".to_string());
+    zh_tw.insert(
+        "config_lock_timeout",
+        "等待設定檔鎖定逾時，可能有其他程序正在寫入設定".to_string(),
+    );
+    zh_tw.insert(
+        "config_write_verify_failed",
+        "設定寫入後驗證失敗，已回復為寫入前的內容".to_string(),
+    );
     translations.insert("zh-TW", zh_tw);
 
     // English (en)
@@ -282,6 +440,15 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
         "Failed to backup configuration".to_string(),
     );
     en.insert("json_format_error", "JSON format error".to_string());
+    en.insert(
+        "config_lock_timeout",
+        "Timed out waiting for the configuration file lock; another process may be writing the configuration".to_string(),
+    );
+    en.insert(
+        "config_write_verify_failed",
+        "Configuration verification failed after writing; the previous content has been restored"
+            .to_string(),
+    );
     translations.insert("en", en);
 
     // Japanese (ja)
@@ -395,6 +562,14 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
         "設定のバックアップに失敗しました".to_string(),
     );
     ja.insert("json_format_error", "JSON形式エラー".to_string());
+    ja.insert(
+        "config_lock_timeout",
+        "設定ファイルのロック待機がタイムアウトしました。別のプロセスが設定を書き込んでいる可能性があります".to_string(),
+    );
+    ja.insert(
+        "config_write_verify_failed",
+        "書き込み後の設定の検証に失敗したため、書き込み前の内容に戻しました".to_string(),
+    );
     translations.insert("ja", ja);
 
     // Korean (ko)
@@ -491,6 +666,11 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
         "preset_not_found",
         "사전 설정을 찾을 수 없습니다".to_string(),
     );
+    ko.insert("preset_official_default", "공식 기본값".to_string());
+    ko.insert("preset_economy", "이코노미".to_string());
+    ko.insert("preset_high_performance", "고성능".to_string());
+    ko.insert("tray_presets", "💾 사전 설정".to_string());
+    ko.insert("tray_current_preset", "현재".to_string());
     ko.insert(
         "read_preset_file_failed",
         "사전 설정 파일을 읽지 못했습니다".to_string(),
@@ -508,8 +688,266 @@ fn get_translations() -> HashMap<&'static str, HashMap<&'static str, String>> {
         "구성을 백업하지 못했습니다".to_string(),
     );
     ko.insert("json_format_error", "JSON 형식 오류".to_string());
+    ko.insert(
+        "config_lock_timeout",
+        "구성 파일 잠금 대기 시간이 초과되었습니다. 다른 프로세스가 구성을 쓰고 있을 수 있습니다"
+            .to_string(),
+    );
+    ko.insert(
+        "config_write_verify_failed",
+        "구성을 쓴 후 검증에 실패하여 이전 내용으로 복원했습니다".to_string(),
+    );
     translations.insert("ko", ko);
 
+    // Spanish (es)
+    let mut es = HashMap::new();
+    es.insert(
+        "home_env_var_error",
+        "No se pudo obtener la variable de entorno HOME".to_string(),
+    );
+    es.insert(
+        "config_file_not_found",
+        "Archivo de configuración no encontrado".to_string(),
+    );
+    es.insert(
+        "read_config_failed",
+        "No se pudo leer el archivo de configuración".to_string(),
+    );
+    es.insert(
+        "parse_json_failed",
+        "No se pudo analizar el JSON".to_string(),
+    );
+    es.insert(
+        "create_backup_failed",
+        "No se pudo crear el archivo de copia de seguridad".to_string(),
+    );
+    es.insert(
+        "create_config_dir_failed",
+        "No se pudo crear el directorio de configuración".to_string(),
+    );
+    es.insert(
+        "serialize_json_failed",
+        "No se pudo serializar el JSON".to_string(),
+    );
+    es.insert(
+        "write_config_failed",
+        "No se pudo escribir el archivo de configuración".to_string(),
+    );
+    es.insert(
+        "config_root_must_be_object",
+        "La raíz de la configuración debe ser un objeto".to_string(),
+    );
+    es.insert(
+        "config_missing_agents",
+        "Falta el campo 'agents' en la configuración".to_string(),
+    );
+    es.insert(
+        "config_missing_categories",
+        "Falta el campo 'categories' en la configuración".to_string(),
+    );
+    es.insert(
+        "read_model_cache_failed",
+        "No se pudo leer el archivo de caché de modelos".to_string(),
+    );
+    es.insert(
+        "parse_model_cache_failed",
+        "No se pudo analizar el archivo de caché de modelos".to_string(),
+    );
+    es.insert(
+        "create_target_dir_failed",
+        "No se pudo crear el directorio de destino".to_string(),
+    );
+    es.insert(
+        "write_export_file_failed",
+        "No se pudo escribir el archivo de exportación".to_string(),
+    );
+    es.insert(
+        "import_file_not_found",
+        "Archivo de importación no encontrado".to_string(),
+    );
+    es.insert(
+        "read_import_file_failed",
+        "No se pudo leer el archivo de importación".to_string(),
+    );
+    es.insert(
+        "parse_import_file_failed",
+        "No se pudo analizar el archivo de importación".to_string(),
+    );
+    es.insert(
+        "preset_name_empty",
+        "El nombre del preajuste no puede estar vacío".to_string(),
+    );
+    es.insert(
+        "preset_name_invalid_path",
+        "El nombre del preajuste no puede contener separadores de ruta".to_string(),
+    );
+    es.insert(
+        "create_preset_dir_failed",
+        "No se pudo crear el directorio de preajustes".to_string(),
+    );
+    es.insert(
+        "write_preset_file_failed",
+        "No se pudo escribir el archivo del preajuste".to_string(),
+    );
+    es.insert("preset_not_found", "Preajuste no encontrado".to_string());
+    es.insert(
+        "preset_official_default",
+        "Predeterminado oficial".to_string(),
+    );
+    es.insert("preset_economy", "Económico".to_string());
+    es.insert("preset_high_performance", "Alto rendimiento".to_string());
+    es.insert("tray_presets", "💾 Preajustes".to_string());
+    es.insert("tray_current_preset", "Actual".to_string());
+    es.insert(
+        "read_preset_file_failed",
+        "No se pudo leer el archivo del preajuste".to_string(),
+    );
+    es.insert(
+        "parse_preset_file_failed",
+        "No se pudo analizar el archivo del preajuste".to_string(),
+    );
+    es.insert(
+        "delete_preset_failed",
+        "No se pudo eliminar el preajuste".to_string(),
+    );
+    es.insert(
+        "backup_config_failed",
+        "No se pudo hacer copia de seguridad de la configuración".to_string(),
+    );
+    es.insert("json_format_error", "Error de formato JSON".to_string());
+    es.insert(
+        "config_lock_timeout",
+        "Se agotó el tiempo de espera del bloqueo del archivo de configuración; otro proceso puede estar escribiéndolo".to_string(),
+    );
+    es.insert(
+        "config_write_verify_failed",
+        "La verificación de la configuración falló tras escribirla; se restauró el contenido anterior".to_string(),
+    );
+    translations.insert("es", es);
+
+    // French (fr)
+    let mut fr = HashMap::new();
+    fr.insert(
+        "home_env_var_error",
+        "Impossible d'obtenir la variable d'environnement HOME".to_string(),
+    );
+    fr.insert(
+        "config_file_not_found",
+        "Fichier de configuration introuvable".to_string(),
+    );
+    fr.insert(
+        "read_config_failed",
+        "Échec de la lecture du fichier de configuration".to_string(),
+    );
+    fr.insert(
+        "parse_json_failed",
+        "Échec de l'analyse du JSON".to_string(),
+    );
+    fr.insert(
+        "create_backup_failed",
+        "Échec de la création du fichier de sauvegarde".to_string(),
+    );
+    fr.insert(
+        "create_config_dir_failed",
+        "Échec de la création du répertoire de configuration".to_string(),
+    );
+    fr.insert(
+        "serialize_json_failed",
+        "Échec de la sérialisation du JSON".to_string(),
+    );
+    fr.insert(
+        "write_config_failed",
+        "Échec de l'écriture du fichier de configuration".to_string(),
+    );
+    fr.insert(
+        "config_root_must_be_object",
+        "La racine de la configuration doit être un objet".to_string(),
+    );
+    fr.insert(
+        "config_missing_agents",
+        "Champ 'agents' manquant dans la configuration".to_string(),
+    );
+    fr.insert(
+        "config_missing_categories",
+        "Champ 'categories' manquant dans la configuration".to_string(),
+    );
+    fr.insert(
+        "read_model_cache_failed",
+        "Échec de la lecture du cache des modèles".to_string(),
+    );
+    fr.insert(
+        "parse_model_cache_failed",
+        "Échec de l'analyse du cache des modèles".to_string(),
+    );
+    fr.insert(
+        "create_target_dir_failed",
+        "Échec de la création du répertoire cible".to_string(),
+    );
+    fr.insert(
+        "write_export_file_failed",
+        "Échec de l'écriture du fichier d'export".to_string(),
+    );
+    fr.insert(
+        "import_file_not_found",
+        "Fichier d'import introuvable".to_string(),
+    );
+    fr.insert(
+        "read_import_file_failed",
+        "Échec de la lecture du fichier d'import".to_string(),
+    );
+    fr.insert(
+        "parse_import_file_failed",
+        "Échec de l'analyse du fichier d'import".to_string(),
+    );
+    fr.insert(
+        "preset_name_empty",
+        "Le nom du préréglage ne peut pas être vide".to_string(),
+    );
+    fr.insert(
+        "preset_name_invalid_path",
+        "Le nom du préréglage ne peut pas contenir de séparateurs de chemin".to_string(),
+    );
+    fr.insert(
+        "create_preset_dir_failed",
+        "Échec de la création du répertoire des préréglages".to_string(),
+    );
+    fr.insert(
+        "write_preset_file_failed",
+        "Échec de l'écriture du fichier de préréglage".to_string(),
+    );
+    fr.insert("preset_not_found", "Préréglage introuvable".to_string());
+    fr.insert("preset_official_default", "Par défaut officiel".to_string());
+    fr.insert("preset_economy", "Économique".to_string());
+    fr.insert("preset_high_performance", "Haute performance".to_string());
+    fr.insert("tray_presets", "💾 Préréglages".to_string());
+    fr.insert("tray_current_preset", "Actuel".to_string());
+    fr.insert(
+        "read_preset_file_failed",
+        "Échec de la lecture du fichier de préréglage".to_string(),
+    );
+    fr.insert(
+        "parse_preset_file_failed",
+        "Échec de l'analyse du fichier de préréglage".to_string(),
+    );
+    fr.insert(
+        "delete_preset_failed",
+        "Échec de la suppression du préréglage".to_string(),
+    );
+    fr.insert(
+        "backup_config_failed",
+        "Échec de la sauvegarde de la configuration".to_string(),
+    );
+    fr.insert("json_format_error", "Erreur de format JSON".to_string());
+    fr.insert(
+        "config_lock_timeout",
+        "Délai d'attente du verrou du fichier de configuration dépassé ; un autre processus écrit peut-être la configuration".to_string(),
+    );
+    fr.insert(
+        "config_write_verify_failed",
+        "La vérification de la configuration a échoué après l'écriture ; le contenu précédent a été restauré".to_string(),
+    );
+    translations.insert("fr", fr);
+
     translations
 }
 
@@ -547,11 +985,34 @@ mod tests {
         assert_eq!(result, "設定檔不存在");
     }
 
+    #[test]
+    fn test_tr_es_fr() {
+        assert_eq!(
+            tr("config_file_not_found", "es"),
+            "Archivo de configuración no encontrado"
+        );
+        assert_eq!(
+            tr("config_file_not_found", "fr"),
+            "Fichier de configuration introuvable"
+        );
+
+        // ja/ko/es/fr 应覆盖英文的全部键
+        let translations = get_translations();
+        let en_keys: std::collections::HashSet<_> = translations["en"].keys().collect();
+        for locale in ["ja", "ko", "es", "fr"] {
+            let keys: std::collections::HashSet<_> = translations[locale].keys().collect();
+            assert_eq!(keys, en_keys, "{} should translate every en key", locale);
+        }
+    }
+
     #[test]
     fn test_tr_fallback_to_en() {
         // 测试不存在的语言时是否回退到英文
-        let result = tr("config_file_not_found", "fr");
+        let result = tr("config_file_not_found", "de");
         assert_eq!(result, "Configuration file not found");
+
+        let result = tr("tray_current_preset", "pt-BR");
+        assert_eq!(result, "Current");
     }
 
     #[test]
@@ -559,28 +1020,135 @@ mod tests {
         // 测试不存在的键是否返回键本身
         let result = tr("unknown_key", "zh-CN");
         assert_eq!(result, "unknown_key");
+
+        // debug 构建下会输出告警，但返回值保持为键本身
+        let result = tr("unknown_key", "de");
+        assert_eq!(result, "unknown_key");
     }
 
     #[test]
+    #[serial_test::serial]
     fn test_get_set_locale() {
-        // 测试获取和设置语言
-        set_locale("en");
+        // 测试获取和设置语言（仅内存，避免写入真实 HOME）
+        apply_locale("en");
         assert_eq!(get_locale(), "en");
 
-        set_locale("zh-CN");
+        apply_locale("zh-CN");
         assert_eq!(get_locale(), "zh-CN");
     }
 
     #[test]
+    #[serial_test::serial]
     fn test_tr_current() {
         // 测试使用当前语言设置的翻译
-        set_locale("zh-CN");
+        apply_locale("zh-CN");
         let result = tr_current("config_file_not_found");
         assert_eq!(result, "配置文件不存在");
 
-        set_locale("en");
+        apply_locale("en");
         let result = tr_current("config_file_not_found");
         assert_eq!(result, "Configuration file not found");
+
+        apply_locale("fr");
+        assert_eq!(get_locale(), "fr");
+        let result = tr_current("config_file_not_found");
+        assert_eq!(result, "Fichier de configuration introuvable");
+
+        apply_locale("zh-CN");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_set_locale_persists_across_restart() {
+        let temp_dir = std::env::temp_dir().join("omo_i18n_persist_locale_test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        set_locale("ja").unwrap();
+        // 不支持的语言被拒绝，内存与磁盘中的设置均保持不变
+        let rejected = set_locale("de");
+        let locale_after_rejected = get_locale();
+        let persisted = read_persisted_locale();
+        // 模拟重启：内存值回到默认，再由 init_locale 从磁盘恢复
+        apply_locale("zh-CN");
+        init_locale();
+        let restored = get_locale();
+
+        apply_locale("zh-CN");
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert!(rejected.is_err());
+        assert_eq!(locale_after_rejected, "ja");
+        assert_eq!(persisted.as_deref(), Some("ja"));
+        assert_eq!(restored, "ja");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_locale_from_env_value() {
+        assert_eq!(locale_from_env_value("zh_CN.UTF-8"), Some("zh-CN"));
+        assert_eq!(locale_from_env_value("zh_TW.UTF-8"), Some("zh-TW"));
+        assert_eq!(locale_from_env_value("en_US.UTF-8"), Some("en"));
+        assert_eq!(locale_from_env_value("fr_FR"), Some("fr"));
+        assert_eq!(locale_from_env_value("C"), None);
+        assert_eq!(locale_from_env_value(""), None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_tr_external_override() {
+        let temp_dir = std::env::temp_dir().join("omo_i18n_override_test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let translations_dir = temp_dir
+            .join(".config")
+            .join("OMO-Switch")
+            .join("translations");
+        std::fs::create_dir_all(&translations_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        std::fs::write(
+            translations_dir.join("ko.json"),
+            r#"{"json_format_error": "JSON 오류 (사용자 정의)"}"#,
+        )
+        .unwrap();
+        reload_translation_overrides();
+        let overridden = tr("json_format_error", "ko");
+        let untouched = tr("config_file_not_found", "ko");
+
+        std::fs::remove_file(translations_dir.join("ko.json")).unwrap();
+        reload_translation_overrides();
+        let restored = tr("json_format_error", "ko");
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+        reload_translation_overrides();
+
+        assert_eq!(overridden, "JSON 오류 (사용자 정의)");
+        assert_eq!(untouched, "구성 파일을 찾을 수 없습니다");
+        assert_eq!(restored, "JSON 형식 오류");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
     }
 
     #[test]
@@ -615,9 +1183,11 @@ mod tests {
             "delete_preset_failed",
             "backup_config_failed",
             "json_format_error",
+            "config_lock_timeout",
+            "config_write_verify_failed",
         ];
 
-        let locales = vec!["zh-CN", "zh-TW", "en", "ja", "ko"];
+        let locales = vec!["zh-CN", "zh-TW", "en", "ja", "ko", "es", "fr"];
 
         for key in &keys {
             for locale in &locales {
@@ -6,7 +6,7 @@ mod i18n;
 mod services;
 mod tray;
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 fn show_main_window<R: tauri::Runtime>(app_handle: &tauri::AppHandle<R>) {
     if let Some(window) = app_handle.get_webview_window("main") {
@@ -23,66 +23,140 @@ fn main() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
         .setup(|app| {
+            // 恢复上次保存的语言设置，无记录时按系统 LANG 推断
+            i18n::init_locale();
+            // 启动时统一迁移旧版本遗留的内置预设标记，避免托盘/前端读到失效名称
+            services::preset_service::migrate_active_preset_marker();
+            // 全新安装时初始化最小配置，避免主窗口首次读取配置直接报错
+            if let Err(err) = services::config_service::ensure_omo_config_exists() {
+                eprintln!("初始化配置文件失败: {}", err);
+            }
             tray::setup_tray(app)?;
+            // 配置文件被外部修改时推送事件并刷新托盘，监听失败仅降级为不推送
+            let app_handle = app.handle().clone();
+            if let Err(err) = services::config_watch_service::start_config_watcher(move || {
+                let _ = app_handle.emit(services::config_watch_service::CONFIG_CHANGED_EVENT, ());
+                if let Err(err) = tray::rebuild_tray_menu(&app_handle) {
+                    eprintln!("托盘菜单刷新失败: {}", err);
+                }
+            }) {
+                eprintln!("监听配置文件失败，外部修改将不会实时推送: {}", err);
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::model_commands::get_available_models,
+            commands::model_commands::search_models,
             commands::model_commands::get_verified_available_models,
             commands::model_commands::get_available_models_with_status,
+            commands::model_commands::invalidate_models_cache,
+            commands::model_commands::get_recent_models,
             commands::model_commands::get_connected_providers,
+            commands::model_commands::get_provider_model_counts,
             commands::model_commands::fetch_models_dev,
+            commands::model_commands::fetch_models_dev_for,
+            commands::model_commands::fetch_models_dev_with_currency,
+            commands::model_commands::refresh_models_dev,
+            commands::model_commands::stream_provider_models,
             commands::config_commands::get_config_path,
             commands::config_commands::get_config_metadata,
             commands::config_commands::get_omo_cache_dir,
+            commands::config_commands::get_resolved_cache_dirs,
             commands::config_commands::read_omo_config,
             commands::config_commands::write_omo_config,
+            commands::config_commands::ensure_omo_config_exists,
             commands::config_commands::validate_config,
             commands::config_commands::update_agent_model,
             commands::config_commands::update_agents_batch,
+            commands::config_commands::set_category_variants,
+            commands::config_commands::remap_provider,
+            commands::config_commands::find_agents_sharing_model,
+            commands::config_commands::reconcile_agent_sources,
             commands::preset_commands::save_preset,
             commands::preset_commands::load_preset,
             commands::preset_commands::get_preset_config,
             commands::preset_commands::list_presets,
             commands::preset_commands::delete_preset,
             commands::preset_commands::rename_preset,
+            commands::preset_commands::export_preset,
+            commands::preset_commands::import_preset,
+            commands::preset_commands::duplicate_preset,
+            commands::preset_commands::diff_presets,
+            commands::preset_commands::diff_preset_with_current,
+            commands::preset_commands::preview_apply_preset,
             commands::preset_commands::get_preset_info,
+            commands::preset_commands::find_presets_using_model,
+            commands::preset_commands::estimate_preset_cost,
             commands::preset_commands::update_preset,
             commands::preset_commands::get_preset_meta,
+            commands::preset_commands::set_preset_description,
             commands::preset_commands::sync_preset_from_config,
             commands::preset_commands::apply_updates_to_preset,
+            commands::preset_commands::list_preset_history,
+            commands::preset_commands::restore_preset_version,
             commands::preset_commands::set_active_preset,
             commands::preset_commands::get_active_preset,
+            commands::preset_commands::clear_active_preset,
+            commands::preset_commands::migrate_active_preset_marker,
             commands::provider_commands::get_provider_status,
             commands::provider_commands::get_provider_config,
             commands::provider_commands::test_provider_connection,
             commands::provider_commands::set_provider_api_key,
+            commands::provider_commands::import_keys_from_env,
             commands::provider_commands::delete_provider_auth,
+            commands::provider_commands::disable_provider,
+            commands::provider_commands::enable_provider,
+            commands::provider_commands::list_disabled_providers,
+            commands::provider_commands::diagnose_providers,
             commands::provider_commands::add_custom_provider,
+            commands::provider_commands::update_custom_provider,
             commands::provider_commands::add_custom_model,
             commands::provider_commands::remove_custom_model,
             commands::provider_commands::get_custom_models,
+            commands::provider_commands::refresh_all_custom_provider_models,
             commands::provider_commands::get_provider_icon,
+            commands::provider_commands::clear_provider_icon_cache,
+            commands::provider_commands::get_provider_icon_cache_size,
             commands::import_export_commands::export_omo_config,
+            commands::import_export_commands::export_minimal_config,
+            commands::import_export_commands::export_config_redacted,
+            commands::import_export_commands::export_config_report,
+            commands::import_export_commands::export_app_bundle,
+            commands::import_export_commands::import_app_bundle,
             commands::import_export_commands::import_omo_config,
+            commands::import_export_commands::import_omo_config_merge,
             commands::import_export_commands::validate_import,
             commands::import_export_commands::get_import_export_history,
+            commands::import_export_commands::get_import_export_history_filtered,
             commands::import_export_commands::restore_backup,
             commands::import_export_commands::delete_backup,
+            commands::import_export_commands::set_backup_note,
             commands::import_export_commands::export_backup,
             commands::import_export_commands::clear_backup_history,
+            commands::import_export_commands::clear_backup_history_keeping,
             commands::import_export_commands::get_backup_history_limit,
             commands::import_export_commands::set_backup_history_limit,
+            commands::import_export_commands::get_backup_compression,
+            commands::import_export_commands::set_backup_compression,
             commands::i18n_commands::get_locale,
             commands::i18n_commands::set_locale,
             commands::version_commands::check_versions,
+            commands::version_commands::check_versions_force,
+            commands::version_commands::check_config_compatibility,
             commands::config_cache_commands::save_config_snapshot,
             commands::config_cache_commands::ensure_snapshot_exists,
             commands::config_cache_commands::load_config_snapshot,
+            commands::config_cache_commands::list_config_snapshots,
+            commands::config_cache_commands::load_config_snapshot_at,
             commands::config_cache_commands::compare_with_snapshot,
+            commands::config_cache_commands::get_snapshot_ignored_paths,
+            commands::config_cache_commands::set_snapshot_ignored_paths,
             commands::config_cache_commands::merge_and_save,
             commands::config_cache_commands::get_config_modification_time,
             commands::config_cache_commands::accept_external_changes,
+            commands::repair_commands::repair_all,
+            commands::repair_commands::migrate_config,
+            commands::repair_commands::config_health_check,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
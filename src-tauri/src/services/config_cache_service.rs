@@ -2,11 +2,23 @@
 //!
 //! 提供配置快照的保存、加载、对比和合并功能
 //! 缓存文件位置: ~/.cache/oh-my-opencode/config-snapshot.json
+//! 历史快照位置: ~/.cache/oh-my-opencode/config-snapshots/config-snapshot-{时间戳}.json
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::services::provider_store;
+
+/// 历史快照保留的最大数量，超出后删除最旧的快照
+pub const SNAPSHOT_HISTORY_LIMIT: usize = 5;
+
+const SNAPSHOT_HISTORY_PREFIX: &str = "config-snapshot-";
+
+/// 快照对比时默认忽略的非实质字段路径（点分隔，例如 "agents.oracle.note"）
+pub const DEFAULT_SNAPSHOT_IGNORED_PATHS: [&str; 2] = ["__meta__", "$schema"];
 
 // ============================================================================
 // 数据结构定义
@@ -36,6 +48,46 @@ pub struct ConfigChange {
     pub new_value: Option<Value>,
 }
 
+/// 外部配置无法接受时建议用户采取的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// 从最近一份可用备份恢复
+    RestoreBackup,
+    /// 没有可用备份，需要手动修正配置文件
+    EditManually,
+}
+
+/// 接受外部变更失败时返回给前端的结构化指引
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalChangeError {
+    /// 原始错误信息（例如校验失败原因）
+    pub message: String,
+    /// 是否可以通过恢复备份修复
+    pub recoverable: bool,
+    /// 建议操作
+    pub suggested_action: RecoveryAction,
+    /// 建议恢复的备份路径（最近一份可通过校验的备份）
+    pub backup_path: Option<String>,
+}
+
+impl ExternalChangeError {
+    /// 根据是否存在可用备份生成处理建议
+    pub fn new(message: String, backup_path: Option<String>) -> Self {
+        let suggested_action = if backup_path.is_some() {
+            RecoveryAction::RestoreBackup
+        } else {
+            RecoveryAction::EditManually
+        };
+        Self {
+            message,
+            recoverable: backup_path.is_some(),
+            suggested_action,
+            backup_path,
+        }
+    }
+}
+
 // ============================================================================
 // 辅助函数
 // ============================================================================
@@ -43,17 +95,37 @@ pub struct ConfigChange {
 /// 获取缓存目录路径（与 oh-my-opencode CLI 保持一致）
 /// 统一使用 ~/.cache/oh-my-opencode/
 fn get_cache_dir() -> Result<PathBuf, String> {
-    std::env::var("HOME")
-        .map(|home| PathBuf::from(home).join(".cache").join("oh-my-opencode"))
-        .map_err(|_| "无法获取 HOME 环境变量".to_string())
+    provider_store::omo_cache_dir()
 }
 
 /// 获取配置快照文件路径
 /// 返回 ~/.cache/oh-my-opencode/config-snapshot.json
-fn get_snapshot_path() -> Result<PathBuf, String> {
+pub(crate) fn get_snapshot_path() -> Result<PathBuf, String> {
     get_cache_dir().map(|p| p.join("config-snapshot.json"))
 }
 
+/// 获取历史快照目录路径
+/// 返回 ~/.cache/oh-my-opencode/config-snapshots/
+pub(crate) fn get_snapshot_history_dir() -> Result<PathBuf, String> {
+    get_cache_dir().map(|p| p.join("config-snapshots"))
+}
+
+/// 从历史快照文件名中解析时间戳
+/// 例如 config-snapshot-1700000000000.json -> 1700000000000
+fn parse_history_timestamp(file_name: &str) -> Option<u64> {
+    file_name
+        .strip_prefix(SNAPSHOT_HISTORY_PREFIX)?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+/// 读取并解析快照文件，不存在或已损坏时返回 None
+fn read_snapshot_file(path: &Path) -> Option<ConfigSnapshot> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
 /// 获取当前 Unix 时间戳（毫秒级）
 fn now_timestamp_ms() -> u64 {
     std::time::SystemTime::now()
@@ -68,8 +140,9 @@ fn now_timestamp_ms() -> u64 {
 
 /// 保存配置快照到缓存文件
 ///
-/// 将当前 OMO 配置保存到 ~/.cache/oh-my-opencode/config-snapshot.json
-/// 只保留最近一次快照，不保留历史版本
+/// 将当前 OMO 配置保存到 ~/.cache/oh-my-opencode/config-snapshot.json，
+/// 同时在 config-snapshots/ 下写入一份带时间戳的历史快照，
+/// 最多保留 SNAPSHOT_HISTORY_LIMIT 份，超出时删除最旧的快照
 ///
 /// 参数：
 /// - config: 要保存的配置内容
@@ -78,15 +151,22 @@ fn now_timestamp_ms() -> u64 {
 /// - Ok(()) 保存成功
 /// - Err(String) 保存失败，包含错误信息
 pub fn save_config_snapshot(config: &Value) -> Result<(), String> {
+    save_config_snapshot_at(config, now_timestamp_ms())
+}
+
+/// 以指定时间戳保存配置快照（便于测试构造有序的历史记录）
+fn save_config_snapshot_at(config: &Value, cached_at: u64) -> Result<(), String> {
     let cache_dir = get_cache_dir()?;
     let snapshot_path = get_snapshot_path()?;
+    let history_dir = get_snapshot_history_dir()?;
 
     // 确保缓存目录存在
     fs::create_dir_all(&cache_dir).map_err(|e| format!("创建缓存目录失败: {}", e))?;
+    fs::create_dir_all(&history_dir).map_err(|e| format!("创建快照历史目录失败: {}", e))?;
 
     // 创建快照结构
     let snapshot = ConfigSnapshot {
-        cached_at: now_timestamp_ms(),
+        cached_at,
         config: config.clone(),
     };
 
@@ -95,14 +175,47 @@ pub fn save_config_snapshot(config: &Value) -> Result<(), String> {
         .map_err(|e| format!("序列化配置快照失败: {}", e))?;
 
     // 写入文件
-    fs::write(&snapshot_path, json_string).map_err(|e| format!("写入配置快照失败: {}", e))?;
+    fs::write(&snapshot_path, &json_string).map_err(|e| format!("写入配置快照失败: {}", e))?;
+
+    let history_path = history_dir.join(format!("{}{}.json", SNAPSHOT_HISTORY_PREFIX, cached_at));
+    fs::write(&history_path, &json_string).map_err(|e| format!("写入历史快照失败: {}", e))?;
+
+    prune_snapshot_history()
+}
+
+/// 删除超出保留数量的最旧历史快照
+fn prune_snapshot_history() -> Result<(), String> {
+    let history_dir = get_snapshot_history_dir()?;
+    let timestamps = list_config_snapshots()?;
+
+    for ts in timestamps.iter().skip(SNAPSHOT_HISTORY_LIMIT) {
+        let path = history_dir.join(format!("{}{}.json", SNAPSHOT_HISTORY_PREFIX, ts));
+        fs::remove_file(&path).map_err(|e| format!("删除旧快照失败: {}", e))?;
+    }
 
     Ok(())
 }
 
+/// 列出所有历史快照的时间戳（Unix 毫秒），按从新到旧排序
+pub fn list_config_snapshots() -> Result<Vec<u64>, String> {
+    let history_dir = get_snapshot_history_dir()?;
+    if !history_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&history_dir).map_err(|e| format!("读取快照历史目录失败: {}", e))?;
+    let mut timestamps: Vec<u64> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| parse_history_timestamp(&entry.file_name().to_string_lossy()))
+        .collect();
+
+    timestamps.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(timestamps)
+}
+
 /// 加载配置快照
 ///
-/// 从 ~/.cache/oh-my-opencode/config-snapshot.json 读取缓存的配置
+/// 从 ~/.cache/oh-my-opencode/config-snapshot.json 读取最新一份缓存的配置
 ///
 /// 返回：
 /// - Some(ConfigSnapshot) 成功读取快照
@@ -110,18 +223,90 @@ pub fn save_config_snapshot(config: &Value) -> Result<(), String> {
 pub fn load_config_snapshot() -> Option<ConfigSnapshot> {
     let snapshot_path = get_snapshot_path().ok()?;
 
-    // 检查文件是否存在
-    if !snapshot_path.exists() {
-        return None;
+    // 解析 JSON（文件不存在或损坏时返回 None 而非 panic）
+    read_snapshot_file(&snapshot_path)
+}
+
+/// 按时间戳加载指定的历史快照
+///
+/// 返回：
+/// - Some(ConfigSnapshot) 成功读取快照
+/// - None 快照不存在（可能已被淘汰）或已损坏
+pub fn load_config_snapshot_at(cached_at: u64) -> Option<ConfigSnapshot> {
+    let history_dir = get_snapshot_history_dir().ok()?;
+    let path = history_dir.join(format!("{}{}.json", SNAPSHOT_HISTORY_PREFIX, cached_at));
+    read_snapshot_file(&path)
+}
+
+/// 快照对比忽略列表文件: ~/.config/OMO-Switch/snapshot-ignore.json（字符串数组）
+fn get_snapshot_ignore_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("OMO-Switch")
+        .join("snapshot-ignore.json"))
+}
+
+/// 读取快照对比的忽略路径列表，文件不存在或格式错误时使用默认列表
+pub fn load_snapshot_ignored_paths() -> Vec<String> {
+    get_snapshot_ignore_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<Vec<String>>(&content).ok())
+        .unwrap_or_else(|| {
+            DEFAULT_SNAPSHOT_IGNORED_PATHS
+                .iter()
+                .map(|path| path.to_string())
+                .collect()
+        })
+}
+
+/// 保存快照对比的忽略路径列表（去除空白项与重复项）
+pub fn save_snapshot_ignored_paths(paths: &[String]) -> Result<(), String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for path in paths.iter().map(|path| path.trim()) {
+        if !path.is_empty() && !normalized.iter().any(|existing| existing == path) {
+            normalized.push(path.to_string());
+        }
     }
 
-    // 读取文件内容
-    let content = fs::read_to_string(&snapshot_path).ok()?;
+    let path = get_snapshot_ignore_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建设置目录失败: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&normalized).map_err(|e| format!("序列化失败: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("写入忽略列表失败: {}", e))
+}
 
-    // 解析 JSON（文件损坏时返回 None 而非 panic）
-    let snapshot: ConfigSnapshot = serde_json::from_str(&content).ok()?;
+/// 返回剔除了忽略路径的配置副本，路径不存在时忽略
+fn strip_ignored_paths(config: &Value, ignored_paths: &[String]) -> Value {
+    let mut stripped = config.clone();
+    for ignored in ignored_paths {
+        let mut segments: Vec<&str> = ignored.split('.').collect();
+        let Some(last) = segments.pop() else {
+            continue;
+        };
+        let parent = segments
+            .iter()
+            .try_fold(&mut stripped, |value, segment| value.get_mut(*segment));
+        if let Some(obj) = parent.and_then(|value| value.as_object_mut()) {
+            obj.remove(last);
+        }
+    }
+    stripped
+}
 
-    Some(snapshot)
+/// 对比两个配置，先剔除忽略路径上的字段，避免元数据变动被当作外部修改
+pub fn compare_configs_ignoring(
+    old_config: &Value,
+    new_config: &Value,
+    ignored_paths: &[String],
+) -> Vec<ConfigChange> {
+    compare_configs(
+        &strip_ignored_paths(old_config, ignored_paths),
+        &strip_ignored_paths(new_config, ignored_paths),
+    )
 }
 
 /// 深度对比两个配置
@@ -202,16 +387,11 @@ fn compare_values(old_val: &Value, new_val: &Value, path: &str, changes: &mut Ve
         return;
     }
 
-    // 处理数组类型的比较（按索引比较）
+    // 处理数组类型的比较
     if let (Some(old_arr), Some(new_arr)) = (old_val.as_array(), new_val.as_array()) {
-        // 简单比较：如果数组长度不同，整体视为修改
+        // 长度不同：按内容识别新增/删除的元素
         if old_arr.len() != new_arr.len() {
-            changes.push(ConfigChange {
-                path: path.to_string(),
-                change_type: "modified".to_string(),
-                old_value: Some(old_val.clone()),
-                new_value: Some(new_val.clone()),
-            });
+            compare_arrays_by_content(old_arr, new_arr, path, changes);
             return;
         }
 
@@ -232,6 +412,68 @@ fn compare_values(old_val: &Value, new_val: &Value, path: &str, changes: &mut Ve
     });
 }
 
+/// 按内容比较两个不等长数组
+///
+/// 基于最长公共子序列匹配相同元素，未匹配的旧元素记为 removed（使用旧数组下标），
+/// 未匹配的新元素记为 added（使用新数组下标）
+fn compare_arrays_by_content(
+    old_arr: &[Value],
+    new_arr: &[Value],
+    path: &str,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let (n, m) = (old_arr.len(), new_arr.len());
+
+    // lcs[i][j] 表示 old_arr[i..] 与 new_arr[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_arr[i] == new_arr[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let removed = |i: usize, changes: &mut Vec<ConfigChange>| {
+        changes.push(ConfigChange {
+            path: format!("{}[{}]", path, i),
+            change_type: "removed".to_string(),
+            old_value: Some(old_arr[i].clone()),
+            new_value: None,
+        });
+    };
+    let added = |j: usize, changes: &mut Vec<ConfigChange>| {
+        changes.push(ConfigChange {
+            path: format!("{}[{}]", path, j),
+            change_type: "added".to_string(),
+            old_value: None,
+            new_value: Some(new_arr[j].clone()),
+        });
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_arr[i] == new_arr[j] {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            removed(i, changes);
+            i += 1;
+        } else {
+            added(j, changes);
+            j += 1;
+        }
+    }
+    for k in i..n {
+        removed(k, changes);
+    }
+    for k in j..m {
+        added(k, changes);
+    }
+}
+
 /// 合并配置
 ///
 /// 将两个配置合并，策略：
@@ -246,15 +488,52 @@ fn compare_values(old_val: &Value, new_val: &Value, path: &str, changes: &mut Ve
 /// 返回：
 /// - Value: 合并后的配置
 pub fn merge_configs(old_config: &Value, new_config: &Value) -> Value {
+    merge_configs_with_deletions(old_config, new_config, &HashSet::new())
+}
+
+/// 合并配置，并排除本地已删除的路径
+///
+/// 与 `merge_configs` 相同，但 deleted_paths 中列出的路径（例如 "agents.metis"）
+/// 视为本地显式删除：即使 old_config 中仍存在，也不会被补回合并结果
+///
+/// 参数：
+/// - old_config: 旧配置（可能包含外部新增的字段）
+/// - new_config: 新配置（优先级更高）
+/// - deleted_paths: 本地已删除的字段路径集合，格式与 ConfigChange.path 一致
+///
+/// 返回：
+/// - Value: 合并后的配置
+pub fn merge_configs_with_deletions(
+    old_config: &Value,
+    new_config: &Value,
+    deleted_paths: &HashSet<String>,
+) -> Value {
+    merge_values(old_config, new_config, "", deleted_paths)
+}
+
+/// 递归合并两个 JSON 值
+fn merge_values(
+    old_config: &Value,
+    new_config: &Value,
+    path: &str,
+    deleted_paths: &HashSet<String>,
+) -> Value {
     // 如果两者都是对象，递归合并
     if let (Some(old_obj), Some(new_obj)) = (old_config.as_object(), new_config.as_object()) {
         let mut merged = old_obj.clone();
 
+        // 移除本地已删除、且新配置中不存在的键
+        merged.retain(|key, _| {
+            new_obj.contains_key(key) || !deleted_paths.contains(&join_path(path, key))
+        });
+
         // 遍历新配置的所有键
         for (key, new_val) in new_obj {
             if let Some(old_val) = merged.get(key) {
                 // 键存在于两者中，递归合并
-                merged.insert(key.clone(), merge_configs(old_val, new_val));
+                let child_path = join_path(path, key);
+                let merged_val = merge_values(old_val, new_val, &child_path, deleted_paths);
+                merged.insert(key.clone(), merged_val);
             } else {
                 // 键只存在于新配置中，直接插入
                 merged.insert(key.clone(), new_val.clone());
@@ -268,6 +547,15 @@ pub fn merge_configs(old_config: &Value, new_config: &Value) -> Value {
     new_config.clone()
 }
 
+/// 拼接配置路径，例如 ("agents", "metis") -> "agents.metis"
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
 // ============================================================================
 // 单元测试
 // ============================================================================
@@ -276,6 +564,7 @@ pub fn merge_configs(old_config: &Value, new_config: &Value) -> Value {
 mod tests {
     use super::*;
     use serde_json::json;
+    use serial_test::serial;
     use std::fs;
 
     /// 测试时间戳生成
@@ -293,6 +582,24 @@ mod tests {
         assert!(path.to_string_lossy().contains("oh-my-opencode"));
     }
 
+    /// 测试各子系统缓存目录与共享解析入口一致
+    #[test]
+    #[serial]
+    fn test_cache_dirs_share_single_resolution() {
+        let shared = provider_store::omo_cache_dir().expect("should get shared cache dir");
+        assert_eq!(get_cache_dir().unwrap(), shared);
+        assert_eq!(
+            get_snapshot_path().unwrap().parent(),
+            Some(shared.as_path())
+        );
+        assert_eq!(
+            crate::services::model_service::get_verified_models_path()
+                .unwrap()
+                .parent(),
+            Some(shared.as_path())
+        );
+    }
+
     /// 测试快照路径
     #[test]
     fn test_snapshot_path() {
@@ -364,6 +671,134 @@ mod tests {
         fs::remove_dir_all(&temp_dir).unwrap();
     }
 
+    /// 测试历史快照超出上限时淘汰最旧的一份，且最新快照始终可读
+    #[test]
+    #[serial]
+    fn test_snapshot_history_prunes_oldest() {
+        let temp_home = std::env::temp_dir().join("omo-snapshot-history-test");
+        let _ = fs::remove_dir_all(&temp_home);
+        fs::create_dir_all(&temp_home).unwrap();
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_home);
+        }
+
+        let base = 1_700_000_000_000u64;
+        for i in 0..(SNAPSHOT_HISTORY_LIMIT as u64 + 2) {
+            save_config_snapshot_at(&json!({ "version": i }), base + i).unwrap();
+        }
+
+        let timestamps = list_config_snapshots().unwrap();
+        let newest = base + SNAPSHOT_HISTORY_LIMIT as u64 + 1;
+        assert_eq!(timestamps.len(), SNAPSHOT_HISTORY_LIMIT);
+        assert_eq!(timestamps.first(), Some(&newest));
+        assert_eq!(timestamps.last(), Some(&(base + 2)));
+        assert!(load_config_snapshot_at(base).is_none());
+        assert!(load_config_snapshot_at(base + 1).is_none());
+        assert_eq!(
+            load_config_snapshot_at(base + 3).unwrap().config,
+            json!({ "version": 3 })
+        );
+
+        let latest = load_config_snapshot().unwrap();
+        assert_eq!(latest.cached_at, newest);
+        assert_eq!(
+            latest.config,
+            json!({ "version": SNAPSHOT_HISTORY_LIMIT + 1 })
+        );
+
+        unsafe {
+            match original_home {
+                Some(home) => std::env::set_var("HOME", home),
+                None => std::env::remove_var("HOME"),
+            }
+        }
+        let _ = fs::remove_dir_all(&temp_home);
+    }
+
+    /// 测试仅 __meta__ / $schema 不同的配置被判为无变更，且忽略列表可配置
+    #[test]
+    #[serial]
+    fn test_compare_configs_ignoring_meta_fields() {
+        let old_config = json!({
+            "$schema": "https://example.com/v1/schema.json",
+            "agents": {"oracle": {"model": "openai/gpt-5", "note": "a"}},
+            "__meta__": {"updated_at": 1}
+        });
+        let meta_only = json!({
+            "$schema": "https://example.com/v2/schema.json",
+            "agents": {"oracle": {"model": "openai/gpt-5", "note": "a"}},
+            "__meta__": {"updated_at": 2}
+        });
+        let defaults: Vec<String> = DEFAULT_SNAPSHOT_IGNORED_PATHS
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+
+        assert!(!compare_configs(&old_config, &meta_only).is_empty());
+        assert!(compare_configs_ignoring(&old_config, &meta_only, &defaults).is_empty());
+
+        let mut note_changed = meta_only.clone();
+        note_changed["agents"]["oracle"]["note"] = json!("b");
+        let changes = compare_configs_ignoring(&old_config, &note_changed, &defaults);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "agents.oracle.note");
+
+        let temp_home = std::env::temp_dir().join("omo-snapshot-ignore-test");
+        let _ = fs::remove_dir_all(&temp_home);
+        fs::create_dir_all(&temp_home).unwrap();
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_home);
+        }
+
+        let before_save = load_snapshot_ignored_paths();
+        save_snapshot_ignored_paths(&[
+            "__meta__".to_string(),
+            " agents.oracle.note ".to_string(),
+            "__meta__".to_string(),
+        ])
+        .unwrap();
+        let custom = load_snapshot_ignored_paths();
+
+        unsafe {
+            match original_home {
+                Some(home) => std::env::set_var("HOME", home),
+                None => std::env::remove_var("HOME"),
+            }
+        }
+        let _ = fs::remove_dir_all(&temp_home);
+
+        assert_eq!(before_save, defaults);
+        assert_eq!(custom, vec!["__meta__", "agents.oracle.note"]);
+        let changes = compare_configs_ignoring(&old_config, &note_changed, &custom);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "$schema");
+    }
+
+    /// 测试校验失败时根据可用备份生成结构化建议
+    #[test]
+    fn test_external_change_error_guidance() {
+        let with_backup = ExternalChangeError::new(
+            "缺少 agents 字段".to_string(),
+            Some("/tmp/backups/oh-my-openagent_1.json".to_string()),
+        );
+        assert!(with_backup.recoverable);
+        assert_eq!(with_backup.suggested_action, RecoveryAction::RestoreBackup);
+
+        let value = serde_json::to_value(&with_backup).unwrap();
+        assert_eq!(value["message"], "缺少 agents 字段");
+        assert_eq!(value["suggested_action"], "restore_backup");
+        assert_eq!(value["backup_path"], "/tmp/backups/oh-my-openagent_1.json");
+
+        let without_backup = ExternalChangeError::new("缺少 agents 字段".to_string(), None);
+        assert!(!without_backup.recoverable);
+        assert_eq!(
+            serde_json::to_value(&without_backup).unwrap()["suggested_action"],
+            "edit_manually"
+        );
+    }
+
     /// 测试配置对比 - 检测添加的字段
     #[test]
     fn test_compare_configs_added() {
@@ -442,6 +877,39 @@ mod tests {
         assert_eq!(model_change.new_value, Some(json!("new-model")));
     }
 
+    /// 测试数组新增一个元素时只报告一条 added
+    #[test]
+    fn test_compare_configs_array_single_added() {
+        let old_config = json!({
+            "agents": {
+                "sisyphus": {
+                    "fallback": ["openai/gpt-5", "anthropic/claude-sonnet-4"]
+                }
+            }
+        });
+
+        let new_config = json!({
+            "agents": {
+                "sisyphus": {
+                    "fallback": ["openai/gpt-5", "google/gemini-2.5-pro", "anthropic/claude-sonnet-4"]
+                }
+            }
+        });
+
+        let changes = compare_configs(&old_config, &new_config);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "agents.sisyphus.fallback[1]");
+        assert_eq!(changes[0].change_type, "added");
+        assert_eq!(changes[0].new_value, Some(json!("google/gemini-2.5-pro")));
+
+        // 反向比较：只报告一条 removed
+        let changes = compare_configs(&new_config, &old_config);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "agents.sisyphus.fallback[1]");
+        assert_eq!(changes[0].change_type, "removed");
+    }
+
     /// 测试配置合并 - 保留外部新增字段
     #[test]
     fn test_merge_configs_preserve_external() {
@@ -475,6 +943,36 @@ mod tests {
         assert_eq!(merged["agents"]["sisyphus"]["custom_field"], "should_keep");
     }
 
+    /// 测试配置合并 - 本地删除的 agent 不会被快照复活
+    #[test]
+    fn test_merge_configs_respects_local_deletions() {
+        let snapshot = json!({
+            "agents": {
+                "sisyphus": { "model": "old-model" },
+                "metis": { "model": "metis-model" }
+            },
+            "external_field": "from_user"
+        });
+
+        let current = json!({
+            "agents": {
+                "sisyphus": { "model": "new-model" }
+            }
+        });
+
+        // 未标记删除时保持原有行为：旧 agent 被补回
+        let merged = merge_configs(&snapshot, &current);
+        assert!(merged["agents"].get("metis").is_some());
+
+        let deleted: HashSet<String> = ["agents.metis".to_string()].into_iter().collect();
+        let merged = merge_configs_with_deletions(&snapshot, &current, &deleted);
+
+        assert!(merged["agents"].get("metis").is_none());
+        assert_eq!(merged["agents"]["sisyphus"]["model"], "new-model");
+        // 其他外部字段仍然保留
+        assert_eq!(merged["external_field"], "from_user");
+    }
+
     /// 测试配置合并 - 完全不同的对象
     #[test]
     fn test_merge_configs_different_objects() {
@@ -1,20 +1,39 @@
 use crate::i18n;
+use fs2::FileExt;
+use serde::Serialize;
 use serde_json::Value;
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 const PRIMARY_CONFIG_BASENAME: &str = "oh-my-openagent.json";
 const PRIMARY_CONFIG_BASENAME_JSONC: &str = "oh-my-openagent.jsonc";
 const LEGACY_CONFIG_BASENAME: &str = "oh-my-opencode.json";
 const LEGACY_CONFIG_BASENAME_JSONC: &str = "oh-my-opencode.jsonc";
+const CONFIG_LOCK_BASENAME: &str = ".omo-switch-config.lock";
+/// 获取配置文件锁的最长等待时间
+const CONFIG_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const CONFIG_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 应用自身最近一次写入配置文件的内容指纹，供文件监听区分外部修改
+static LAST_APP_WRITE_FINGERPRINT: Mutex<Option<u64>> = Mutex::new(None);
+
+thread_local! {
+    // 当前线程持有配置锁的嵌套层数，支持在锁内再次调用 write_omo_config
+    static CONFIG_LOCK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
 
 fn get_config_dir() -> Result<PathBuf, String> {
     let home = std::env::var("HOME").map_err(|_| i18n::tr_current("home_env_var_error"))?;
     Ok(PathBuf::from(home).join(".config").join("opencode"))
 }
 
-fn get_config_candidates() -> Result<Vec<PathBuf>, String> {
+pub(crate) fn get_config_candidates() -> Result<Vec<PathBuf>, String> {
     let dir = get_config_dir()?;
     Ok(vec![
         dir.join(PRIMARY_CONFIG_BASENAME),
@@ -74,6 +93,64 @@ pub(crate) fn write_string_atomically(
     Ok(())
 }
 
+/// 配置文件锁守卫，离开作用域时释放锁
+/// 同一线程内重入获得的守卫不持有文件句柄，只有最外层守卫负责解锁
+pub(crate) struct ConfigLock {
+    file: Option<fs::File>,
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        CONFIG_LOCK_DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+        if let Some(file) = self.file.take() {
+            let _ = FileExt::unlock(&file);
+        }
+    }
+}
+
+/// 获取配置文件锁（对 .lock 文件加操作系统建议锁，跨线程、跨进程互斥）
+/// 锁文件常驻不删除；持有者进程退出时系统自动释放锁，不会遗留死锁
+/// 同一线程内可重入；超过 CONFIG_LOCK_TIMEOUT 仍未获取到时返回错误
+pub(crate) fn acquire_config_lock() -> Result<ConfigLock, String> {
+    if CONFIG_LOCK_DEPTH.with(|d| d.get()) > 0 {
+        CONFIG_LOCK_DEPTH.with(|d| d.set(d.get() + 1));
+        return Ok(ConfigLock { file: None });
+    }
+
+    let dir = get_config_dir()?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("create_config_dir_failed"), e))?;
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dir.join(CONFIG_LOCK_BASENAME))
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_config_failed"), e))?;
+
+    let deadline = Instant::now() + CONFIG_LOCK_TIMEOUT;
+    while file.try_lock_exclusive().is_err() {
+        if Instant::now() >= deadline {
+            return Err(i18n::tr_current("config_lock_timeout"));
+        }
+        std::thread::sleep(CONFIG_LOCK_RETRY_INTERVAL);
+    }
+
+    // 记录持有者 PID，仅用于排查
+    if file.set_len(0).is_ok() {
+        let _ = write!(file, "{}", std::process::id());
+    }
+    CONFIG_LOCK_DEPTH.with(|d| d.set(1));
+    Ok(ConfigLock { file: Some(file) })
+}
+
+/// 在配置文件锁内执行操作
+/// 用于“读取-修改-写入”流程，避免主窗口、托盘等并发写入时互相覆盖
+pub fn with_config_lock<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let _lock = acquire_config_lock()?;
+    f()
+}
+
 /// 获取 OMO 配置文件路径
 /// 返回当前实际写入使用的配置路径（优先已存在文件，否则新建到 openagent 文件名）
 pub fn get_config_path() -> Result<PathBuf, String> {
@@ -117,14 +194,24 @@ pub fn read_omo_config() -> Result<Value, String> {
 }
 
 /// 写入 OMO 配置文件
-/// 先创建 .bak 备份，再写入新配置
+/// 先创建 .bak 备份，再写入新配置，写入后回读校验
 /// 使用 serde_json::Value 确保不丢失任何字段
+/// 写入期间持有配置文件锁；需要覆盖“读取-修改-写入”时由调用方使用 with_config_lock
 pub fn write_omo_config(config: &Value) -> Result<(), String> {
+    write_omo_config_with(config, write_string_atomically)
+}
+
+fn write_omo_config_with<W>(config: &Value, write: W) -> Result<(), String>
+where
+    W: FnOnce(&PathBuf, &str, &str) -> Result<(), String>,
+{
+    let _lock = acquire_config_lock()?;
     let config_path = resolve_write_config_path()?;
+    let backup_path = config_path.with_extension("json.bak");
 
     // 如果原文件存在，先创建备份
-    if config_path.exists() {
-        let backup_path = config_path.with_extension("json.bak");
+    let has_backup = config_path.exists();
+    if has_backup {
         fs::copy(&config_path, &backup_path)
             .map_err(|e| format!("{}: {}", i18n::tr_current("create_backup_failed"), e))?;
     }
@@ -133,15 +220,67 @@ pub fn write_omo_config(config: &Value) -> Result<(), String> {
     let json_string = serde_json::to_string_pretty(config)
         .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
 
-    write_string_atomically(
+    write(
         &config_path,
         &json_string,
         &i18n::tr_current("write_config_failed"),
     )?;
 
+    // 回读校验：磁盘写满或中断可能导致内容被截断，校验失败时从 .bak 回滚
+    if !is_written_config_intact(&config_path, config) {
+        let restored = if has_backup {
+            fs::copy(&backup_path, &config_path).map(|_| ())
+        } else {
+            fs::remove_file(&config_path)
+        };
+        if let Err(e) = restored {
+            eprintln!("警告：回滚配置文件失败: {}", e);
+        }
+        return Err(i18n::tr_current("config_write_verify_failed"));
+    }
+
+    *LAST_APP_WRITE_FINGERPRINT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(content_fingerprint(json_string.as_bytes()));
     Ok(())
 }
 
+fn content_fingerprint(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 文件内容是否与应用自身最近一次写入的配置完全一致（即非外部修改）
+pub(crate) fn is_last_app_write(content: &[u8]) -> bool {
+    let last = *LAST_APP_WRITE_FINGERPRINT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    last == Some(content_fingerprint(content))
+}
+
+/// 回读文件并确认可解析、顶层为对象且与写入内容一致
+fn is_written_config_intact(path: &PathBuf, expected: &Value) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .is_some_and(|written| written.is_object() && written == *expected)
+}
+
+/// 确保 OMO 配置文件存在
+/// 所有候选配置文件都不存在时写入最小合法配置 {"agents":{},"categories":{}}
+/// 返回是否新建了配置文件；已存在时不做任何修改
+pub fn ensure_omo_config_exists() -> Result<bool, String> {
+    with_config_lock(|| {
+        if resolve_existing_config_path()?.is_some() {
+            return Ok(false);
+        }
+
+        write_omo_config(&serde_json::json!({ "agents": {}, "categories": {} }))?;
+        Ok(true)
+    })
+}
+
 /// 验证配置文件基本结构
 /// 检查是否包含必需的 agents 和 categories 键
 pub fn validate_config(config: &Value) -> Result<(), String> {
@@ -171,9 +310,230 @@ pub fn validate_config(config: &Value) -> Result<(), String> {
         return Err("'categories' 字段必须是对象".to_string());
     }
 
+    for section in ["agents", "categories"] {
+        for (name, entry) in obj[section].as_object().unwrap() {
+            validate_config_entry(section, name, entry)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 校验单个 agent/category：model 若存在必须是 provider/model 形式，variant 若存在必须是字符串
+fn validate_config_entry(section: &str, name: &str, entry: &Value) -> Result<(), String> {
+    let Some(entry) = entry.as_object() else {
+        return Err(format!("{}.{} 必须是对象", section, name));
+    };
+
+    if let Some(model) = entry.get("model") {
+        let Some(model) = model.as_str() else {
+            return Err(format!("{}.{}.model 必须是字符串", section, name));
+        };
+        let is_qualified = model.split_once('/').is_some_and(|(provider, model_id)| {
+            !provider.trim().is_empty() && !model_id.trim().is_empty()
+        });
+        if !is_qualified {
+            return Err(format!(
+                "{}.{}.model 必须是 provider/model 格式的非空字符串",
+                section, name
+            ));
+        }
+    }
+
+    if entry
+        .get("variant")
+        .is_some_and(|variant| !variant.is_string())
+    {
+        return Err(format!("{}.{}.variant 必须是字符串", section, name));
+    }
+
     Ok(())
 }
 
+/// 为所有已配置模型的 category 设置 variant，返回受影响的数量
+/// variant 为 "none" 时移除该字段，与单项更新的语义保持一致
+fn apply_variant_to_categories(config: &mut Value, variant: &str) -> usize {
+    let Some(categories) = config.get_mut("categories").and_then(|c| c.as_object_mut()) else {
+        return 0;
+    };
+
+    let mut updated = 0;
+    for category in categories.values_mut() {
+        let Some(obj) = category.as_object_mut() else {
+            continue;
+        };
+        let has_model = obj
+            .get("model")
+            .and_then(|m| m.as_str())
+            .is_some_and(|m| !m.trim().is_empty());
+        if !has_model {
+            continue;
+        }
+
+        if variant == "none" {
+            obj.remove("variant");
+        } else {
+            obj.insert("variant".to_string(), Value::String(variant.to_string()));
+        }
+        updated += 1;
+    }
+
+    updated
+}
+
+/// 批量设置所有 category 的 variant（不影响 agents）
+/// 校验通过后一次性写入配置文件
+pub fn set_category_variants(variant: &str) -> Result<usize, String> {
+    with_config_lock(|| {
+        let mut config = read_omo_config()?;
+        let updated = apply_variant_to_categories(&mut config, variant);
+        validate_config(&config)?;
+        write_omo_config(&config)?;
+        Ok(updated)
+    })
+}
+
+/// 按 provider 重新映射模型的结果
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ProviderRemapResult {
+    /// 被改动的字段路径，例如 "agents.sisyphus.model"
+    pub changed: Vec<String>,
+    /// 属于源 provider 但 model_map 中没有映射、因而保持不变的字段路径
+    pub skipped: Vec<String>,
+}
+
+/// 将 agents 与 categories 中 `from_provider/` 开头的模型按 model_map 替换为 `to_provider/...`
+/// model_map 的键为源模型 ID，值为目标模型 ID（均不含 provider 前缀）
+fn apply_provider_remap(
+    config: &mut Value,
+    from_provider: &str,
+    to_provider: &str,
+    model_map: &HashMap<String, String>,
+) -> ProviderRemapResult {
+    let prefix = format!("{}/", from_provider);
+    let mut result = ProviderRemapResult {
+        changed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for section in ["agents", "categories"] {
+        let Some(entries) = config.get_mut(section).and_then(|s| s.as_object_mut()) else {
+            continue;
+        };
+        for (name, entry) in entries.iter_mut() {
+            let Some(model_id) = entry
+                .get("model")
+                .and_then(|m| m.as_str())
+                .and_then(|m| m.strip_prefix(&prefix))
+            else {
+                continue;
+            };
+
+            let path = format!("{}.{}.model", section, name);
+            match model_map.get(model_id) {
+                Some(target) => {
+                    entry["model"] = Value::String(format!("{}/{}", to_provider, target));
+                    result.changed.push(path);
+                }
+                None => result.skipped.push(path),
+            }
+        }
+    }
+
+    result
+}
+
+/// 把使用某个 provider 的 agent/category 一键切换到另一个 provider 的等价模型
+/// 校验通过后一次性写入配置文件；没有任何改动时不写入
+pub fn remap_provider(
+    from_provider: &str,
+    to_provider: &str,
+    model_map: &HashMap<String, String>,
+) -> Result<ProviderRemapResult, String> {
+    with_config_lock(|| {
+        let mut config = read_omo_config()?;
+        let result = apply_provider_remap(&mut config, from_provider, to_provider, model_map);
+        if !result.changed.is_empty() {
+            validate_config(&config)?;
+            write_omo_config(&config)?;
+        }
+        Ok(result)
+    })
+}
+
+/// 按模型分组 agents 与 categories，仅保留被多个条目共用的模型
+/// 同一模型下先列 agents 再列 categories，均保持配置中的原始顺序
+fn group_entries_sharing_model(config: &Value) -> HashMap<String, Vec<String>> {
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+
+    for section in ["agents", "categories"] {
+        let Some(entries) = config.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, entry) in entries {
+            let Some(model) = entry.get("model").and_then(|m| m.as_str()) else {
+                continue;
+            };
+            let model = model.trim();
+            if model.is_empty() {
+                continue;
+            }
+            groups
+                .entry(model.to_string())
+                .or_default()
+                .push(name.clone());
+        }
+    }
+
+    groups.retain(|_, names| names.len() > 1);
+    groups
+}
+
+/// 找出被多个 agent/category 共用的模型，用于提示意外的模型趋同
+pub fn find_agents_sharing_model() -> Result<HashMap<String, Vec<String>>, String> {
+    let config = read_omo_config()?;
+    Ok(group_entries_sharing_model(&config))
+}
+
+/// 当前配置与应用内显示名表之间的 agent 名称差异
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AgentSourceReconciliation {
+    /// 仅出现在当前配置中的 agent（应用尚未收录显示名）
+    pub only_in_config: Vec<String>,
+    /// 仅出现在应用内显示名表中的 agent（当前配置未使用）
+    pub only_in_app_table: Vec<String>,
+}
+
+/// 计算两方 agent 名称集合的差集，结果按名称排序
+fn diff_agent_sources(config_agents: &[String], app_agents: &[&str]) -> AgentSourceReconciliation {
+    let config: BTreeSet<&str> = config_agents.iter().map(String::as_str).collect();
+    let app: BTreeSet<&str> = app_agents.iter().copied().collect();
+
+    AgentSourceReconciliation {
+        only_in_config: config
+            .difference(&app)
+            .map(|name| name.to_string())
+            .collect(),
+        only_in_app_table: app
+            .difference(&config)
+            .map(|name| name.to_string())
+            .collect(),
+    }
+}
+
+/// 对比当前配置与应用内显示名表中的 agent 名称
+/// 用于诊断两者之间的漂移（例如配置中新增了应用尚未收录显示名的 agent）
+pub fn reconcile_agent_sources(app_agents: &[&str]) -> Result<AgentSourceReconciliation, String> {
+    let config = read_omo_config()?;
+    let config_agents: Vec<String> = config
+        .get("agents")
+        .and_then(|v| v.as_object())
+        .map(|agents| agents.keys().cloned().collect())
+        .unwrap_or_default();
+
+    Ok(diff_agent_sources(&config_agents, app_agents))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,18 +550,131 @@ mod tests {
             .contains(".config/opencode/oh-my-openagent.json"));
     }
 
+    /// 测试批量设置 category variant 只作用于有模型的 category
+    #[test]
+    fn test_apply_variant_to_categories() {
+        let mut config = json!({
+            "agents": {
+                "oracle": { "model": "openai/gpt-5" }
+            },
+            "categories": {
+                "ultrabrain": { "model": "openai/gpt-5" },
+                "visual-engineering": { "model": "google/gemini-3-pro", "variant": "low" },
+                "quick": {}
+            }
+        });
+
+        assert_eq!(apply_variant_to_categories(&mut config, "high"), 2);
+        assert_eq!(config["categories"]["ultrabrain"]["variant"], "high");
+        assert_eq!(
+            config["categories"]["visual-engineering"]["variant"],
+            "high"
+        );
+        assert!(config["categories"]["quick"].get("variant").is_none());
+        assert!(config["agents"]["oracle"].get("variant").is_none());
+
+        assert_eq!(apply_variant_to_categories(&mut config, "none"), 2);
+        assert!(config["categories"]["ultrabrain"].get("variant").is_none());
+    }
+
+    /// 测试按 provider 重新映射：有映射的替换，无映射的跳过并记录
+    #[test]
+    fn test_apply_provider_remap() {
+        let mut config = json!({
+            "agents": {
+                "sisyphus": { "model": "anthropic/claude-opus-4-5", "variant": "max" },
+                "explore": { "model": "anthropic/claude-haiku-4-5" },
+                "oracle": { "model": "openai/gpt-5" }
+            },
+            "categories": {
+                "quick": { "model": "anthropic/claude-haiku-4-5" },
+                "writing": { "model": "anthropic/claude-sonnet-4-5" }
+            }
+        });
+        let model_map: HashMap<String, String> = [
+            ("claude-opus-4-5".to_string(), "gpt-5".to_string()),
+            ("claude-haiku-4-5".to_string(), "gpt-5-mini".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let result = apply_provider_remap(&mut config, "anthropic", "openai", &model_map);
+
+        assert_eq!(
+            result.changed,
+            vec![
+                "agents.sisyphus.model",
+                "agents.explore.model",
+                "categories.quick.model"
+            ]
+        );
+        assert_eq!(result.skipped, vec!["categories.writing.model"]);
+        assert_eq!(config["agents"]["sisyphus"]["model"], "openai/gpt-5");
+        assert_eq!(config["agents"]["sisyphus"]["variant"], "max");
+        assert_eq!(config["categories"]["quick"]["model"], "openai/gpt-5-mini");
+        assert_eq!(
+            config["categories"]["writing"]["model"],
+            "anthropic/claude-sonnet-4-5"
+        );
+        assert_eq!(config["agents"]["oracle"]["model"], "openai/gpt-5");
+    }
+
+    /// 测试共用模型分组只保留多于一个条目的模型
+    #[test]
+    fn test_group_entries_sharing_model() {
+        let config = json!({
+            "agents": {
+                "explore": { "model": "anthropic/claude-haiku-4-5" },
+                "librarian": { "model": "anthropic/claude-haiku-4-5" },
+                "oracle": { "model": "openai/gpt-5" }
+            },
+            "categories": {
+                "quick": { "model": "anthropic/claude-haiku-4-5" },
+                "writing": {}
+            }
+        });
+
+        let groups = group_entries_sharing_model(&config);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups.get("anthropic/claude-haiku-4-5").cloned(),
+            Some(vec![
+                "explore".to_string(),
+                "librarian".to_string(),
+                "quick".to_string()
+            ])
+        );
+    }
+
+    /// 测试两方 agent 名称差集计算
+    #[test]
+    fn test_diff_agent_sources() {
+        let config_agents = vec![
+            "sisyphus".to_string(),
+            "oracle".to_string(),
+            "my-agent".to_string(),
+        ];
+        let app = ["sisyphus", "oracle", "hephaestus"];
+
+        let result = diff_agent_sources(&config_agents, &app);
+
+        assert_eq!(result.only_in_config, vec!["my-agent".to_string()]);
+        assert_eq!(result.only_in_app_table, vec!["hephaestus".to_string()]);
+    }
+
     /// 测试配置验证 - 有效配置
     #[test]
     fn test_validate_config_valid() {
         let config = json!({
             "agents": {
                 "sisyphus": {
-                    "model": "test-model"
+                    "model": "test-provider/test-model"
                 }
             },
             "categories": {
                 "quick": {
-                    "model": "test-model"
+                    "model": "test-provider/test-model"
                 }
             }
         });
@@ -209,6 +682,52 @@ mod tests {
         assert!(validate_config(&config).is_ok());
     }
 
+    /// 测试配置验证 - 缺少 model 的条目允许通过
+    #[test]
+    fn test_validate_config_entry_without_model() {
+        let config = json!({
+            "agents": { "oracle": { "variant": "high" } },
+            "categories": { "writing": {} }
+        });
+
+        assert!(validate_config(&config).is_ok());
+    }
+
+    /// 测试配置验证 - model 不是字符串或格式不正确
+    #[test]
+    fn test_validate_config_model_not_string() {
+        let config = json!({
+            "agents": { "sisyphus": { "model": 42 } },
+            "categories": {}
+        });
+        assert_eq!(
+            validate_config(&config).unwrap_err(),
+            "agents.sisyphus.model 必须是字符串"
+        );
+
+        let config = json!({
+            "agents": {},
+            "categories": { "quick": { "model": "gpt-5" } }
+        });
+        assert!(validate_config(&config)
+            .unwrap_err()
+            .starts_with("categories.quick.model"));
+    }
+
+    /// 测试配置验证 - variant 不是字符串
+    #[test]
+    fn test_validate_config_variant_not_string() {
+        let config = json!({
+            "agents": { "oracle": { "model": "openai/gpt-5", "variant": true } },
+            "categories": {}
+        });
+
+        assert_eq!(
+            validate_config(&config).unwrap_err(),
+            "agents.oracle.variant 必须是字符串"
+        );
+    }
+
     /// 测试配置验证 - 缺少 agents
     #[test]
     fn test_validate_config_missing_agents() {
@@ -345,6 +864,56 @@ mod tests {
         fs::remove_dir_all(&temp_dir).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_write_omo_config_rolls_back_truncated_write() {
+        let temp_dir = std::env::temp_dir().join("omo-write-config-verify-test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        fs::create_dir_all(&config_dir).unwrap();
+        let config_path = config_dir.join("oh-my-openagent.json");
+        let initial = json!({
+            "agents": {"a": {"model": "p/m1"}},
+            "categories": {}
+        });
+        fs::write(
+            &config_path,
+            serde_json::to_string_pretty(&initial).unwrap(),
+        )
+        .unwrap();
+
+        let updated = json!({
+            "agents": {"a": {"model": "p/m2"}},
+            "categories": {}
+        });
+        // 模拟写入“成功”但内容被截断
+        let result = write_omo_config_with(&updated, |path, content, _| {
+            fs::write(path, &content[..content.len() / 2]).map_err(|e| e.to_string())
+        });
+        let restored: Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert!(result.is_err());
+        assert_eq!(restored, initial);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
     #[test]
     #[serial]
     fn test_write_omo_config_is_atomic_and_creates_backup() {
@@ -403,4 +972,131 @@ mod tests {
 
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    #[serial]
+    fn test_ensure_omo_config_exists_creates_once() {
+        let temp_dir = std::env::temp_dir().join("omo-ensure-config-test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        assert!(ensure_omo_config_exists().unwrap());
+        assert_eq!(
+            read_omo_config().unwrap(),
+            json!({ "agents": {}, "categories": {} })
+        );
+
+        // 已存在时不覆盖用户配置
+        let existing = json!({
+            "agents": { "sisyphus": { "model": "anthropic/claude-opus-4-5" } },
+            "categories": {}
+        });
+        write_omo_config(&existing).unwrap();
+        assert!(!ensure_omo_config_exists().unwrap());
+        assert_eq!(read_omo_config().unwrap(), existing);
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_leftover_lock_file_does_not_block() {
+        let temp_dir = std::env::temp_dir().join("omo-config-leftover-lock-test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        // 异常退出的进程留下的锁文件：文件存在但没有进程持有锁
+        let lock_path = get_config_dir().unwrap().join(CONFIG_LOCK_BASENAME);
+        fs::create_dir_all(lock_path.parent().unwrap()).unwrap();
+        fs::write(&lock_path, "999999").unwrap();
+
+        let started = Instant::now();
+        let acquired = acquire_config_lock().map(drop);
+        let elapsed = started.elapsed();
+
+        // 另一个句柄持有锁时获取会等待直到超时
+        let holder = fs::File::open(&lock_path).unwrap();
+        holder.lock_exclusive().unwrap();
+        let blocked = acquire_config_lock().map(drop);
+        holder.unlock().unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        assert!(acquired.is_ok());
+        assert!(elapsed < CONFIG_LOCK_TIMEOUT);
+        assert!(blocked.is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_concurrent_locked_updates_do_not_lose_writes() {
+        let temp_dir = std::env::temp_dir().join("omo-config-lock-test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        write_omo_config(&json!({ "agents": {}, "categories": {} })).unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    with_config_lock(|| {
+                        let mut config = read_omo_config()?;
+                        // 放大“读取-修改-写入”窗口，无锁时必然出现覆盖
+                        std::thread::sleep(Duration::from_millis(10));
+                        config["agents"][format!("agent-{}", i)] = json!({ "model": "m" });
+                        write_omo_config(&config)
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        let config = read_omo_config().unwrap();
+        let agents = config["agents"].as_object().unwrap();
+        assert_eq!(agents.len(), 8);
+        // 所有线程结束后锁已释放，可立即再次获取
+        assert!(acquire_config_lock().is_ok());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
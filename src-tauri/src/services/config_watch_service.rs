@@ -0,0 +1,192 @@
+//! 配置文件监听服务
+//!
+//! 监听 ~/.config/opencode/ 下 oh-my-opencode 配置文件的外部修改，
+//! 变更时回调通知（由 main 转为 Tauri 事件推送给前端并刷新托盘菜单）
+//! 应用自身通过 write_omo_config 写入的内容不会触发回调
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::config_service::{get_config_candidates, is_last_app_write};
+
+/// 推送给前端的配置变更事件名
+pub const CONFIG_CHANGED_EVENT: &str = "omo://config-changed";
+
+/// 合并同一次保存产生的多条文件事件（截断、写入、临时文件重命名等）
+const CONFIG_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+lazy_static::lazy_static! {
+    // 应用运行期间持有监听器，释放即停止监听
+    static ref CONFIG_WATCHER: Mutex<Option<ConfigWatcher>> = Mutex::new(None);
+}
+
+/// 配置文件监听句柄，drop 后停止监听
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// 事件是否涉及任一候选配置文件的创建、修改或删除
+fn is_config_event(event: &Event, config_names: &[OsString]) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| {
+        path.file_name()
+            .is_some_and(|name| config_names.iter().any(|config| config == name))
+    })
+}
+
+/// 当前生效的配置文件内容是否来自应用自身的写入
+fn is_app_own_write(candidates: &[PathBuf]) -> bool {
+    candidates
+        .iter()
+        .find(|path| path.exists())
+        .and_then(|path| fs::read(path).ok())
+        .is_some_and(|content| is_last_app_write(&content))
+}
+
+/// 监听配置文件所在目录，配置文件变化时在后台线程调用 on_change
+///
+/// 监听目录而非文件本身：原子写入与多数编辑器都以重命名替换文件，直接监听文件会丢失后续事件。
+/// 短时间内的连续变化只触发一次回调；变化后内容与应用最近一次写入一致时不回调。
+pub fn watch_config_file<F>(on_change: F) -> Result<ConfigWatcher, String>
+where
+    F: Fn() + Send + 'static,
+{
+    let candidates = get_config_candidates()?;
+    let config_dir = candidates
+        .first()
+        .and_then(|path| path.parent())
+        .ok_or_else(|| "无法确定配置目录".to_string())?
+        .to_path_buf();
+    let config_names: Vec<OsString> = candidates
+        .iter()
+        .filter_map(|path| path.file_name().map(|name| name.to_os_string()))
+        .collect();
+    fs::create_dir_all(&config_dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+
+    let (tx, rx) = mpsc::channel::<()>();
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) if is_config_event(&event, &config_names) => {
+                let _ = tx.send(());
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("配置文件监听出错: {}", e),
+        })
+        .map_err(|e| format!("创建配置文件监听失败: {}", e))?;
+    watcher
+        .watch(&config_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("监听配置目录失败: {}", e))?;
+
+    // 监听器释放后发送端随之关闭，recv 返回 Err，线程退出
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            while rx.recv_timeout(CONFIG_WATCH_DEBOUNCE).is_ok() {}
+            if !is_app_own_write(&candidates) {
+                on_change();
+            }
+        }
+    });
+
+    Ok(ConfigWatcher { _watcher: watcher })
+}
+
+/// 启动应用级配置文件监听，重复调用时替换之前的监听器
+pub fn start_config_watcher<F>(on_change: F) -> Result<(), String>
+where
+    F: Fn() + Send + 'static,
+{
+    let watcher = watch_config_file(on_change)?;
+    let mut guard = CONFIG_WATCHER.lock().unwrap_or_else(|e| e.into_inner());
+    *guard = Some(watcher);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::config_service::{get_config_path, write_omo_config};
+    use serde_json::json;
+    use serial_test::serial;
+    use std::time::Instant;
+
+    /// 等待下一次回调，超时返回 false
+    fn wait_event(rx: &mpsc::Receiver<()>, timeout: Duration) -> bool {
+        rx.recv_timeout(timeout).is_ok()
+    }
+
+    /// 丢弃已到达及稍后到达的回调，直到静默一段时间
+    fn drain_events(rx: &mpsc::Receiver<()>) {
+        while rx.recv_timeout(CONFIG_WATCH_DEBOUNCE * 3).is_ok() {}
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_file_change_triggers_single_event() {
+        let temp_home = std::env::temp_dir().join("omo_config_watch_test");
+        let _ = fs::remove_dir_all(&temp_home);
+        fs::create_dir_all(&temp_home).unwrap();
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_home);
+        }
+
+        // 先创建配置文件再启动监听
+        let config_path = get_config_path().unwrap();
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"{"agents": {}, "categories": {}}"#).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let watcher = watch_config_file(move || {
+            let _ = tx.send(());
+        })
+        .unwrap();
+
+        // 同步屏障：确认监听已生效，并丢弃启动前写入可能迟到的事件
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut barrier_seen = false;
+        while !barrier_seen && Instant::now() < deadline {
+            fs::write(&config_path, r#"{"agents": {}, "categories": {}, "x": 1}"#).unwrap();
+            barrier_seen = wait_event(&rx, Duration::from_secs(1));
+        }
+        drain_events(&rx);
+
+        // 应用自身写入不触发
+        write_omo_config(&json!({ "agents": { "sisyphus": {} }, "categories": {} })).unwrap();
+        let after_app_write = wait_event(&rx, CONFIG_WATCH_DEBOUNCE * 3);
+
+        // 同目录下的其他文件变化不触发
+        fs::write(config_path.with_file_name("unrelated.json"), "{}").unwrap();
+        let after_unrelated = wait_event(&rx, CONFIG_WATCH_DEBOUNCE * 3);
+
+        // 外部修改只触发一次
+        fs::write(
+            &config_path,
+            r#"{"agents": {"oracle": {"model": "openai/gpt-5"}}, "categories": {}}"#,
+        )
+        .unwrap();
+        let after_modify = wait_event(&rx, Duration::from_secs(5));
+        let extra_event = wait_event(&rx, CONFIG_WATCH_DEBOUNCE * 3);
+
+        drop(watcher);
+        unsafe {
+            match original_home {
+                Some(home) => std::env::set_var("HOME", home),
+                None => std::env::remove_var("HOME"),
+            }
+        }
+        let _ = fs::remove_dir_all(&temp_home);
+
+        assert!(barrier_seen);
+        assert!(!after_app_write);
+        assert!(!after_unrelated);
+        assert!(after_modify);
+        assert!(!extra_event);
+    }
+}
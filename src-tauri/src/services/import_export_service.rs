@@ -1,27 +1,100 @@
 use chrono::Local;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 use crate::i18n;
-use crate::services::config_service::{read_omo_config, validate_config, write_omo_config};
+use crate::services::config_cache_service::merge_configs;
+use crate::services::config_service::{
+    acquire_config_lock, get_config_path, read_omo_config, validate_config, with_config_lock,
+    write_omo_config,
+};
+use crate::services::model_service::get_connected_providers;
+use crate::services::{preset_service, provider_store};
 
 const DEFAULT_MAX_BACKUP_RECORDS: usize = 10;
 const MAX_BACKUP_RECORDS_UPPER: usize = 500;
 const BACKUP_PREFIX_OPENAGENT: &str = "oh-my-openagent_";
 const BACKUP_PREFIX_OPENCODE: &str = "oh-my-opencode_";
 const BACKUP_PREFIX_EXPORT: &str = "export_";
+const BACKUP_PREFIX_PRESET: &str = "preset_";
+const BACKUP_SUFFIX_JSON: &str = ".json";
+const BACKUP_SUFFIX_GZIP: &str = ".json.gz";
+/// 备份备注旁文件后缀，例如 export_xxx.json.note
+const BACKUP_NOTE_SUFFIX: &str = ".note";
 
 fn is_managed_backup_filename(filename: &str) -> bool {
     filename.starts_with(BACKUP_PREFIX_OPENAGENT)
         || filename.starts_with(BACKUP_PREFIX_OPENCODE)
         || filename.starts_with(BACKUP_PREFIX_EXPORT)
+        || filename.starts_with(BACKUP_PREFIX_PRESET)
+}
+
+/// 是否为受管理的备份文件（支持 .json 与 gzip 压缩的 .json.gz）
+fn is_managed_backup_file(path: &Path) -> bool {
+    let filename = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    is_managed_backup_filename(filename)
+        && (filename.ends_with(BACKUP_SUFFIX_JSON) || filename.ends_with(BACKUP_SUFFIX_GZIP))
+}
+
+/// 读取备份文件内容，.json.gz 备份会先解压
+fn read_backup_content(path: &Path) -> Result<String, String> {
+    let is_gzip = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|name| name.ends_with(BACKUP_SUFFIX_GZIP));
+    if !is_gzip {
+        return fs::read_to_string(path).map_err(|e| format!("读取备份文件失败: {}", e));
+    }
+
+    let file = fs::File::open(path).map_err(|e| format!("读取备份文件失败: {}", e))?;
+    let mut content = String::new();
+    GzDecoder::new(file)
+        .read_to_string(&mut content)
+        .map_err(|e| format!("解压备份文件失败: {}", e))?;
+    Ok(content)
+}
+
+/// 写入备份文件，compress 为 true 时使用 gzip 压缩
+fn write_backup_content(path: &Path, content: &str, compress: bool) -> Result<(), String> {
+    if !compress {
+        return fs::write(path, content)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("backup_config_failed"), e));
+    }
+
+    let file = fs::File::create(path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("backup_config_failed"), e))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder
+        .write_all(content.as_bytes())
+        .and_then(|_| encoder.finish().map(|_| ()))
+        .map_err(|e| format!("{}: {}", i18n::tr_current("backup_config_failed"), e))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ImportExportSettings {
     max_backup_records: usize,
+    /// 是否将备份写为 gzip 压缩的 .json.gz
+    #[serde(default)]
+    compress_backups: bool,
+}
+
+impl Default for ImportExportSettings {
+    fn default() -> Self {
+        Self {
+            max_backup_records: DEFAULT_MAX_BACKUP_RECORDS,
+            compress_backups: false,
+        }
+    }
 }
 
 fn normalize_max_backup_records(value: usize) -> usize {
@@ -37,41 +110,19 @@ fn get_settings_path() -> Result<PathBuf, String> {
 }
 
 fn load_settings() -> ImportExportSettings {
-    let path = match get_settings_path() {
-        Ok(p) => p,
-        Err(_) => {
-            return ImportExportSettings {
-                max_backup_records: DEFAULT_MAX_BACKUP_RECORDS,
-            };
-        }
+    let Ok(path) = get_settings_path() else {
+        return ImportExportSettings::default();
     };
-
-    if !path.exists() {
-        return ImportExportSettings {
-            max_backup_records: DEFAULT_MAX_BACKUP_RECORDS,
-        };
-    }
-
-    let content = match fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => {
-            return ImportExportSettings {
-                max_backup_records: DEFAULT_MAX_BACKUP_RECORDS,
-            };
-        }
+    let Ok(content) = fs::read_to_string(&path) else {
+        return ImportExportSettings::default();
     };
-
-    let parsed: ImportExportSettings = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(_) => {
-            return ImportExportSettings {
-                max_backup_records: DEFAULT_MAX_BACKUP_RECORDS,
-            };
-        }
+    let Ok(parsed) = serde_json::from_str::<ImportExportSettings>(&content) else {
+        return ImportExportSettings::default();
     };
 
     ImportExportSettings {
         max_backup_records: normalize_max_backup_records(parsed.max_backup_records),
+        compress_backups: parsed.compress_backups,
     }
 }
 
@@ -82,6 +133,7 @@ fn save_settings(settings: &ImportExportSettings) -> Result<(), String> {
     }
     let normalized = ImportExportSettings {
         max_backup_records: normalize_max_backup_records(settings.max_backup_records),
+        compress_backups: settings.compress_backups,
     };
     let content =
         serde_json::to_string_pretty(&normalized).map_err(|e| format!("序列化设置失败: {}", e))?;
@@ -105,12 +157,7 @@ fn get_managed_backup_entries_with_ts() -> Result<Vec<(PathBuf, u64)>, String> {
     for entry in entries {
         let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
         let path = entry.path();
-        let filename = path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or_default();
-        let is_managed = is_managed_backup_filename(filename);
-        if !is_managed || path.extension().and_then(|s| s.to_str()) != Some("json") {
+        if !is_managed_backup_file(&path) {
             continue;
         }
         let metadata = fs::metadata(&path).map_err(|e| format!("获取文件元数据失败: {}", e))?;
@@ -127,6 +174,31 @@ fn get_managed_backup_entries_with_ts() -> Result<Vec<(PathBuf, u64)>, String> {
     Ok(result)
 }
 
+/// 备份文件对应的备注旁文件路径
+fn backup_note_path(backup_path: &Path) -> PathBuf {
+    let mut note_path = backup_path.as_os_str().to_owned();
+    note_path.push(BACKUP_NOTE_SUFFIX);
+    PathBuf::from(note_path)
+}
+
+/// 读取备份备注，不存在或为空时返回 None
+fn read_backup_note(backup_path: &Path) -> Option<String> {
+    fs::read_to_string(backup_note_path(backup_path))
+        .ok()
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty())
+}
+
+/// 删除备份文件并连带清理其备注
+fn remove_backup_file(backup_path: &Path) -> std::io::Result<()> {
+    fs::remove_file(backup_path)?;
+    let note_path = backup_note_path(backup_path);
+    if note_path.exists() {
+        fs::remove_file(note_path)?;
+    }
+    Ok(())
+}
+
 fn prune_backup_history_to_limit(limit: usize) -> Result<usize, String> {
     let normalized_limit = normalize_max_backup_records(limit);
     let entries = get_managed_backup_entries_with_ts()?;
@@ -136,7 +208,7 @@ fn prune_backup_history_to_limit(limit: usize) -> Result<usize, String> {
 
     let mut deleted = 0usize;
     for (path, _) in entries.into_iter().skip(normalized_limit) {
-        fs::remove_file(&path).map_err(|e| format!("删除超限备份失败: {}", e))?;
+        remove_backup_file(&path).map_err(|e| format!("删除超限备份失败: {}", e))?;
         deleted += 1;
     }
     Ok(deleted)
@@ -150,26 +222,46 @@ pub fn set_max_backup_records(limit: usize) -> Result<usize, String> {
     let normalized = normalize_max_backup_records(limit);
     save_settings(&ImportExportSettings {
         max_backup_records: normalized,
+        ..load_settings()
     })?;
     let _ = prune_backup_history_to_limit(normalized)?;
     Ok(normalized)
 }
 
+pub fn get_compress_backups() -> bool {
+    load_settings().compress_backups
+}
+
+/// 设置新备份是否使用 gzip 压缩，已有备份保持原格式
+pub fn set_compress_backups(enabled: bool) -> Result<bool, String> {
+    save_settings(&ImportExportSettings {
+        compress_backups: enabled,
+        ..load_settings()
+    })?;
+    Ok(enabled)
+}
+
 /// 导出当前 OMO 配置到指定路径
 ///
 /// # 参数
 /// - `path`: 导出文件的完整路径（包含文件名）
+/// - `redact`: 是否对导出副本中的敏感字段脱敏
 ///
 /// # 返回
 /// - `Ok(())`: 导出成功
 /// - `Err(String)`: 导出失败，包含错误信息
-pub fn export_config(path: &str) -> Result<(), String> {
+pub fn export_config(path: &str, redact: bool) -> Result<(), String> {
     // 读取当前配置
-    let config = read_omo_config()?;
+    let mut config = read_omo_config()?;
 
     // 验证配置有效性
     validate_config(&config)?;
 
+    // 脱敏只作用于导出副本，不改动实际配置
+    if redact {
+        redact_sensitive_fields(&mut config);
+    }
+
     // 确保目标路径的父目录存在
     let target_path = PathBuf::from(path);
     if let Some(parent) = target_path.parent() {
@@ -189,14 +281,235 @@ pub fn export_config(path: &str) -> Result<(), String> {
 }
 
 /// 导出配置并可选记录导出快照到备份目录
-pub fn export_config_with_history(path: &str, record_history: bool) -> Result<(), String> {
-    export_config(path)?;
+/// redact 为 true 时导出文件中的 baseURL/apiKey 等敏感字段会被替换为 `<redacted>`
+pub fn export_config_with_history(
+    path: &str,
+    record_history: bool,
+    redact: bool,
+) -> Result<(), String> {
+    export_config(path, redact)?;
     if record_history {
         backup_current_config_with_prefix("export")?;
     }
     Ok(())
 }
 
+/// 敏感字段脱敏后的占位值
+const REDACTED_VALUE: &str = "<redacted>";
+
+/// 判断字段名是否属于 baseURL / apiKey / token / secret 一类的敏感字段
+fn is_sensitive_key(key: &str) -> bool {
+    let normalized = key.to_lowercase().replace(['_', '-'], "");
+    normalized == "baseurl"
+        || normalized.ends_with("apikey")
+        || normalized.ends_with("token")
+        || normalized.ends_with("secret")
+}
+
+/// 递归将敏感字段的字符串值替换为占位值，保持 JSON 结构不变
+fn redact_sensitive_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if child.is_string() && is_sensitive_key(key) {
+                    *child = Value::String(REDACTED_VALUE.to_string());
+                } else {
+                    redact_sensitive_fields(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_sensitive_fields),
+        _ => {}
+    }
+}
+
+/// 精简配置时始终保留的顶层字段
+const MINIMAL_CONFIG_KEEP_KEYS: [&str; 3] = ["$schema", "agents", "categories"];
+
+/// 构建精简配置
+/// - 移除 agents/categories 中没有 model 的条目
+/// - strip_extra 为 true 时移除除 $schema/agents/categories 以外的顶层字段
+fn build_minimal_config(config: &Value, strip_extra: bool) -> Value {
+    let mut minimal = config.clone();
+    let Some(obj) = minimal.as_object_mut() else {
+        return minimal;
+    };
+
+    if strip_extra {
+        obj.retain(|key, _| MINIMAL_CONFIG_KEEP_KEYS.contains(&key.as_str()));
+    }
+
+    for section in ["agents", "categories"] {
+        if let Some(entries) = obj.get_mut(section).and_then(|v| v.as_object_mut()) {
+            entries.retain(|_, entry| {
+                entry
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .is_some_and(|m| !m.trim().is_empty())
+            });
+        }
+    }
+
+    minimal
+}
+
+/// 导出精简配置（只读转换，不写入任何文件）
+/// 仅保留配置了 model 的 agent/category，写入由调用方通过正常导入流程完成
+pub fn export_minimal_config(strip_extra: bool) -> Result<Value, String> {
+    let config = read_omo_config()?;
+    Ok(build_minimal_config(&config, strip_extra))
+}
+
+/// 脱敏过程中的自定义供应商 ID 映射（按首次出现顺序编号）
+struct ProviderAliases<'a> {
+    builtin_ids: &'a HashSet<String>,
+    aliases: HashMap<String, String>,
+}
+
+impl ProviderAliases<'_> {
+    fn alias_for(&mut self, provider_id: &str) -> String {
+        if self.builtin_ids.contains(provider_id) {
+            return provider_id.to_string();
+        }
+        let next = self.aliases.len() + 1;
+        self.aliases
+            .entry(provider_id.to_string())
+            .or_insert_with(|| format!("custom-provider-{}", next))
+            .clone()
+    }
+
+    /// 只替换 `provider/model` 中的自定义供应商前缀，其余格式原样保留
+    fn alias_model(&mut self, model: &str) -> Option<String> {
+        let (provider_id, model_id) = model.split_once('/')?;
+        if provider_id.is_empty() || model_id.is_empty() {
+            return None;
+        }
+        Some(format!("{}/{}", self.alias_for(provider_id), model_id))
+    }
+}
+
+/// 构建脱敏配置：敏感字段（baseURL/apiKey/token 等）与普通导出共用同一套遮盖规则，
+/// 另将 agents/categories 中 model 的自定义供应商 ID 替换为 custom-provider-N，结构保持不变
+fn build_redacted_config(config: &Value, builtin_ids: &HashSet<String>) -> Value {
+    let mut redacted = config.clone();
+    redact_sensitive_fields(&mut redacted);
+
+    let mut aliases = ProviderAliases {
+        builtin_ids,
+        aliases: HashMap::new(),
+    };
+    for section in ["agents", "categories"] {
+        let Some(entries) = redacted.get_mut(section).and_then(|v| v.as_object_mut()) else {
+            continue;
+        };
+        for entry in entries.values_mut() {
+            let aliased = entry
+                .get("model")
+                .and_then(|m| m.as_str())
+                .and_then(|model| aliases.alias_model(model));
+            if let Some(model) = aliased {
+                entry["model"] = Value::String(model);
+            }
+        }
+    }
+    redacted
+}
+
+/// 导出脱敏后的配置，用于在公开 issue 中分享（只读转换，不写入任何文件）
+pub fn export_config_redacted() -> Result<Value, String> {
+    let config = read_omo_config()?;
+    let builtin_ids: HashSet<String> = provider_store::load_builtin_provider_presets()
+        .into_keys()
+        .collect();
+    Ok(build_redacted_config(&config, &builtin_ids))
+}
+
+/// Markdown 表格单元格转义：`|` 会破坏列分隔，换行会破坏行结构
+fn escape_markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// 渲染 agents/categories 中某一节的表格行
+fn render_report_section(
+    out: &mut String,
+    title: &str,
+    entries: Option<&serde_json::Map<String, Value>>,
+    connected: &HashSet<String>,
+) {
+    out.push_str(&format!("## {}\n\n", title));
+    let Some(entries) = entries.filter(|entries| !entries.is_empty()) else {
+        out.push_str("_无_\n\n");
+        return;
+    };
+
+    out.push_str("| 名称 | 模型 | Variant | Provider 已连接 |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for (name, entry) in entries {
+        let model = entry
+            .get("model")
+            .and_then(|m| m.as_str())
+            .map(str::trim)
+            .unwrap_or_default();
+        let variant = entry
+            .get("variant")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let provider_status = match model.split_once('/') {
+            Some((provider_id, _)) if connected.contains(provider_id) => "✅",
+            Some(_) => "❌",
+            None => "-",
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape_markdown_cell(name),
+            if model.is_empty() {
+                "-".to_string()
+            } else {
+                escape_markdown_cell(model)
+            },
+            if variant.is_empty() {
+                "-".to_string()
+            } else {
+                escape_markdown_cell(variant)
+            },
+            provider_status
+        ));
+    }
+    out.push('\n');
+}
+
+/// 将配置渲染为 Markdown 报告：按 agent/category 列出 model、variant 及其 provider 是否已连接
+pub fn render_report_markdown(config: &Value, connected: &HashSet<String>) -> String {
+    let mut out = String::from("# OMO 配置报告\n\n");
+    for (section, title) in [("agents", "Agents"), ("categories", "Categories")] {
+        render_report_section(
+            &mut out,
+            title,
+            config.get(section).and_then(|v| v.as_object()),
+            connected,
+        );
+    }
+    out
+}
+
+/// 导出当前配置的可读报告到指定路径，format 目前仅支持 `markdown`
+pub fn export_config_report(path: &str, format: &str) -> Result<(), String> {
+    let config = read_omo_config()?;
+    let connected: HashSet<String> = get_connected_providers()?.into_iter().collect();
+    let report = match format.trim().to_lowercase().as_str() {
+        "markdown" | "md" => render_report_markdown(&config, &connected),
+        other => return Err(format!("不支持的报告格式: {}", other)),
+    };
+
+    let target_path = PathBuf::from(path);
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("create_target_dir_failed"), e))?;
+    }
+    fs::write(&target_path, report)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_export_file_failed"), e))
+}
+
 /// 从文件导入配置（先验证，再备份，再应用）
 ///
 /// # 参数
@@ -233,6 +546,27 @@ pub fn import_config(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// 以合并模式导入配置文件
+///
+/// 与 `import_config` 不同，不会整体覆盖当前配置：
+/// 导入文件中的字段优先，当前配置中导入文件没有的 agent/category 及其他字段保留。
+///
+/// # 参数
+/// - `path`: 导入文件的完整路径
+pub fn import_config_merge(path: &str) -> Result<(), String> {
+    let imported_config = validate_import_file(path)?;
+
+    with_config_lock(|| {
+        let current_config = read_omo_config()?;
+
+        let merged_config = merge_configs(&current_config, &imported_config);
+        validate_config(&merged_config)?;
+
+        backup_current_config()?;
+        write_omo_config(&merged_config)
+    })
+}
+
 /// 验证导入文件的有效性（不应用）
 ///
 /// # 参数
@@ -272,7 +606,7 @@ fn backup_current_config() -> Result<PathBuf, String> {
     backup_current_config_with_prefix("oh-my-openagent")
 }
 
-fn backup_current_config_with_prefix(prefix: &str) -> Result<PathBuf, String> {
+pub(crate) fn backup_current_config_with_prefix(prefix: &str) -> Result<PathBuf, String> {
     let config = read_omo_config()?;
 
     // 获取配置文件所在目录
@@ -288,10 +622,16 @@ fn backup_current_config_with_prefix(prefix: &str) -> Result<PathBuf, String> {
     // 生成带毫秒时间戳的备份文件名，并保证同毫秒下不覆盖
     let now = Local::now();
     let timestamp = now.format("%Y%m%d_%H%M%S_%3f");
-    let mut backup_path = backup_dir.join(format!("{}_{}.json", prefix, timestamp));
+    let compress = get_compress_backups();
+    let suffix = if compress {
+        BACKUP_SUFFIX_GZIP
+    } else {
+        BACKUP_SUFFIX_JSON
+    };
+    let mut backup_path = backup_dir.join(format!("{}_{}{}", prefix, timestamp, suffix));
     let mut idx = 1usize;
     while backup_path.exists() {
-        backup_path = backup_dir.join(format!("{}_{}_{}.json", prefix, timestamp, idx));
+        backup_path = backup_dir.join(format!("{}_{}_{}{}", prefix, timestamp, idx, suffix));
         idx += 1;
     }
 
@@ -299,8 +639,7 @@ fn backup_current_config_with_prefix(prefix: &str) -> Result<PathBuf, String> {
     let json_string = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
 
-    fs::write(&backup_path, json_string)
-        .map_err(|e| format!("{}: {}", i18n::tr_current("backup_config_failed"), e))?;
+    write_backup_content(&backup_path, &json_string, compress)?;
 
     let limit = get_max_backup_records();
     let _ = prune_backup_history_to_limit(limit)?;
@@ -328,13 +667,13 @@ fn ensure_backup_path(path: &str) -> Result<PathBuf, String> {
     if !canonical_target.starts_with(&canonical_dir) {
         return Err("非法备份路径".to_string());
     }
-    if canonical_target.extension().and_then(|s| s.to_str()) != Some("json") {
-        return Err("仅支持 JSON 备份文件".to_string());
-    }
     let filename = canonical_target
         .file_name()
         .and_then(|s| s.to_str())
         .ok_or_else(|| "无效备份文件名".to_string())?;
+    if !(filename.ends_with(BACKUP_SUFFIX_JSON) || filename.ends_with(BACKUP_SUFFIX_GZIP)) {
+        return Err("仅支持 JSON 备份文件".to_string());
+    }
     if !is_managed_backup_filename(filename) {
         return Err("仅允许操作 OMO 生成的备份文件".to_string());
     }
@@ -342,27 +681,110 @@ fn ensure_backup_path(path: &str) -> Result<PathBuf, String> {
     Ok(canonical_target)
 }
 
+/// 恢复备份时失败的步骤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreStep {
+    /// 定位备份文件（不存在或路径非法）
+    Locate,
+    /// 读取或解压备份文件
+    Read,
+    /// 解析备份 JSON
+    Parse,
+    /// 备份内容未通过配置校验
+    Validate,
+    /// 恢复前自动备份当前配置
+    Backup,
+    /// 写入配置文件
+    Write,
+}
+
+/// 恢复备份失败的结构化错误
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreError {
+    pub step: RestoreStep,
+    pub message: String,
+    /// 写入失败后是否已从自动备份回滚当前配置
+    pub rolled_back: bool,
+}
+
+impl RestoreError {
+    fn at(step: RestoreStep) -> impl FnOnce(String) -> Self {
+        move |message| Self {
+            step,
+            message,
+            rolled_back: false,
+        }
+    }
+}
+
 /// 从备份文件恢复配置（会先自动备份当前配置）
-pub fn restore_from_backup(path: &str) -> Result<(), String> {
-    let backup_path = ensure_backup_path(path)?;
-    let content =
-        fs::read_to_string(&backup_path).map_err(|e| format!("读取备份文件失败: {}", e))?;
-    let config: Value =
-        serde_json::from_str(&content).map_err(|e| format!("解析备份文件失败: {}", e))?;
-    validate_config(&config)?;
+/// 写入失败时从刚创建的自动备份回滚当前配置
+pub fn restore_from_backup(path: &str) -> Result<(), RestoreError> {
+    restore_from_backup_with(path, write_omo_config)
+}
 
-    backup_current_config()?;
-    write_omo_config(&config)?;
-    Ok(())
+fn restore_from_backup_with<W>(path: &str, write: W) -> Result<(), RestoreError>
+where
+    W: FnOnce(&Value) -> Result<(), String>,
+{
+    let backup_path = ensure_backup_path(path).map_err(RestoreError::at(RestoreStep::Locate))?;
+    let content = read_backup_content(&backup_path).map_err(RestoreError::at(RestoreStep::Read))?;
+    let config: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("解析备份文件失败: {}", e))
+        .map_err(RestoreError::at(RestoreStep::Parse))?;
+    validate_config(&config).map_err(RestoreError::at(RestoreStep::Validate))?;
+
+    // 自动备份、写入与回滚期间持有配置锁，避免与其他写入交错
+    let _lock = acquire_config_lock().map_err(RestoreError::at(RestoreStep::Backup))?;
+    let auto_backup = backup_current_config().map_err(RestoreError::at(RestoreStep::Backup))?;
+    let Err(write_err) = write(&config) else {
+        return Ok(());
+    };
+
+    let rollback = read_backup_content(&auto_backup)
+        .and_then(|content| {
+            serde_json::from_str::<Value>(&content).map_err(|e| format!("解析自动备份失败: {}", e))
+        })
+        .and_then(|previous| write_omo_config(&previous));
+    Err(match rollback {
+        Ok(()) => RestoreError {
+            step: RestoreStep::Write,
+            message: format!("{}（已从自动备份回滚当前配置）", write_err),
+            rolled_back: true,
+        },
+        Err(rollback_err) => RestoreError {
+            step: RestoreStep::Write,
+            message: format!("{}；回滚失败: {}", write_err, rollback_err),
+            rolled_back: false,
+        },
+    })
 }
 
 /// 删除单条备份记录
 pub fn delete_backup_entry(path: &str) -> Result<(), String> {
     let backup_path = ensure_backup_path(path)?;
-    fs::remove_file(&backup_path).map_err(|e| format!("删除备份失败: {}", e))?;
+    remove_backup_file(&backup_path).map_err(|e| format!("删除备份失败: {}", e))?;
     Ok(())
 }
 
+/// 为单条备份设置备注（保存在同名 .note 旁文件中），备注为空时清除
+pub fn set_backup_note(path: &str, note: &str) -> Result<(), String> {
+    let backup_path = ensure_backup_path(path)?;
+    let note_path = backup_note_path(&backup_path);
+    let note = note.trim();
+
+    if note.is_empty() {
+        if note_path.exists() {
+            fs::remove_file(&note_path).map_err(|e| format!("删除备份备注失败: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    fs::write(&note_path, note).map_err(|e| format!("写入备份备注失败: {}", e))
+}
+
 /// 导出指定备份记录到目标路径
 pub fn export_backup_entry(path: &str, target_path: &str) -> Result<(), String> {
     let backup_path = ensure_backup_path(path)?;
@@ -372,38 +794,24 @@ pub fn export_backup_entry(path: &str, target_path: &str) -> Result<(), String>
         fs::create_dir_all(parent).map_err(|e| format!("创建目标目录失败: {}", e))?;
     }
 
-    let content =
-        fs::read_to_string(&backup_path).map_err(|e| format!("读取备份文件失败: {}", e))?;
+    // 压缩备份导出时还原为普通 JSON
+    let content = read_backup_content(&backup_path)?;
     fs::write(&target, content).map_err(|e| format!("写入导出文件失败: {}", e))?;
     Ok(())
 }
 
 /// 清空备份历史
 pub fn clear_backup_history() -> Result<usize, String> {
-    let home = std::env::var("HOME").map_err(|_| i18n::tr_current("home_env_var_error"))?;
-    let backup_dir = PathBuf::from(home)
-        .join(".config")
-        .join("opencode")
-        .join("backups");
-
-    if !backup_dir.exists() {
-        return Ok(0);
-    }
+    clear_backup_history_keeping(0)
+}
 
-    let entries = fs::read_dir(&backup_dir).map_err(|e| format!("读取备份目录失败: {}", e))?;
+/// 清理备份历史，仅保留最近 keep 条托管备份，返回删除数量
+/// keep 为 0 时等价于全部清空；非托管文件不受影响
+pub fn clear_backup_history_keeping(keep: usize) -> Result<usize, String> {
     let mut deleted = 0usize;
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
-        let path = entry.path();
-        let filename = path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or_default();
-        let is_managed = is_managed_backup_filename(filename);
-        if is_managed && path.extension().and_then(|s| s.to_str()) == Some("json") {
-            fs::remove_file(&path).map_err(|e| format!("删除备份文件失败: {}", e))?;
-            deleted += 1;
-        }
+    for (path, _) in get_managed_backup_entries_with_ts()?.into_iter().skip(keep) {
+        remove_backup_file(&path).map_err(|e| format!("删除备份文件失败: {}", e))?;
+        deleted += 1;
     }
     Ok(deleted)
 }
@@ -426,7 +834,7 @@ pub fn get_backup_history() -> Result<Vec<BackupInfo>, String> {
         return Ok(Vec::new());
     }
 
-    // 读取目录中的所有 .json 文件
+    // 读取目录中的所有 .json / .json.gz 备份文件
     let entries = fs::read_dir(&backup_dir).map_err(|e| format!("读取备份目录失败: {}", e))?;
 
     let mut backups = Vec::new();
@@ -435,14 +843,9 @@ pub fn get_backup_history() -> Result<Vec<BackupInfo>, String> {
         let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
         let path = entry.path();
 
-        // 只处理 .json 文件
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
+        // 只处理受管理的 .json / .json.gz 备份文件
+        if is_managed_backup_file(&path) {
             if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                let is_managed = is_managed_backup_filename(filename);
-                if !is_managed {
-                    continue;
-                }
-
                 // 获取文件元数据
                 let metadata =
                     fs::metadata(&path).map_err(|e| format!("获取文件元数据失败: {}", e))?;
@@ -458,6 +861,8 @@ pub fn get_backup_history() -> Result<Vec<BackupInfo>, String> {
 
                 let op = if filename.starts_with(BACKUP_PREFIX_EXPORT) {
                     "export"
+                } else if filename.starts_with(BACKUP_PREFIX_PRESET) {
+                    "preset"
                 } else {
                     "import"
                 };
@@ -477,6 +882,7 @@ pub fn get_backup_history() -> Result<Vec<BackupInfo>, String> {
                     created_at_ts,
                     size: metadata.len(),
                     operation: op.to_string(),
+                    note: read_backup_note(&path),
                 });
             }
         }
@@ -488,6 +894,53 @@ pub fn get_backup_history() -> Result<Vec<BackupInfo>, String> {
     Ok(backups)
 }
 
+/// 查找最近一份内容可通过配置校验的备份，返回其路径；没有可用备份时返回 None
+pub fn find_latest_valid_backup() -> Option<String> {
+    get_backup_history()
+        .ok()?
+        .into_iter()
+        .find(|backup| {
+            read_backup_content(Path::new(&backup.path))
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .is_some_and(|config| validate_config(&config).is_ok())
+        })
+        .map(|backup| backup.path)
+}
+
+/// 按操作类型与创建时间区间（毫秒，闭区间）筛选备份记录，参数为 None 表示不限制
+fn filter_backup_history(
+    backups: Vec<BackupInfo>,
+    operation: Option<&str>,
+    since_ts: Option<u64>,
+    until_ts: Option<u64>,
+) -> Vec<BackupInfo> {
+    backups
+        .into_iter()
+        .filter(|backup| operation.is_none_or(|op| backup.operation == op))
+        .filter(|backup| since_ts.is_none_or(|since| backup.created_at_ts >= since))
+        .filter(|backup| until_ts.is_none_or(|until| backup.created_at_ts <= until))
+        .collect()
+}
+
+/// 获取筛选后的导入/导出历史记录
+///
+/// # 参数
+/// - `operation`: 操作类型（import/export/preset），None 表示全部
+/// - `since_ts` / `until_ts`: 创建时间戳区间（毫秒，含边界），None 表示不限制
+pub fn get_backup_history_filtered(
+    operation: Option<&str>,
+    since_ts: Option<u64>,
+    until_ts: Option<u64>,
+) -> Result<Vec<BackupInfo>, String> {
+    Ok(filter_backup_history(
+        get_backup_history()?,
+        operation,
+        since_ts,
+        until_ts,
+    ))
+}
+
 /// 备份信息结构
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct BackupInfo {
@@ -503,6 +956,257 @@ pub struct BackupInfo {
     pub size: u64,
     /// 记录类型：import/export
     pub operation: String,
+    /// 用户备注
+    pub note: Option<String>,
+}
+
+/// 应用迁移包格式版本
+const APP_BUNDLE_FORMAT_VERSION: u32 = 1;
+const APP_BUNDLE_MANIFEST_ENTRY: &str = "manifest.json";
+const APP_BUNDLE_CONFIG_ENTRY: &str = "oh-my-opencode.json";
+const APP_BUNDLE_SETTINGS_ENTRY: &str = "import-export-settings.json";
+const APP_BUNDLE_AUTH_ENTRY: &str = "auth.json";
+const APP_BUNDLE_PRESETS_PREFIX: &str = "presets/";
+
+/// 迁移包清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppBundleManifest {
+    format_version: u32,
+    created_at: String,
+    locale: String,
+    include_secrets: bool,
+}
+
+/// 导入迁移包的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppBundleImportResult {
+    /// 迁移包中记录的界面语言，前端据此切换
+    pub locale: String,
+    /// 还原的预设数量
+    pub presets: usize,
+    /// 是否还原了 auth.json 中的凭据
+    pub restored_secrets: bool,
+}
+
+/// 收集迁移包内容：配置、预设、导入导出设置、语言，include_secrets 时附带 auth.json
+fn collect_app_bundle_entries(include_secrets: bool) -> Result<Vec<(String, String)>, String> {
+    let config = read_omo_config()?;
+    validate_config(&config)?;
+
+    let manifest = AppBundleManifest {
+        format_version: APP_BUNDLE_FORMAT_VERSION,
+        created_at: Local::now().to_rfc3339(),
+        locale: i18n::get_locale(),
+        include_secrets,
+    };
+
+    let mut entries = vec![
+        (
+            APP_BUNDLE_MANIFEST_ENTRY.to_string(),
+            serde_json::to_string_pretty(&manifest)
+                .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?,
+        ),
+        (
+            APP_BUNDLE_CONFIG_ENTRY.to_string(),
+            serde_json::to_string_pretty(&config)
+                .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?,
+        ),
+    ];
+
+    let presets_dir = preset_service::get_presets_dir()?;
+    if presets_dir.exists() {
+        let mut preset_files: Vec<PathBuf> = fs::read_dir(&presets_dir)
+            .map_err(|e| format!("读取预设目录失败: {}", e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json")
+            })
+            .collect();
+        preset_files.sort();
+        for path in preset_files {
+            let Some(filename) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path).map_err(|e| format!("读取预设失败: {}", e))?;
+            entries.push((
+                format!("{}{}", APP_BUNDLE_PRESETS_PREFIX, filename),
+                content,
+            ));
+        }
+    }
+
+    let settings_path = get_settings_path()?;
+    if settings_path.exists() {
+        let content =
+            fs::read_to_string(&settings_path).map_err(|e| format!("读取设置失败: {}", e))?;
+        entries.push((APP_BUNDLE_SETTINGS_ENTRY.to_string(), content));
+    }
+
+    if include_secrets {
+        let auth_path = provider_store::get_auth_file_path()?;
+        if auth_path.exists() {
+            let content = fs::read_to_string(&auth_path)
+                .map_err(|e| format!("读取 auth.json 失败: {}", e))?;
+            entries.push((APP_BUNDLE_AUTH_ENTRY.to_string(), content));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// 导出应用迁移包（zip）：配置、预设、导入导出设置与语言
+/// auth.json 含敏感 key，仅在 include_secrets 为 true 时打包
+pub fn export_app_bundle(path: &str, include_secrets: bool) -> Result<(), String> {
+    let entries = collect_app_bundle_entries(include_secrets)?;
+
+    let target_path = PathBuf::from(path);
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("create_target_dir_failed"), e))?;
+    }
+    let file = fs::File::create(&target_path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_export_file_failed"), e))?;
+
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let write_failed = |e: &dyn std::fmt::Display| {
+        format!("{}: {}", i18n::tr_current("write_export_file_failed"), e)
+    };
+    for (name, content) in entries {
+        zip.start_file(name, options)
+            .map_err(|e| write_failed(&e))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| write_failed(&e))?;
+    }
+    zip.finish().map_err(|e| write_failed(&e))?;
+    Ok(())
+}
+
+/// 读取迁移包中的全部条目
+fn read_app_bundle_entries(path: &str) -> Result<HashMap<String, String>, String> {
+    let file = fs::File::open(path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("read_import_file_failed"), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("迁移包格式无效: {}", e))?;
+
+    let mut entries = HashMap::new();
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("读取迁移包条目失败: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .map_err(|e| format!("读取迁移包条目失败: {}", e))?;
+        entries.insert(entry.name().to_string(), content);
+    }
+    Ok(entries)
+}
+
+/// 预设条目只允许 presets/<name>.json，拒绝子目录与路径穿越
+fn app_bundle_preset_filename(entry_name: &str) -> Option<&str> {
+    let filename = entry_name.strip_prefix(APP_BUNDLE_PRESETS_PREFIX)?;
+    let is_safe = filename.ends_with(".json")
+        && !filename.contains(['/', '\\'])
+        && !filename.starts_with('.');
+    is_safe.then_some(filename)
+}
+
+/// 导入应用迁移包：先完整校验所有条目，再备份当前配置并依次还原
+pub fn import_app_bundle(path: &str) -> Result<AppBundleImportResult, String> {
+    let entries = read_app_bundle_entries(path)?;
+
+    // 1. 校验
+    let manifest: AppBundleManifest = entries
+        .get(APP_BUNDLE_MANIFEST_ENTRY)
+        .ok_or("迁移包缺少 manifest.json".to_string())
+        .and_then(|content| {
+            serde_json::from_str(content).map_err(|e| format!("解析 manifest.json 失败: {}", e))
+        })?;
+    if manifest.format_version > APP_BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "迁移包版本 {} 高于当前支持的版本 {}",
+            manifest.format_version, APP_BUNDLE_FORMAT_VERSION
+        ));
+    }
+    // 语言在最后一步才写入，须在覆盖配置与预设之前确认有效
+    if !i18n::is_supported_locale(&manifest.locale) {
+        return Err(format!("迁移包中的语言设置无效: {}", manifest.locale));
+    }
+
+    let config: Value = entries
+        .get(APP_BUNDLE_CONFIG_ENTRY)
+        .ok_or(format!("迁移包缺少 {}", APP_BUNDLE_CONFIG_ENTRY))
+        .and_then(|content| {
+            serde_json::from_str(content)
+                .map_err(|e| format!("{}: {}", i18n::tr_current("json_format_error"), e))
+        })?;
+    validate_config(&config)?;
+
+    let mut presets = Vec::new();
+    for (name, content) in &entries {
+        let Some(filename) = app_bundle_preset_filename(name) else {
+            continue;
+        };
+        serde_json::from_str::<Value>(content)
+            .map_err(|e| format!("预设 {} 格式错误: {}", filename, e))?;
+        presets.push((filename, content));
+    }
+
+    let settings = match entries.get(APP_BUNDLE_SETTINGS_ENTRY) {
+        Some(content) => Some(
+            serde_json::from_str::<ImportExportSettings>(content)
+                .map_err(|e| format!("解析导入导出设置失败: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let auth = match entries.get(APP_BUNDLE_AUTH_ENTRY) {
+        Some(content) => Some(
+            serde_json::from_str::<HashMap<String, provider_store::AuthEntry>>(content)
+                .map_err(|e| format!("解析 auth.json 失败: {}", e))?,
+        ),
+        None => None,
+    };
+
+    // 2. 还原配置（写入前备份当前配置）
+    with_config_lock(|| {
+        if get_config_path()?.exists() {
+            backup_current_config()?;
+        }
+        write_omo_config(&config)
+    })?;
+
+    // 3. 还原预设（同名覆盖，不删除本机已有的其他预设）
+    let presets_dir = preset_service::get_presets_dir()?;
+    if !presets.is_empty() {
+        fs::create_dir_all(&presets_dir).map_err(|e| format!("创建预设目录失败: {}", e))?;
+    }
+    for (filename, content) in &presets {
+        fs::write(presets_dir.join(filename), content)
+            .map_err(|e| format!("写入预设 {} 失败: {}", filename, e))?;
+    }
+
+    // 4. 还原设置、凭据与语言
+    if let Some(settings) = settings {
+        save_settings(&settings)?;
+    }
+    let restored_secrets = auth.is_some();
+    if let Some(auth) = auth {
+        let mut current = provider_store::read_auth_file().unwrap_or_default();
+        current.extend(auth);
+        provider_store::write_auth_file(&current)?;
+    }
+    i18n::set_locale(&manifest.locale)?;
+
+    Ok(AppBundleImportResult {
+        locale: manifest.locale,
+        presets: presets.len(),
+        restored_secrets,
+    })
 }
 
 #[cfg(test)]
@@ -568,7 +1272,7 @@ mod tests {
         let valid_config = json!({
             "agents": {
                 "test": {
-                    "model": "test-model"
+                    "model": "test-provider/test-model"
                 }
             },
             "categories": {}
@@ -588,6 +1292,38 @@ mod tests {
         fs::remove_dir_all(&temp_dir).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_import_config_merge_keeps_local_only_agents() {
+        let (temp_home, _guard) = with_temp_home("omo_test_import_merge");
+        write_omo_config(&json!({
+            "agents": {
+                "sisyphus": {"model": "openai/gpt-5"},
+                "oracle": {"model": "anthropic/claude-opus-4-5"}
+            },
+            "categories": {"quick": {"model": "openai/gpt-5-mini"}}
+        }))
+        .unwrap();
+
+        let import_file = temp_home.join("import.json");
+        fs::write(
+            &import_file,
+            r#"{"agents":{"sisyphus":{"model":"google/gemini-3-pro"}},"categories":{}}"#,
+        )
+        .unwrap();
+
+        import_config_merge(import_file.to_str().unwrap()).unwrap();
+
+        let merged = read_omo_config().unwrap();
+        assert_eq!(merged["agents"]["sisyphus"]["model"], "google/gemini-3-pro");
+        assert_eq!(
+            merged["agents"]["oracle"]["model"],
+            "anthropic/claude-opus-4-5"
+        );
+        assert_eq!(merged["categories"]["quick"]["model"], "openai/gpt-5-mini");
+        assert_eq!(get_backup_history().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_validate_invalid_json() {
         // 创建临时测试文件
@@ -641,6 +1377,150 @@ mod tests {
         fs::remove_dir_all(&temp_dir).unwrap();
     }
 
+    #[test]
+    fn test_redact_sensitive_fields_keeps_structure() {
+        let original = json!({
+            "agents": {
+                "sisyphus": {"model": "my-proxy/gpt-5", "variant": "high"}
+            },
+            "categories": {},
+            "provider": {
+                "my-proxy": {
+                    "options": {
+                        "baseURL": "https://proxy.internal.example.com/v1",
+                        "apiKey": "sk-secret",
+                        "timeout": 30
+                    }
+                }
+            },
+            "mcp": [{"api_key": "abc", "url": "https://mcp.example.com"}]
+        });
+
+        let mut redacted = original.clone();
+        redact_sensitive_fields(&mut redacted);
+
+        let options = &redacted["provider"]["my-proxy"]["options"];
+        assert_eq!(options["baseURL"], REDACTED_VALUE);
+        assert_eq!(options["apiKey"], REDACTED_VALUE);
+        assert_eq!(options["timeout"], 30);
+        assert_eq!(redacted["mcp"][0]["api_key"], REDACTED_VALUE);
+        assert_eq!(redacted["mcp"][0]["url"], "https://mcp.example.com");
+        assert_eq!(redacted["agents"], original["agents"]);
+
+        let keys = |v: &Value| v.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&redacted), keys(&original));
+        assert_eq!(
+            keys(options),
+            keys(&original["provider"]["my-proxy"]["options"])
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_config_with_redact_leaves_config_untouched() {
+        let (temp_home, _guard) = with_temp_home("omo_test_export_redact");
+        let config = json!({
+            "agents": {"sisyphus": {"model": "openai/gpt-5"}},
+            "categories": {},
+            "provider": {"my-proxy": {"options": {"baseURL": "https://proxy.example.com"}}}
+        });
+        write_omo_config(&config).unwrap();
+
+        let export_path = temp_home.join("export.json");
+        export_config_with_history(export_path.to_str().unwrap(), false, true).unwrap();
+
+        let exported: Value =
+            serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+        assert_eq!(
+            exported["provider"]["my-proxy"]["options"]["baseURL"],
+            REDACTED_VALUE
+        );
+        assert_eq!(read_omo_config().unwrap(), config);
+    }
+
+    #[test]
+    fn test_build_minimal_config_drops_entries_without_model() {
+        let config = json!({
+            "$schema": "https://example.com/schema.json",
+            "agents": {
+                "sisyphus": {"model": "openai/gpt-5"},
+                "oracle": {"variant": "high"},
+                "explore": {"model": ""}
+            },
+            "categories": {
+                "quick": {"model": "anthropic/claude-haiku-4-5"},
+                "writing": {}
+            },
+            "disabled_hooks": []
+        });
+
+        let minimal = build_minimal_config(&config, false);
+        assert_eq!(minimal["$schema"], config["$schema"]);
+        assert!(minimal["agents"].get("sisyphus").is_some());
+        assert!(minimal["agents"].get("oracle").is_none());
+        assert!(minimal["agents"].get("explore").is_none());
+        assert!(minimal["categories"].get("quick").is_some());
+        assert!(minimal["categories"].get("writing").is_none());
+        assert!(minimal.get("disabled_hooks").is_some());
+
+        let stripped = build_minimal_config(&config, true);
+        assert_eq!(stripped["$schema"], config["$schema"]);
+        assert!(stripped.get("disabled_hooks").is_none());
+    }
+
+    #[test]
+    fn test_build_redacted_config_masks_custom_providers_and_secrets() {
+        let config = json!({
+            "agents": {
+                "oracle": { "model": "openai/gpt-5" },
+                "explore": { "model": "corp-gateway/qwen-max", "variant": "high" },
+                "librarian": { "model": "corp-gateway/deepseek-v3" },
+                "metis": { "model": "my-proxy/glm-4.6" }
+            },
+            "categories": {
+                "quick": { "model": "corp-gateway/qwen-max" }
+            },
+            "$schema": "https://raw.githubusercontent.com/code-yeongyu/oh-my-opencode/master/assets/oh-my-opencode.schema.json",
+            "prompts": { "oracle": "prompts/foo.md" },
+            "provider": { "corp-gateway": { "baseURL": "https://llm.corp.example.com/v1", "apiKey": "sk-secret" } },
+            "note": "keep this/that text"
+        });
+        let builtin_ids: HashSet<String> = ["openai".to_string()].into_iter().collect();
+
+        let redacted = build_redacted_config(&config, &builtin_ids);
+
+        assert_eq!(redacted["agents"]["oracle"]["model"], "openai/gpt-5");
+        assert_eq!(
+            redacted["agents"]["explore"]["model"],
+            "custom-provider-1/qwen-max"
+        );
+        assert_eq!(redacted["agents"]["explore"]["variant"], "high");
+        assert_eq!(
+            redacted["agents"]["librarian"]["model"],
+            "custom-provider-1/deepseek-v3"
+        );
+        assert_eq!(
+            redacted["agents"]["metis"]["model"],
+            "custom-provider-2/glm-4.6"
+        );
+        assert_eq!(
+            redacted["categories"]["quick"]["model"],
+            "custom-provider-1/qwen-max"
+        );
+        assert_eq!(redacted["$schema"], config["$schema"]);
+        assert_eq!(redacted["prompts"]["oracle"], "prompts/foo.md");
+        assert_eq!(redacted["note"], "keep this/that text");
+        // 敏感字段与普通导出使用同一占位值
+        assert_eq!(
+            redacted["provider"]["corp-gateway"]["baseURL"],
+            REDACTED_VALUE
+        );
+        assert_eq!(
+            redacted["provider"]["corp-gateway"]["apiKey"],
+            REDACTED_VALUE
+        );
+    }
+
     #[test]
     fn test_normalize_max_backup_records_bounds() {
         assert_eq!(normalize_max_backup_records(0), 1);
@@ -649,6 +1529,175 @@ mod tests {
         assert_eq!(normalize_max_backup_records(9999), 500);
     }
 
+    #[test]
+    #[serial]
+    fn test_app_bundle_round_trip() {
+        let (temp_home, _guard) = with_temp_home("omo_test_app_bundle");
+        let config_dir = temp_home.join(".config").join("opencode");
+        fs::create_dir_all(&config_dir).unwrap();
+        let config = json!({
+            "agents": {"sisyphus": {"model": "openai/gpt-5", "variant": "high"}},
+            "categories": {"quick": {"model": "openai/gpt-5-mini"}}
+        });
+        write_omo_config(&config).unwrap();
+
+        let presets_dir = preset_service::get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        let preset = r#"{"agents":{"oracle":{"model":"google/gemini-3-pro"}},"categories":{}}"#;
+        fs::write(presets_dir.join("work.json"), preset).unwrap();
+        set_max_backup_records(7).unwrap();
+
+        let auth_path = provider_store::get_auth_file_path().unwrap();
+        fs::create_dir_all(auth_path.parent().unwrap()).unwrap();
+        fs::write(&auth_path, r#"{"openai":{"type":"api","key":"sk-bundle"}}"#).unwrap();
+
+        let plain_bundle = temp_home.join("bundle.zip");
+        let secret_bundle = temp_home.join("bundle-secrets.zip");
+        export_app_bundle(plain_bundle.to_str().unwrap(), false).unwrap();
+        export_app_bundle(secret_bundle.to_str().unwrap(), true).unwrap();
+
+        let plain_entries = read_app_bundle_entries(plain_bundle.to_str().unwrap()).unwrap();
+        assert!(!plain_entries.contains_key(APP_BUNDLE_AUTH_ENTRY));
+        assert!(plain_entries.contains_key("presets/work.json"));
+
+        // 模拟新机器：清空配置、预设、设置与凭据
+        write_omo_config(&json!({"agents": {}, "categories": {}})).unwrap();
+        fs::remove_dir_all(&presets_dir).unwrap();
+        fs::remove_file(get_settings_path().unwrap()).unwrap();
+        fs::remove_file(&auth_path).unwrap();
+
+        let result = import_app_bundle(secret_bundle.to_str().unwrap()).unwrap();
+        assert_eq!(result.presets, 1);
+        assert!(result.restored_secrets);
+        assert_eq!(read_omo_config().unwrap(), config);
+        assert_eq!(
+            fs::read_to_string(presets_dir.join("work.json")).unwrap(),
+            preset
+        );
+        assert_eq!(get_max_backup_records(), 7);
+        let auth = provider_store::read_auth_file().unwrap();
+        assert_eq!(
+            auth.get("openai").and_then(|entry| entry.key.as_deref()),
+            Some("sk-bundle")
+        );
+
+        assert!(app_bundle_preset_filename("presets/../auth.json").is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_app_bundle_rejects_unknown_locale_before_writing() {
+        let (temp_home, _guard) = with_temp_home("omo_test_app_bundle_locale");
+        let original = json!({"agents": {"sisyphus": {"model": "openai/gpt-5"}}, "categories": {}});
+        write_omo_config(&original).unwrap();
+
+        let bundle = temp_home.join("bundle.zip");
+        export_app_bundle(bundle.to_str().unwrap(), false).unwrap();
+
+        // 篡改 manifest 中的语言，并让迁移包中的配置与本机不同
+        let mut entries = read_app_bundle_entries(bundle.to_str().unwrap()).unwrap();
+        let mut manifest: Value =
+            serde_json::from_str(&entries[APP_BUNDLE_MANIFEST_ENTRY]).unwrap();
+        manifest["locale"] = json!("xx-YY");
+        entries.insert(APP_BUNDLE_MANIFEST_ENTRY.to_string(), manifest.to_string());
+        entries.insert(
+            APP_BUNDLE_CONFIG_ENTRY.to_string(),
+            r#"{"agents": {}, "categories": {}}"#.to_string(),
+        );
+        let tampered = temp_home.join("tampered.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&tampered).unwrap());
+        for (name, content) in &entries {
+            zip.start_file(name.as_str(), zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let result = import_app_bundle(tampered.to_str().unwrap());
+
+        assert!(result.unwrap_err().contains("xx-YY"));
+        assert_eq!(read_omo_config().unwrap(), original);
+        assert!(!temp_home.join(".config/OMO-Switch/locale").exists());
+    }
+
+    #[test]
+    fn test_render_report_markdown_lists_agents() {
+        let config = json!({
+            "agents": {
+                "sisyphus": { "model": "openai/gpt-5", "variant": "high" },
+                "oracle": { "model": "custom/model-a" }
+            },
+            "categories": {
+                "quick": { "model": "openai/gpt-5-mini" }
+            }
+        });
+        let connected: HashSet<String> = ["openai".to_string()].into_iter().collect();
+
+        let report = render_report_markdown(&config, &connected);
+        assert!(report.contains("| sisyphus | openai/gpt-5 | high | ✅ |"));
+        assert!(report.contains("| oracle | custom/model-a | - | ❌ |"));
+        assert!(report.contains("| quick | openai/gpt-5-mini | - | ✅ |"));
+        assert!(render_report_markdown(&json!({}), &connected).contains("_无_"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_latest_valid_backup_skips_invalid() {
+        let (temp_home, _guard) = with_temp_home("omo_test_latest_valid_backup");
+        let backup_dir = temp_home.join(".config").join("opencode").join("backups");
+
+        assert_eq!(find_latest_valid_backup(), None);
+
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(backup_dir.join("export_1.json"), "{not json").unwrap();
+        fs::write(backup_dir.join("export_2.json"), r#"{"categories": {}}"#).unwrap();
+        assert_eq!(find_latest_valid_backup(), None);
+
+        let valid = backup_dir.join("oh-my-openagent_3.json");
+        fs::write(&valid, r#"{"agents": {}, "categories": {}}"#).unwrap();
+        assert_eq!(
+            find_latest_valid_backup(),
+            Some(valid.to_string_lossy().to_string())
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_clear_backup_history_keeping_recent() {
+        let (temp_home, _guard) = with_temp_home("omo_test_backup_clear_keeping");
+        let backup_dir = temp_home.join(".config").join("opencode").join("backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+
+        for name in [
+            "oh-my-openagent_1.json",
+            "export_2.json",
+            "preset_3.json",
+            "export_4.json",
+        ] {
+            fs::write(backup_dir.join(name), "{}").unwrap();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        fs::write(backup_dir.join("manual-note.json"), "{}").unwrap();
+
+        assert_eq!(clear_backup_history_keeping(2).unwrap(), 2);
+        let remaining: Vec<_> = fs::read_dir(&backup_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        let managed_count = remaining
+            .iter()
+            .filter(|name| is_managed_backup_filename(name))
+            .count();
+        assert_eq!(managed_count, 2);
+        assert!(remaining.iter().any(|name| name == "manual-note.json"));
+
+        // 保留数量不少于现有数量时不删除
+        assert_eq!(clear_backup_history_keeping(5).unwrap(), 0);
+        // keep = 0 等价于全部清空
+        assert_eq!(clear_backup_history_keeping(0).unwrap(), 2);
+        assert!(backup_dir.join("manual-note.json").exists());
+    }
+
     #[test]
     #[serial]
     fn test_set_max_backup_records_prunes_managed_only() {
@@ -679,6 +1728,84 @@ mod tests {
         assert!(entries.iter().any(|name| name == "manual-note.json"));
     }
 
+    #[test]
+    #[serial]
+    fn test_compressed_backup_can_be_restored() {
+        let (temp_home, _guard) = with_temp_home("omo_test_backup_gzip");
+        let original = json!({
+            "agents": {"sisyphus": {"model": "openai/gpt-5"}},
+            "categories": {}
+        });
+        write_omo_config(&original).unwrap();
+
+        set_compress_backups(true).unwrap();
+        assert!(get_compress_backups());
+        assert_eq!(get_max_backup_records(), DEFAULT_MAX_BACKUP_RECORDS);
+        let backup_path = backup_current_config_with_prefix("oh-my-openagent").unwrap();
+        assert!(backup_path.to_string_lossy().ends_with(BACKUP_SUFFIX_GZIP));
+
+        // 旧的未压缩备份仍然可见
+        let backup_dir = temp_home.join(".config").join("opencode").join("backups");
+        fs::write(backup_dir.join("export_legacy.json"), "{}").unwrap();
+        assert_eq!(get_backup_history().unwrap().len(), 2);
+
+        write_omo_config(&json!({
+            "agents": {"sisyphus": {"model": "anthropic/claude-opus-4-5"}},
+            "categories": {}
+        }))
+        .unwrap();
+        restore_from_backup(backup_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(read_omo_config().unwrap(), original);
+    }
+
+    #[test]
+    #[serial]
+    fn test_restore_from_backup_rolls_back_on_write_failure() {
+        let (_temp_home, _guard) = with_temp_home("omo_test_restore_rollback");
+        let backup_config = json!({
+            "agents": {"sisyphus": {"model": "openai/gpt-5"}},
+            "categories": {}
+        });
+        write_omo_config(&backup_config).unwrap();
+        let backup_path = backup_current_config_with_prefix("export").unwrap();
+
+        let current = json!({
+            "agents": {"sisyphus": {"model": "anthropic/claude-opus-4-5"}},
+            "categories": {}
+        });
+        write_omo_config(&current).unwrap();
+
+        // 模拟写入了一半后失败
+        let err = restore_from_backup_with(backup_path.to_str().unwrap(), |_| {
+            let path = crate::services::config_service::get_config_path()?;
+            fs::write(path, "{\"agents\":").unwrap();
+            Err("磁盘已满".to_string())
+        })
+        .unwrap_err();
+
+        assert_eq!(err.step, RestoreStep::Write);
+        assert!(err.rolled_back);
+        assert!(err.message.contains("磁盘已满"));
+        assert_eq!(read_omo_config().unwrap(), current);
+    }
+
+    #[test]
+    #[serial]
+    fn test_restore_from_backup_reports_validate_step() {
+        let (temp_home, _guard) = with_temp_home("omo_test_restore_validate");
+        let backup_dir = temp_home.join(".config").join("opencode").join("backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+        let invalid_backup = backup_dir.join("export_invalid.json");
+        fs::write(&invalid_backup, r#"{"agents":{}}"#).unwrap();
+
+        let err = restore_from_backup(invalid_backup.to_str().unwrap()).unwrap_err();
+
+        assert_eq!(err.step, RestoreStep::Validate);
+        assert!(!err.rolled_back);
+        assert_eq!(get_backup_history().unwrap().len(), 1);
+    }
+
     #[test]
     #[serial]
     fn test_get_backup_history_filters_and_marks_operation() {
@@ -688,18 +1815,96 @@ mod tests {
 
         fs::write(backup_dir.join("oh-my-openagent_a.json"), "{}").unwrap();
         fs::write(backup_dir.join("export_b.json"), "{}").unwrap();
+        fs::write(backup_dir.join("preset_c.json"), "{}").unwrap();
         fs::write(backup_dir.join("random.json"), "{}").unwrap();
 
         let history = get_backup_history().unwrap();
-        assert_eq!(history.len(), 2);
+        assert_eq!(history.len(), 3);
 
         let ops: HashSet<_> = history.iter().map(|x| x.operation.as_str()).collect();
         assert!(ops.contains("import"));
         assert!(ops.contains("export"));
+        assert!(ops.contains("preset"));
 
         let names: HashSet<_> = history.iter().map(|x| x.filename.as_str()).collect();
         assert!(names.contains("oh-my-openagent_a.json"));
         assert!(names.contains("export_b.json"));
         assert!(!names.contains("random.json"));
     }
+
+    #[test]
+    fn test_filter_backup_history_by_operation_and_time_range() {
+        let backup = |filename: &str, operation: &str, created_at_ts: u64| BackupInfo {
+            filename: filename.to_string(),
+            path: format!("/tmp/{}", filename),
+            created_at: String::new(),
+            created_at_ts,
+            size: 2,
+            operation: operation.to_string(),
+            note: None,
+        };
+        let backups = || {
+            vec![
+                backup("export_3.json", "export", 3_000),
+                backup("oh-my-openagent_2.json", "import", 2_000),
+                backup("export_1.json", "export", 1_000),
+                backup("preset_0.json", "preset", 500),
+            ]
+        };
+        let names =
+            |list: Vec<BackupInfo>| list.into_iter().map(|b| b.filename).collect::<Vec<_>>();
+
+        assert_eq!(
+            names(filter_backup_history(backups(), Some("export"), None, None)),
+            vec!["export_3.json", "export_1.json"]
+        );
+        assert_eq!(
+            names(filter_backup_history(
+                backups(),
+                None,
+                Some(1_000),
+                Some(2_000)
+            )),
+            vec!["oh-my-openagent_2.json", "export_1.json"]
+        );
+        assert_eq!(
+            names(filter_backup_history(
+                backups(),
+                Some("export"),
+                Some(2_000),
+                None
+            )),
+            vec!["export_3.json"]
+        );
+        assert_eq!(filter_backup_history(backups(), None, None, None).len(), 4);
+    }
+
+    #[test]
+    #[serial]
+    fn test_backup_note_listed_in_history_and_removed_with_backup() {
+        let (temp_home, _guard) = with_temp_home("omo_test_backup_note");
+        let backup_dir = temp_home.join(".config").join("opencode").join("backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+        let backup = backup_dir.join("export_note.json");
+        fs::write(&backup, r#"{"agents":{},"categories":{}}"#).unwrap();
+        let backup_path = backup.to_str().unwrap();
+
+        set_backup_note(backup_path, "  升级前  ").unwrap();
+        let history = get_backup_history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].note.as_deref(), Some("升级前"));
+
+        // 清空备注
+        set_backup_note(backup_path, "").unwrap();
+        assert_eq!(get_backup_history().unwrap()[0].note, None);
+
+        set_backup_note(backup_path, "保留").unwrap();
+        let note_path = backup_note_path(&backup);
+        assert!(note_path.exists());
+
+        delete_backup_entry(backup_path).unwrap();
+        assert!(!backup.exists());
+        assert!(!note_path.exists());
+        assert!(get_backup_history().unwrap().is_empty());
+    }
 }
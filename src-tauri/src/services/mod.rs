@@ -1,8 +1,10 @@
 pub mod config_cache_service;
 pub mod config_service;
+pub mod config_watch_service;
 pub mod import_export_service;
 pub mod model_service;
 pub mod preset_service;
 pub mod provider_service;
 pub mod provider_store;
+pub mod repair_service;
 pub mod version_service;
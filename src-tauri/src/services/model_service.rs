@@ -7,6 +7,7 @@ use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use crate::services::provider_store;
@@ -18,6 +19,12 @@ pub struct ModelInfo {
     pub name: Option<String>,
     pub description: Option<String>,
     pub pricing: Option<ModelPricing>,
+    /// 上下文窗口大小（token 数）
+    pub context_limit: Option<u64>,
+    /// 支持的输入模态，例如 ["text", "image"]
+    pub modalities: Option<Vec<String>>,
+    /// 是否支持工具调用
+    pub supports_tools: Option<bool>,
 }
 
 /// 模型定价信息
@@ -28,6 +35,17 @@ pub struct ModelPricing {
     pub currency: Option<String>,
 }
 
+/// 附带目标货币换算结果的模型信息，原始 pricing 保持不变
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfoWithCurrency {
+    #[serde(flatten)]
+    pub info: ModelInfo,
+    /// 换算为目标货币后的定价，无法换算时为 None
+    pub converted_pricing: Option<ModelPricing>,
+    /// 是否已成功换算为目标货币
+    pub currency_converted: bool,
+}
+
 /// 本地缓存的模型列表结构 - 对应 provider-models.json
 /// 支持两种格式：模型字符串数组 或 模型对象数组
 #[derive(Debug, Deserialize)]
@@ -97,6 +115,37 @@ struct ModelsDevModel {
     name: Option<String>,
     description: Option<String>,
     pricing: Option<ModelsDevPricing>,
+    limit: Option<ModelsDevLimit>,
+    modalities: Option<ModelsDevModalities>,
+    tool_call: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsDevLimit {
+    context: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelsDevModalities {
+    input: Option<Vec<String>>,
+}
+
+impl From<ModelsDevModel> for ModelInfo {
+    fn from(m: ModelsDevModel) -> Self {
+        ModelInfo {
+            id: m.id,
+            name: m.name,
+            description: m.description,
+            pricing: m.pricing.map(|p| ModelPricing {
+                prompt: p.prompt,
+                completion: p.completion,
+                currency: p.currency,
+            }),
+            context_limit: m.limit.and_then(|l| l.context),
+            modalities: m.modalities.and_then(|mods| mods.input),
+            supports_tools: m.tool_call,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -109,9 +158,12 @@ struct ModelsDevPricing {
 /// 获取缓存目录路径（与 oh-my-opencode CLI 保持一致）
 /// 统一使用 ~/.cache/oh-my-opencode/
 fn get_cache_dir() -> Result<PathBuf, String> {
-    std::env::var("HOME")
-        .map(|home| PathBuf::from(home).join(".cache").join("oh-my-opencode"))
-        .map_err(|_| "无法获取 HOME 环境变量".to_string())
+    provider_store::omo_cache_dir()
+}
+
+/// 校验覆盖层缓存文件路径
+pub(crate) fn get_verified_models_path() -> Result<PathBuf, String> {
+    Ok(get_cache_dir()?.join("verified-provider-models.json"))
 }
 
 /// 获取可用模型列表，按提供商分组（缓存快照）
@@ -137,11 +189,16 @@ fn parse_opencode_models_output(output: &str) -> HashMap<String, Vec<String>> {
     let mut result: HashMap<String, Vec<String>> = HashMap::new();
 
     for line in output.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
+        // 去掉前导标记（如 `* `、`- `），只取第一个空白前的 id，丢弃 `(default)` 等附加说明
+        let Some(token) = line
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '*' | '-' | '•' | '>'))
+            .split_whitespace()
+            .next()
+        else {
             continue;
-        }
-        let Some((provider_id, model_id)) = trimmed.split_once('/') else {
+        };
+        let token = token.trim_end_matches('*');
+        let Some((provider_id, model_id)) = token.split_once('/') else {
             continue;
         };
         if provider_id.is_empty() || model_id.is_empty() {
@@ -179,16 +236,77 @@ fn build_opencode_path_env() -> Option<String> {
     let opencode_bin = PathBuf::from(home).join(".opencode").join("bin");
     let opencode_bin_str = opencode_bin.to_string_lossy().to_string();
     let current_path = env::var("PATH").unwrap_or_default();
-    if current_path
-        .split(':')
-        .any(|p| p == opencode_bin.as_os_str().to_string_lossy())
-    {
-        Some(current_path)
-    } else if current_path.is_empty() {
-        Some(opencode_bin_str)
-    } else {
-        Some(format!("{}:{}", opencode_bin_str, current_path))
+    let login_path = get_login_shell_path().unwrap_or_default();
+    Some(merge_path_entries(&[
+        &opencode_bin_str,
+        &current_path,
+        login_path,
+    ]))
+}
+
+/// 合并多个 PATH 字符串，按出现顺序去重并忽略空条目
+fn merge_path_entries(paths: &[&str]) -> String {
+    let mut seen: HashSet<&str> = HashSet::new();
+    paths
+        .iter()
+        .flat_map(|path| path.split(':'))
+        .filter(|entry| !entry.is_empty() && seen.insert(*entry))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 登录 shell 的 PATH 只探测一次，避免每次执行 `opencode models` 都启动 shell
+static LOGIN_SHELL_PATH: OnceLock<Option<String>> = OnceLock::new();
+const LOGIN_SHELL_PATH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// macOS 从 GUI 启动时继承的 PATH 很精简，通过 `$SHELL -lc 'echo $PATH'`
+/// 读取登录 shell 的 PATH，以找到 volta/asdf/nvm 等管理的 opencode
+fn get_login_shell_path() -> Option<&'static str> {
+    LOGIN_SHELL_PATH
+        .get_or_init(|| {
+            if cfg!(target_os = "macos") && !cfg!(test) {
+                read_login_shell_path()
+            } else {
+                None
+            }
+        })
+        .as_deref()
+}
+
+fn read_login_shell_path() -> Option<String> {
+    let shell = env::var("SHELL")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "/bin/zsh".to_string());
+    let mut child = Command::new(shell)
+        .args(["-lc", "echo $PATH"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => break,
+            Ok(Some(_)) | Err(_) => return None,
+            Ok(None) if start.elapsed() > LOGIN_SHELL_PATH_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+        }
     }
+
+    let output = child.wait_with_output().ok()?;
+    // 登录 shell 可能先输出欢迎信息，PATH 取最后一个非空行
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
 }
 
 fn build_opencode_candidates() -> Vec<String> {
@@ -314,6 +432,83 @@ fn get_available_models_from_opencode_cmd() -> Result<HashMap<String, Vec<String
     ))
 }
 
+/// `opencode models` 校验结果的内存缓存有效期，避免频繁重建托盘/刷新时反复 spawn 子进程
+const OPENCODE_MODELS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+struct CachedOpencodeModels {
+    fetched_at: Instant,
+    models: HashMap<String, Vec<String>>,
+}
+
+static OPENCODE_MODELS_CACHE: Mutex<Option<CachedOpencodeModels>> = Mutex::new(None);
+
+/// TTL 内直接返回缓存结果，否则调用 fetch 并缓存成功结果（失败不缓存）
+fn get_models_with_cache<F>(
+    cache: &Mutex<Option<CachedOpencodeModels>>,
+    ttl: Duration,
+    fetch: F,
+) -> Result<HashMap<String, Vec<String>>, String>
+where
+    F: FnOnce() -> Result<HashMap<String, Vec<String>>, String>,
+{
+    let mut cached = cache.lock().map_err(|e| format!("模型缓存锁异常: {}", e))?;
+    if let Some(entry) = cached.as_ref() {
+        if entry.fetched_at.elapsed() < ttl {
+            return Ok(entry.models.clone());
+        }
+    }
+
+    let models = fetch()?;
+    *cached = Some(CachedOpencodeModels {
+        fetched_at: Instant::now(),
+        models: models.clone(),
+    });
+    Ok(models)
+}
+
+fn get_available_models_from_opencode_cmd_cached() -> Result<HashMap<String, Vec<String>>, String> {
+    get_models_with_cache(
+        &OPENCODE_MODELS_CACHE,
+        OPENCODE_MODELS_CACHE_TTL,
+        get_available_models_from_opencode_cmd,
+    )
+}
+
+/// 主动使 `opencode models` 内存缓存失效，下次获取时重新执行命令
+pub fn invalidate_models_cache() {
+    if let Ok(mut cached) = OPENCODE_MODELS_CACHE.lock() {
+        *cached = None;
+    }
+}
+
+/// 最近使用模型 LRU 列表的容量
+const RECENT_MODELS_LIMIT: usize = 8;
+
+/// 将 `provider/model` 移到最近使用列表最前面（LRU），超出容量的旧条目被丢弃
+pub fn record_recent_model(provider: &str, model: &str) -> Result<(), String> {
+    let full_id = qualify_model_id(provider, model);
+
+    let mut models = provider_store::read_recent_models();
+    models.retain(|existing| existing != &full_id);
+    models.insert(0, full_id);
+    models.truncate(RECENT_MODELS_LIMIT);
+    provider_store::write_recent_models(&models)
+}
+
+/// 将模型 ID 规范为 `provider/model` 格式；已带该 provider 前缀时原样返回
+pub(crate) fn qualify_model_id(provider: &str, model: &str) -> String {
+    if model.starts_with(&format!("{}/", provider)) {
+        model.to_string()
+    } else {
+        format!("{}/{}", provider, model)
+    }
+}
+
+/// 获取最近使用的模型（`provider/model` 格式，最近的在前）
+pub fn get_recent_models() -> Vec<String> {
+    provider_store::read_recent_models()
+}
+
 fn merge_custom_models(result: &mut HashMap<String, Vec<String>>) {
     let custom_models = provider_store::get_custom_models();
     for (provider_id, models) in custom_models {
@@ -327,10 +522,9 @@ fn merge_custom_models(result: &mut HashMap<String, Vec<String>>) {
 }
 
 fn read_verified_models_override() -> HashMap<String, Vec<String>> {
-    let Ok(cache_dir) = get_cache_dir() else {
+    let Ok(cache_file) = get_verified_models_path() else {
         return HashMap::new();
     };
-    let cache_file = cache_dir.join("verified-provider-models.json");
     if !cache_file.exists() {
         return HashMap::new();
     }
@@ -352,10 +546,15 @@ fn get_cached_available_models() -> Result<HashMap<String, Vec<String>>, String>
         let content = fs::read_to_string(&cache_file)
             .map_err(|e| format!("{}: {}", i18n::tr_current("read_model_cache_failed"), e))?;
 
-        let cache: ProviderModelsCache = serde_json::from_str(&content)
-            .map_err(|e| format!("{}: {}", i18n::tr_current("parse_model_cache_failed"), e))?;
-
-        cache.into_string_map()
+        match serde_json::from_str::<ProviderModelsCache>(&content) {
+            Ok(cache) => cache.into_string_map(),
+            Err(e) => {
+                // 缓存损坏时保留现场并降级为空，opencode 下次运行会重建
+                let reason = format!("{}: {}", i18n::tr_current("parse_model_cache_failed"), e);
+                provider_store::quarantine_corrupt_cache(&cache_file, &reason);
+                HashMap::new()
+            }
+        }
     } else {
         HashMap::new()
     };
@@ -376,7 +575,7 @@ fn write_verified_models_override(models: &HashMap<String, Vec<String>>) -> Resu
     fs::create_dir_all(&cache_dir)
         .map_err(|e| format!("创建模型缓存目录失败 {:?}: {}", cache_dir, e))?;
 
-    let cache_file = cache_dir.join("verified-provider-models.json");
+    let cache_file = get_verified_models_path()?;
     let payload = VerifiedModelsCache {
         models: models.clone(),
     };
@@ -391,19 +590,123 @@ pub fn get_available_models() -> Result<HashMap<String, Vec<String>>, String> {
     get_cached_available_models()
 }
 
+/// 按子串（不区分大小写）过滤模型 ID，只保留有命中的供应商分组；空查询返回全部
+fn filter_models_by_query(
+    models: HashMap<String, Vec<String>>,
+    query: &str,
+) -> HashMap<String, Vec<String>> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return models;
+    }
+
+    models
+        .into_iter()
+        .filter_map(|(provider_id, list)| {
+            let matched: Vec<String> = list
+                .into_iter()
+                .filter(|model_id| model_id.to_lowercase().contains(&query))
+                .collect();
+            (!matched.is_empty()).then_some((provider_id, matched))
+        })
+        .collect()
+}
+
+/// 在可用模型列表中搜索模型 ID
+pub fn search_models(query: &str) -> Result<HashMap<String, Vec<String>>, String> {
+    Ok(filter_models_by_query(get_available_models()?, query))
+}
+
+/// 统计每个供应商的模型数量，已连接但没有模型的供应商计为 0
+fn count_models_by_provider(
+    models: &HashMap<String, Vec<String>>,
+    connected: &[String],
+) -> HashMap<String, usize> {
+    let mut counts: HashMap<String, usize> = models
+        .iter()
+        .map(|(provider_id, list)| (provider_id.clone(), list.len()))
+        .collect();
+    for provider_id in connected {
+        counts.entry(provider_id.clone()).or_insert(0);
+    }
+    counts
+}
+
+/// 获取每个供应商暴露的模型数量，供概览面板使用而无需传输完整列表
+pub fn get_provider_model_counts() -> Result<HashMap<String, usize>, String> {
+    let models = get_available_models()?;
+    let connected = get_connected_providers().unwrap_or_default();
+    Ok(count_models_by_provider(&models, &connected))
+}
+
+/// 分块推送模型列表时每块包含的模型数量
+pub const MODEL_STREAM_CHUNK_SIZE: usize = 50;
+
+/// `omo://models-chunk` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsChunkPayload {
+    pub provider_id: String,
+    pub index: usize,
+    pub models: Vec<String>,
+}
+
+/// `omo://models-done` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsDonePayload {
+    pub provider_id: String,
+    pub total: usize,
+}
+
+/// 获取单个供应商的模型列表（含自定义模型），供分块推送使用
+pub fn get_provider_models(provider_id: &str) -> Result<Vec<String>, String> {
+    Ok(get_cached_available_models()?
+        .remove(provider_id)
+        .unwrap_or_default())
+}
+
+/// 将模型列表按固定大小切分为事件负载
+pub fn build_model_chunks(provider_id: &str, models: &[String]) -> Vec<ModelsChunkPayload> {
+    models
+        .chunks(MODEL_STREAM_CHUNK_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| ModelsChunkPayload {
+            provider_id: provider_id.to_string(),
+            index,
+            models: chunk.to_vec(),
+        })
+        .collect()
+}
+
+/// 从模型映射中移除被临时禁用的供应商
+pub fn remove_disabled_providers(models: &mut HashMap<String, Vec<String>>) {
+    let disabled = provider_store::read_disabled_providers();
+    models.retain(|provider_id, _| !disabled.contains(provider_id));
+}
+
 /// 获取通过 `opencode models` 校验后的可用模型列表
 /// 用于异步校验阶段，避免缓存中包含不在 opencode 可用集合内的旧模型。
 pub fn get_verified_available_models() -> Result<HashMap<String, Vec<String>>, String> {
-    let mut result = get_available_models_from_opencode_cmd()?;
+    let mut result = get_available_models_from_opencode_cmd_cached()?;
     merge_custom_models(&mut result);
     Ok(result)
 }
 
+/// 跳过内存缓存重新执行 `opencode models` 校验，并写入校验覆盖层，
+/// 使托盘等读取缓存文件的入口立即看到新连接供应商的模型
+pub fn refresh_verified_available_models() -> Result<HashMap<String, Vec<String>>, String> {
+    invalidate_models_cache();
+    let verified = get_available_models_from_opencode_cmd_cached()?;
+    write_verified_models_override(&verified)?;
+    get_verified_available_models()
+}
+
 /// 统一返回模型及其来源状态（方案三：后端单一裁决）
 pub fn get_available_models_with_status() -> Result<AvailableModelsWithStatus, String> {
     let validated_at = Utc::now().to_rfc3339();
 
-    match get_available_models_from_opencode_cmd() {
+    match get_available_models_from_opencode_cmd_cached() {
         Ok(mut verified_models) => {
             // 校验结果只用于覆盖对应 provider，避免把总表收缩成“仅可用 provider”
             let mut merged_models = get_cached_available_models()?;
@@ -476,7 +779,7 @@ pub fn get_connected_providers() -> Result<Vec<String>, String> {
 }
 
 /// models.dev 缓存文件路径
-fn get_models_dev_cache_path() -> Option<PathBuf> {
+pub(crate) fn get_models_dev_cache_path() -> Option<PathBuf> {
     get_cache_dir()
         .ok()
         .map(|p| p.join("models-dev-cache.json"))
@@ -499,19 +802,74 @@ fn now_unix_secs() -> u64 {
         .as_secs()
 }
 
-/// 缓存有效期：30 分钟
-const CACHE_TTL_SECS: u64 = 30 * 60;
+/// 默认缓存有效期：30 分钟
+const DEFAULT_CACHE_TTL_SECS: u64 = 30 * 60;
+/// 默认请求超时：5 秒
+const DEFAULT_MODELS_DEV_TIMEOUT_SECS: u64 = 5;
+/// 网络错误时的最大重试次数
+const MODELS_DEV_MAX_RETRIES: u32 = 2;
+/// 重试退避基准时长，每次重试翻倍
+const MODELS_DEV_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// models.dev 相关设置（~/.config/OMO-Switch/model-dev-settings.json）
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelsDevSettings {
+    /// 缓存有效期（秒），0 表示每次都重新拉取
+    cache_ttl_secs: Option<u64>,
+    /// 单次请求超时（秒）
+    request_timeout_secs: Option<u64>,
+}
+
+fn get_models_dev_settings_path() -> Result<PathBuf, String> {
+    let home = env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("OMO-Switch")
+        .join("model-dev-settings.json"))
+}
+
+/// 读取 models.dev 设置，文件缺失或损坏时返回默认值
+fn read_models_dev_settings() -> ModelsDevSettings {
+    get_models_dev_settings_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<ModelsDevSettings>(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 读取 models.dev 缓存有效期，配置缺失或损坏时回退到 30 分钟
+fn get_models_dev_cache_ttl_secs() -> u64 {
+    read_models_dev_settings()
+        .cache_ttl_secs
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS)
+}
+
+/// 读取 models.dev 请求超时，配置缺失或为 0 时回退到 5 秒
+fn get_models_dev_timeout_secs() -> u64 {
+    read_models_dev_settings()
+        .request_timeout_secs
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_MODELS_DEV_TIMEOUT_SECS)
+}
+
+fn is_models_dev_cache_expired(cached_at: u64, now: u64, ttl_secs: u64) -> bool {
+    now.saturating_sub(cached_at) >= ttl_secs
+}
 
 /// 读取本地 models.dev 缓存（仅在有效期内）
 fn read_models_dev_cache() -> Option<Vec<ModelInfo>> {
     let cache_path = get_models_dev_cache_path()?;
     let content = fs::read_to_string(&cache_path).ok()?;
     let cache: ModelsDevCache = serde_json::from_str(&content).ok()?;
-    let age = now_unix_secs().saturating_sub(cache.cached_at);
-    if age < CACHE_TTL_SECS {
-        Some(cache.models)
-    } else {
+    if is_models_dev_cache_expired(
+        cache.cached_at,
+        now_unix_secs(),
+        get_models_dev_cache_ttl_secs(),
+    ) {
         None
+    } else {
+        Some(cache.models)
     }
 }
 
@@ -545,12 +903,77 @@ fn read_expired_cache() -> Vec<ModelInfo> {
     Vec::new()
 }
 
+/// models.dev 请求失败原因：仅网络错误会触发重试
+#[derive(Debug)]
+enum ModelsDevFetchError {
+    /// 连接失败、超时或服务端 5xx
+    Network(String),
+    /// 响应状态异常或解析失败，不重试
+    Invalid(String),
+}
+
+/// 执行请求，网络错误时按指数退避重试（最多 max_retries 次），解析错误直接返回
+fn fetch_with_retry<T, F>(
+    mut request: F,
+    max_retries: u32,
+    backoff: Duration,
+) -> Result<T, ModelsDevFetchError>
+where
+    F: FnMut() -> Result<T, ModelsDevFetchError>,
+{
+    let mut attempt = 0;
+    loop {
+        match request() {
+            Err(ModelsDevFetchError::Network(e)) if attempt < max_retries => {
+                eprintln!("models.dev 请求失败（{}），第 {} 次重试", e, attempt + 1);
+                std::thread::sleep(backoff * 2u32.pow(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn request_models_dev(timeout: Duration) -> Result<ModelsDevResponse, ModelsDevFetchError> {
+    let response = ureq::get("https://models.dev/api.json")
+        .timeout(timeout)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) if code < 500 => {
+                ModelsDevFetchError::Invalid(format!("models.dev API 返回状态码 {}", code))
+            }
+            e => ModelsDevFetchError::Network(format!("models.dev API 不可用（{}）", e)),
+        })?;
+    response
+        .into_json::<ModelsDevResponse>()
+        .map_err(|e| ModelsDevFetchError::Invalid(format!("解析 models.dev API 响应失败（{}）", e)))
+}
+
+/// 请求 models.dev API 并在成功后写入缓存
+fn fetch_models_dev_from_api() -> Result<Vec<ModelInfo>, String> {
+    let timeout = Duration::from_secs(get_models_dev_timeout_secs());
+    let models_dev = fetch_with_retry(
+        || request_models_dev(timeout),
+        MODELS_DEV_MAX_RETRIES,
+        MODELS_DEV_RETRY_BACKOFF,
+    )
+    .map_err(|e| match e {
+        ModelsDevFetchError::Network(msg) | ModelsDevFetchError::Invalid(msg) => msg,
+    })?;
+
+    let models: Vec<ModelInfo> = models_dev.models.into_iter().map(ModelInfo::from).collect();
+
+    // 写入缓存
+    write_models_dev_cache(&models);
+    Ok(models)
+}
+
 /// 从 models.dev API 获取模型详细信息（带本地缓存）
 ///
 /// 策略：
-/// 1. 先读本地缓存（30分钟有效期）
+/// 1. 先读本地缓存（有效期读取 model-dev-settings.json，默认 30 分钟）
 /// 2. 缓存命中 → 直接返回，零延迟
-/// 3. 缓存未命中 → 请求 API（5秒超时），成功后写入缓存
+/// 3. 缓存未命中 → 请求 API（默认 5 秒超时，网络错误最多重试 2 次），成功后写入缓存
 /// 4. API 失败 → 尝试读取过期缓存作为兜底
 /// 5. 都没有 → 返回空列表
 pub fn fetch_models_dev() -> Result<Vec<ModelInfo>, String> {
@@ -560,50 +983,244 @@ pub fn fetch_models_dev() -> Result<Vec<ModelInfo>, String> {
     }
 
     // 2. 缓存未命中，请求 API
-    let response = ureq::get("https://models.dev/api.json")
-        .timeout(Duration::from_secs(2))
-        .call();
-
-    match response {
-        Ok(resp) => {
-            match resp.into_json::<ModelsDevResponse>() {
-                Ok(models_dev) => {
-                    let models: Vec<ModelInfo> = models_dev
-                        .models
-                        .into_iter()
-                        .map(|m| ModelInfo {
-                            id: m.id,
-                            name: m.name,
-                            description: m.description,
-                            pricing: m.pricing.map(|p| ModelPricing {
-                                prompt: p.prompt,
-                                completion: p.completion,
-                                currency: p.currency,
-                            }),
-                        })
-                        .collect();
-
-                    // 写入缓存
-                    write_models_dev_cache(&models);
-                    Ok(models)
-                }
-                Err(e) => {
-                    eprintln!("解析 models.dev API 响应失败（{}），尝试过期缓存", e);
-                    Ok(read_expired_cache())
-                }
-            }
-        }
+    match fetch_models_dev_from_api() {
+        Ok(models) => Ok(models),
         Err(e) => {
-            eprintln!("models.dev API 不可用（{}），尝试过期缓存", e);
+            eprintln!("{}，尝试过期缓存", e);
             Ok(read_expired_cache())
         }
     }
 }
+
+/// 只保留 id 的 provider 前缀（`provider/model`）属于指定供应商的模型
+fn filter_models_dev_by_providers(models: Vec<ModelInfo>, providers: &[String]) -> Vec<ModelInfo> {
+    models
+        .into_iter()
+        .filter(|model| {
+            model
+                .id
+                .split_once('/')
+                .is_some_and(|(provider, _)| providers.iter().any(|p| p == provider))
+        })
+        .collect()
+}
+
+/// 获取指定供应商的 models.dev 模型信息
+/// 缓存仍保存全量数据，过滤在内存中完成
+pub fn fetch_models_dev_for(providers: &[String]) -> Result<Vec<ModelInfo>, String> {
+    Ok(filter_models_dev_by_providers(
+        fetch_models_dev()?,
+        providers,
+    ))
+}
+
+/// 忽略本地缓存强制重新拉取 models.dev，成功后写回缓存
+pub fn refresh_models_dev() -> Result<Vec<ModelInfo>, String> {
+    fetch_models_dev_from_api()
+}
+
+/// models.dev 未标注货币时的默认货币
+pub(crate) const DEFAULT_PRICING_CURRENCY: &str = "USD";
+
+/// 汇率表路径（~/.config/OMO-Switch/fx-rates.json）
+/// 格式：`{"CNY": 7.2, "EUR": 0.92}`，表示 1 USD 可兑换的目标货币数量
+fn get_fx_rates_path() -> Result<PathBuf, String> {
+    let home = env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("OMO-Switch")
+        .join("fx-rates.json"))
+}
+
+/// 读取汇率表（货币代码统一转为大写），文件缺失或损坏时返回空表
+fn read_fx_rates() -> HashMap<String, f64> {
+    get_fx_rates_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<HashMap<String, f64>>(&content).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(currency, rate)| (currency.to_uppercase(), rate))
+        .collect()
+}
+
+/// 查询 1 USD 兑换指定货币的汇率，USD 未配置时视为 1
+fn usd_rate(currency: &str, rates: &HashMap<String, f64>) -> Option<f64> {
+    let rate = rates
+        .get(currency)
+        .copied()
+        .or_else(|| (currency == DEFAULT_PRICING_CURRENCY).then_some(1.0))?;
+    (rate.is_finite() && rate > 0.0).then_some(rate)
+}
+
+/// 将定价换算为目标货币，缺少源货币或目标货币的汇率时返回 None
+fn convert_pricing(
+    pricing: &ModelPricing,
+    target: &str,
+    rates: &HashMap<String, f64>,
+) -> Option<ModelPricing> {
+    let source = pricing
+        .currency
+        .as_deref()
+        .unwrap_or(DEFAULT_PRICING_CURRENCY)
+        .to_uppercase();
+    let target = target.to_uppercase();
+    let factor = if source == target {
+        1.0
+    } else {
+        usd_rate(&target, rates)? / usd_rate(&source, rates)?
+    };
+
+    Some(ModelPricing {
+        prompt: pricing.prompt.map(|price| price * factor),
+        completion: pricing.completion.map(|price| price * factor),
+        currency: Some(target),
+    })
+}
+
+/// 为模型列表附加目标货币换算结果
+fn attach_converted_pricing(
+    models: Vec<ModelInfo>,
+    target: &str,
+    rates: &HashMap<String, f64>,
+) -> Vec<ModelInfoWithCurrency> {
+    models
+        .into_iter()
+        .map(|info| {
+            let converted_pricing = info
+                .pricing
+                .as_ref()
+                .and_then(|pricing| convert_pricing(pricing, target, rates));
+            ModelInfoWithCurrency {
+                currency_converted: converted_pricing.is_some(),
+                converted_pricing,
+                info,
+            }
+        })
+        .collect()
+}
+
+/// 获取 models.dev 模型信息，并按 fx-rates.json 将价格换算为目标货币
+/// 无对应汇率的模型保留原始价格，currency_converted 为 false
+pub fn fetch_models_dev_with_currency(target: &str) -> Result<Vec<ModelInfoWithCurrency>, String> {
+    Ok(attach_converted_pricing(
+        fetch_models_dev()?,
+        target,
+        &read_fx_rates(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serial_test::serial;
 
+    #[test]
+    fn test_count_models_by_provider_includes_empty_connected() {
+        let mut models = HashMap::new();
+        models.insert(
+            "anthropic".to_string(),
+            vec![
+                "claude-opus-4-5".to_string(),
+                "claude-haiku-4-5".to_string(),
+            ],
+        );
+        let connected = vec!["anthropic".to_string(), "groq".to_string()];
+
+        let counts = count_models_by_provider(&models, &connected);
+
+        assert_eq!(counts.get("anthropic"), Some(&2));
+        assert_eq!(counts.get("groq"), Some(&0));
+        assert_eq!(counts.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_models_by_query_case_insensitive_across_providers() {
+        let mut models = HashMap::new();
+        models.insert(
+            "openai".to_string(),
+            vec![
+                "gpt-5".to_string(),
+                "GPT-5-mini".to_string(),
+                "o3".to_string(),
+            ],
+        );
+        models.insert(
+            "openrouter".to_string(),
+            vec![
+                "openai/gpt-5".to_string(),
+                "anthropic/claude-opus-4-5".to_string(),
+            ],
+        );
+        models.insert("groq".to_string(), vec!["llama-3.3-70b".to_string()]);
+
+        let result = filter_models_by_query(models.clone(), "Gpt-5");
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result.get("openai"),
+            Some(&vec!["gpt-5".to_string(), "GPT-5-mini".to_string()])
+        );
+        assert_eq!(
+            result.get("openrouter"),
+            Some(&vec!["openai/gpt-5".to_string()])
+        );
+        assert!(!result.contains_key("groq"));
+
+        // 空查询返回全部
+        assert_eq!(filter_models_by_query(models.clone(), "  "), models);
+    }
+
+    #[test]
+    fn test_get_models_with_cache_respects_ttl() {
+        let cache = Mutex::new(None);
+        let spawn_count = std::cell::Cell::new(0);
+        let fetch = || {
+            spawn_count.set(spawn_count.get() + 1);
+            Ok(HashMap::from([(
+                "openai".to_string(),
+                vec!["gpt-5".to_string()],
+            )]))
+        };
+        let ttl = Duration::from_secs(10);
+
+        let first = get_models_with_cache(&cache, ttl, fetch).unwrap();
+        let second = get_models_with_cache(&cache, ttl, fetch).unwrap();
+        assert_eq!(spawn_count.get(), 1);
+        assert_eq!(first, second);
+
+        // TTL 过期后重新获取
+        get_models_with_cache(&cache, Duration::ZERO, fetch).unwrap();
+        assert_eq!(spawn_count.get(), 2);
+
+        // 主动失效后重新获取
+        *cache.lock().unwrap() = None;
+        get_models_with_cache(&cache, ttl, fetch).unwrap();
+        assert_eq!(spawn_count.get(), 3);
+
+        // 失败结果不缓存
+        let failing = Mutex::new(None);
+        assert!(get_models_with_cache(&failing, ttl, || Err("boom".to_string())).is_err());
+        assert!(failing.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_build_model_chunks() {
+        let models: Vec<String> = (0..MODEL_STREAM_CHUNK_SIZE * 2 + 3)
+            .map(|i| format!("model-{}", i))
+            .collect();
+
+        let chunks = build_model_chunks("openrouter", &models);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].models.len(), MODEL_STREAM_CHUNK_SIZE);
+        assert_eq!(chunks[2].index, 2);
+        assert_eq!(
+            chunks[2].models,
+            vec!["model-100", "model-101", "model-102"]
+        );
+        assert!(build_model_chunks("openrouter", &[]).is_empty());
+    }
+
     #[test]
     fn test_parse_opencode_models_output() {
         let output = r#"
@@ -619,6 +1236,43 @@ invalid-line
         assert!(!parsed.contains_key("invalid-line"));
     }
 
+    #[test]
+    fn test_merge_path_entries_dedupes_in_order() {
+        assert_eq!(
+            merge_path_entries(&[
+                "/home/u/.opencode/bin",
+                "/usr/bin:/bin::/home/u/.opencode/bin",
+                "/home/u/.volta/bin:/usr/bin:/opt/homebrew/bin",
+            ]),
+            "/home/u/.opencode/bin:/usr/bin:/bin:/home/u/.volta/bin:/opt/homebrew/bin"
+        );
+        assert_eq!(merge_path_entries(&["", "/usr/bin", ""]), "/usr/bin");
+        assert_eq!(merge_path_entries(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_opencode_models_output_strips_markers() {
+        let output = "  openai/gpt-5.3-codex (default)\n\
+* openai/gpt-5.2\n\
+openai/gpt-5.3-codex*\n\
+   anthropic/claude-sonnet-4-6   Claude Sonnet 4.6\n\
+openai/gpt-4.1\n";
+
+        let parsed = parse_opencode_models_output(output);
+        assert_eq!(
+            parsed.get("openai").cloned(),
+            Some(vec![
+                "gpt-5.3-codex".to_string(),
+                "gpt-5.2".to_string(),
+                "gpt-4.1".to_string()
+            ])
+        );
+        assert_eq!(
+            parsed.get("anthropic").cloned(),
+            Some(vec!["claude-sonnet-4-6".to_string()])
+        );
+    }
+
     #[test]
     fn test_get_available_models() {
         // 测试读取本地缓存的模型列表
@@ -722,6 +1376,272 @@ invalid-line
         }
     }
 
+    #[test]
+    fn test_filter_models_dev_by_providers() {
+        let model = |id: &str| ModelInfo {
+            id: id.to_string(),
+            name: None,
+            description: None,
+            pricing: None,
+            context_limit: None,
+            modalities: None,
+            supports_tools: None,
+        };
+        let models = vec![
+            model("anthropic/claude-sonnet-4-5"),
+            model("openai/gpt-5"),
+            model("openai/o3"),
+            model("groq/llama-3.3-70b"),
+            model("no-provider-prefix"),
+        ];
+
+        let filtered = filter_models_dev_by_providers(
+            models,
+            &["openai".to_string(), "anthropic".to_string()],
+        );
+        let ids: Vec<&str> = filtered.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["anthropic/claude-sonnet-4-5", "openai/gpt-5", "openai/o3"]
+        );
+    }
+
+    #[test]
+    fn test_convert_pricing_with_fx_rates() {
+        let rates = HashMap::from([("CNY".to_string(), 7.0), ("EUR".to_string(), 0.5)]);
+        let pricing = |currency: Option<&str>| ModelPricing {
+            prompt: Some(2.0),
+            completion: Some(10.0),
+            currency: currency.map(str::to_string),
+        };
+
+        // 未标注货币按 USD 处理，目标货币大小写不敏感
+        let cny = convert_pricing(&pricing(None), "cny", &rates).unwrap();
+        assert_eq!(cny.prompt, Some(14.0));
+        assert_eq!(cny.completion, Some(70.0));
+        assert_eq!(cny.currency.as_deref(), Some("CNY"));
+
+        // 非 USD 之间经由 USD 汇率交叉换算
+        let eur = convert_pricing(&pricing(Some("CNY")), "EUR", &rates).unwrap();
+        assert_eq!(eur.prompt, Some(2.0 * 0.5 / 7.0));
+
+        // 同币种无需汇率
+        let same = convert_pricing(&pricing(Some("JPY")), "JPY", &rates).unwrap();
+        assert_eq!(same.prompt, Some(2.0));
+
+        // 缺少汇率时不换算
+        assert!(convert_pricing(&pricing(None), "JPY", &rates).is_none());
+        assert!(convert_pricing(&pricing(Some("GBP")), "CNY", &rates).is_none());
+
+        let model = ModelInfo {
+            id: "openai/gpt-5".to_string(),
+            name: None,
+            description: None,
+            pricing: Some(pricing(None)),
+            context_limit: None,
+            modalities: None,
+            supports_tools: None,
+        };
+        let result = attach_converted_pricing(vec![model], "JPY", &rates);
+        assert!(!result[0].currency_converted);
+        assert!(result[0].converted_pricing.is_none());
+        assert_eq!(result[0].info.pricing.as_ref().unwrap().prompt, Some(2.0));
+    }
+
+    #[test]
+    fn test_models_dev_model_parses_capability_fields() {
+        let response: ModelsDevResponse = serde_json::from_str(
+            r#"{
+              "models": [{
+                "id": "claude-sonnet-4-5",
+                "name": "Claude Sonnet 4.5",
+                "limit": { "context": 200000, "output": 64000 },
+                "modalities": { "input": ["text", "image", "pdf"], "output": ["text"] },
+                "tool_call": true
+              }]
+            }"#,
+        )
+        .unwrap();
+
+        let info = ModelInfo::from(response.models.into_iter().next().unwrap());
+        assert_eq!(info.context_limit, Some(200000));
+        assert_eq!(
+            info.modalities,
+            Some(vec![
+                "text".to_string(),
+                "image".to_string(),
+                "pdf".to_string()
+            ])
+        );
+        assert_eq!(info.supports_tools, Some(true));
+    }
+
+    #[test]
+    fn test_models_dev_model_without_capability_fields() {
+        let response: ModelsDevResponse =
+            serde_json::from_str(r#"{ "models": [{ "id": "legacy-model", "name": "Legacy" }] }"#)
+                .unwrap();
+        let info = ModelInfo::from(response.models.into_iter().next().unwrap());
+        assert_eq!(info.id, "legacy-model");
+        assert_eq!(info.context_limit, None);
+        assert_eq!(info.modalities, None);
+        assert_eq!(info.supports_tools, None);
+
+        // 旧版本写入的缓存没有这些字段，仍可正常解析
+        let cache: ModelsDevCache = serde_json::from_str(
+            r#"{ "cached_at": 1, "models": [{ "id": "gpt-5", "name": null, "description": null, "pricing": null }] }"#,
+        )
+        .unwrap();
+        assert_eq!(cache.models[0].context_limit, None);
+        assert_eq!(cache.models[0].supports_tools, None);
+    }
+
+    #[test]
+    fn test_models_dev_cache_zero_ttl_always_expired() {
+        let now = now_unix_secs();
+        assert!(is_models_dev_cache_expired(now, now, 0));
+        assert!(is_models_dev_cache_expired(now + 60, now, 0));
+        assert!(!is_models_dev_cache_expired(
+            now - 10,
+            now,
+            DEFAULT_CACHE_TTL_SECS
+        ));
+        assert!(is_models_dev_cache_expired(
+            now - DEFAULT_CACHE_TTL_SECS,
+            now,
+            DEFAULT_CACHE_TTL_SECS
+        ));
+    }
+
+    #[test]
+    fn test_fetch_with_retry_only_retries_network_errors() {
+        let attempts = std::cell::Cell::new(0);
+        let result: Result<(), _> = fetch_with_retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                Err(ModelsDevFetchError::Network("timeout".to_string()))
+            },
+            MODELS_DEV_MAX_RETRIES,
+            Duration::ZERO,
+        );
+        assert!(matches!(result, Err(ModelsDevFetchError::Network(_))));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result: Result<(), _> = fetch_with_retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                Err(ModelsDevFetchError::Invalid("invalid json".to_string()))
+            },
+            MODELS_DEV_MAX_RETRIES,
+            Duration::ZERO,
+        );
+        assert!(matches!(result, Err(ModelsDevFetchError::Invalid(_))));
+        assert_eq!(attempts.get(), 1);
+
+        attempts.set(0);
+        let result = fetch_with_retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                if attempts.get() < 2 {
+                    Err(ModelsDevFetchError::Network("reset".to_string()))
+                } else {
+                    Ok("ok")
+                }
+            },
+            MODELS_DEV_MAX_RETRIES,
+            Duration::ZERO,
+        );
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_models_dev_cache_ttl_secs_from_settings() {
+        let temp_dir = std::env::temp_dir().join("omo_test_models_dev_ttl_settings");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).expect("创建临时目录失败");
+
+        let original_home = std::env::var("HOME").ok();
+        // SAFETY: 测试中修改 HOME 环境变量是安全的
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let default_ttl = get_models_dev_cache_ttl_secs();
+        let settings_path = get_models_dev_settings_path().unwrap();
+        std::fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &settings_path,
+            r#"{"cacheTtlSecs":0,"requestTimeoutSecs":15}"#,
+        )
+        .unwrap();
+        let configured_ttl = get_models_dev_cache_ttl_secs();
+        let configured_timeout = get_models_dev_timeout_secs();
+
+        // SAFETY: 测试中恢复 HOME 环境变量是安全的
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(default_ttl, DEFAULT_CACHE_TTL_SECS);
+        assert_eq!(configured_ttl, 0);
+        assert_eq!(configured_timeout, 15);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_record_recent_model_lru() {
+        let temp_dir = std::env::temp_dir().join("omo_test_recent_models_lru");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        record_recent_model("openai", "gpt-5").unwrap();
+        record_recent_model("anthropic", "claude-sonnet-4-5").unwrap();
+        record_recent_model("openai", "openai/gpt-5").unwrap();
+        let deduped = get_recent_models();
+        for i in 0..RECENT_MODELS_LIMIT + 3 {
+            record_recent_model("openai", &format!("model-{}", i)).unwrap();
+        }
+        let capped = get_recent_models();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(
+            deduped,
+            vec![
+                "openai/gpt-5".to_string(),
+                "anthropic/claude-sonnet-4-5".to_string()
+            ]
+        );
+        assert_eq!(capped.len(), RECENT_MODELS_LIMIT);
+        assert_eq!(
+            capped[0],
+            format!("openai/model-{}", RECENT_MODELS_LIMIT + 2)
+        );
+        assert!(!capped.contains(&"openai/gpt-5".to_string()));
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
     /// 测试合并自定义模型到缓存模型列表
     ///
     /// 验证：
@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use super::config_service::{read_omo_config, write_omo_config};
+use super::config_cache_service::{compare_configs, ConfigChange};
+use super::config_service::{get_config_path, read_omo_config, validate_config, write_omo_config};
+use super::import_export_service;
+use super::model_service::{fetch_models_dev, ModelPricing, DEFAULT_PRICING_CURRENCY};
 use crate::i18n;
 
 /// 预设元数据结构体
@@ -17,8 +21,20 @@ pub struct PresetMeta {
     pub updated_at: u64,
     /// 元数据版本号，当前为 1
     pub version: u32,
+    /// 预设描述（可选，旧预设无此字段时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 预设来源："manual"（用户保存/复制）| "builtin"（内置生成）| "imported"（外部导入）
+    /// 旧预设无此字段时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
+/// 预设来源：用户手动保存或复制
+pub const PRESET_SOURCE_MANUAL: &str = "manual";
+/// 预设来源：从外部文件导入
+pub const PRESET_SOURCE_IMPORTED: &str = "imported";
+
 impl PresetMeta {
     /// 创建新的预设元数据
     /// created_at 和 updated_at 都设置为当前时间
@@ -28,6 +44,16 @@ impl PresetMeta {
             created_at: now,
             updated_at: now,
             version: 1,
+            description: None,
+            source: None,
+        }
+    }
+
+    /// 创建带来源标记的新预设元数据
+    pub fn with_source(source: &str) -> Self {
+        Self {
+            source: Some(source.to_string()),
+            ..Self::new()
         }
     }
 
@@ -64,6 +90,11 @@ fn current_timestamp_ms() -> u64 {
 /// 元数据字段名称
 const META_FIELD: &str = "__meta__";
 
+/// 预设历史版本目录（位于预设目录下）
+const PRESET_HISTORY_DIR: &str = ".history";
+/// 每个预设默认保留的历史版本数量
+pub const PRESET_HISTORY_LIMIT: usize = 5;
+
 /// 获取预设目录路径
 /// 返回 ~/.config/OMO-Switch/presets/ 的完整路径
 pub fn get_presets_dir() -> Result<PathBuf, String> {
@@ -124,7 +155,7 @@ fn build_preset_with_meta(config: &Value, preset_path: &PathBuf) -> Result<Value
         meta.update();
         meta
     } else {
-        PresetMeta::new()
+        PresetMeta::with_source(PRESET_SOURCE_MANUAL)
     };
 
     if let Some(obj) = preset.as_object_mut() {
@@ -174,6 +205,11 @@ pub fn load_preset(name: &str) -> Result<(), String> {
         obj.remove(META_FIELD);
     }
 
+    // 应用预设前先做一次受管备份，便于在备份历史中恢复
+    if get_config_path()?.exists() {
+        import_export_service::backup_current_config_with_prefix("preset")?;
+    }
+
     write_omo_config(&preset_config)?;
     set_active_preset(name)?;
 
@@ -204,6 +240,117 @@ pub fn get_preset_config(name: &str) -> Result<Value, String> {
     Ok(preset_config)
 }
 
+/// 导出预设到指定路径（去掉 __meta__ 字段），便于分享给他人
+pub fn export_preset(name: &str, target_path: &str) -> Result<(), String> {
+    let preset_config = get_preset_config(name)?;
+
+    let target = PathBuf::from(target_path);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("create_target_dir_failed"), e))?;
+    }
+
+    let json_string = serde_json::to_string_pretty(&preset_config)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
+    fs::write(&target, json_string).map_err(|e| format!("写入导出文件失败: {}", e))?;
+
+    Ok(())
+}
+
+/// 从外部文件导入预设
+/// 校验配置结构后保存为新预设，并生成全新的 __meta__（忽略文件自带的元数据）
+pub fn import_preset(source_path: &str, new_name: &str) -> Result<(), String> {
+    let preset_path = get_new_preset_path(new_name)?;
+
+    let content = fs::read_to_string(source_path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("read_import_file_failed"), e))?;
+    let mut preset: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("parse_import_file_failed"), e))?;
+
+    if let Some(obj) = preset.as_object_mut() {
+        obj.remove(META_FIELD);
+    }
+    validate_config(&preset)?;
+
+    write_new_preset(&preset_path, preset, PRESET_SOURCE_IMPORTED)
+}
+
+/// 复制预设（另存为），新预设拥有独立的 __meta__
+pub fn duplicate_preset(source: &str, new_name: &str) -> Result<(), String> {
+    let preset_path = get_new_preset_path(new_name)?;
+    let preset = get_preset_config(source)?;
+
+    write_new_preset(&preset_path, preset, PRESET_SOURCE_MANUAL)
+}
+
+/// 比较两个预设（均不含 __meta__），a 视为旧值、b 视为新值
+pub fn diff_presets(a: &str, b: &str) -> Result<Vec<ConfigChange>, String> {
+    let old_config = get_preset_config(a)?;
+    let new_config = get_preset_config(b)?;
+    Ok(compare_configs(&old_config, &new_config))
+}
+
+/// 比较当前磁盘配置与预设，返回切换到该预设后会发生的变更
+pub fn diff_preset_with_current(name: &str) -> Result<Vec<ConfigChange>, String> {
+    let preset_config = get_preset_config(name)?;
+    let mut current_config = read_omo_config()?;
+    if let Some(obj) = current_config.as_object_mut() {
+        obj.remove(META_FIELD);
+    }
+    Ok(compare_configs(&current_config, &preset_config))
+}
+
+/// 试运行应用预设：返回 load_preset 将对当前配置文件产生的变更，不写入磁盘
+/// 与 diff_preset_with_current 不同，这里保留当前配置中的 __meta__，
+/// 因为应用预设时整份配置会被覆盖，该字段也会被移除
+pub fn preview_apply_preset(name: &str) -> Result<Vec<ConfigChange>, String> {
+    let preset_config = get_preset_config(name)?;
+    let current_config = if get_config_path()?.exists() {
+        read_omo_config()?
+    } else {
+        Value::Object(serde_json::Map::new())
+    };
+    Ok(compare_configs(&current_config, &preset_config))
+}
+
+/// 校验新预设名称合法且未被占用，返回其文件路径
+fn get_new_preset_path(new_name: &str) -> Result<PathBuf, String> {
+    if new_name.is_empty() {
+        return Err(i18n::tr_current("preset_name_empty"));
+    }
+    if new_name.contains('/') || new_name.contains('\\') {
+        return Err(i18n::tr_current("preset_name_invalid_path"));
+    }
+
+    let preset_path = get_preset_path(new_name)?;
+    if preset_path.exists() {
+        return Err("预设名称已存在".to_string());
+    }
+
+    Ok(preset_path)
+}
+
+/// 为不含 __meta__ 的预设配置生成带来源标记的全新元数据并写入
+fn write_new_preset(preset_path: &PathBuf, mut preset: Value, source: &str) -> Result<(), String> {
+    if let Some(obj) = preset.as_object_mut() {
+        obj.insert(
+            META_FIELD.to_string(),
+            PresetMeta::with_source(source).to_value(),
+        );
+    }
+
+    let presets_dir = get_presets_dir()?;
+    fs::create_dir_all(&presets_dir)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("create_preset_dir_failed"), e))?;
+
+    let json_string = serde_json::to_string_pretty(&preset)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
+    fs::write(preset_path, json_string)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
+
+    Ok(())
+}
+
 /// 列出所有预设
 /// 返回预设名称列表（不含 .json 后缀）
 ///
@@ -268,13 +415,21 @@ pub fn delete_preset(name: &str) -> Result<(), String> {
     fs::remove_file(&preset_path)
         .map_err(|e| format!("{}: {}", i18n::tr_current("delete_preset_failed"), e))?;
 
+    // 历史版本随预设一并删除，失败不影响删除结果
+    if let Some(history_dir) = get_preset_history_dir(name).ok().filter(|dir| dir.exists()) {
+        if let Err(e) = fs::remove_dir_all(&history_dir) {
+            eprintln!("删除预设历史版本失败: {}", e);
+        }
+    }
+
     Ok(())
 }
 
-/// 重命名预设（原子操作）
-/// 1. 校验旧名称与新名称
-/// 2. 预设文件从 old_name.json 重命名为 new_name.json
-/// 3. 若当前激活预设是旧名称，同步更新 active_preset
+/// 重命名预设
+/// 1. 校验旧名称与新名称（新名称不能已存在）
+/// 2. 读取旧预设并保留 __meta__（created_at 不变，刷新 updated_at）
+/// 3. 写入 new_name.json 后删除 old_name.json
+/// 4. 若当前激活预设是旧名称，同步更新 active_preset
 pub fn rename_preset(old_name: &str, new_name: &str) -> Result<(), String> {
     if old_name.is_empty() || new_name.is_empty() {
         return Err(i18n::tr_current("preset_name_empty"));
@@ -303,10 +458,28 @@ pub fn rename_preset(old_name: &str, new_name: &str) -> Result<(), String> {
         return Err("预设名称已存在".to_string());
     }
 
+    let json_string = serde_json::to_string_pretty(&build_renamed_preset(&old_path)?)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
+
     if case_only_rename {
         rename_case_only_preset(&old_path, &new_path)?;
+        fs::write(&new_path, json_string)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
     } else {
-        fs::rename(&old_path, &new_path).map_err(|e| format!("重命名预设失败: {}", e))?;
+        fs::write(&new_path, json_string)
+            .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
+        if let Err(e) = fs::remove_file(&old_path) {
+            let _ = fs::remove_file(&new_path);
+            return Err(format!("重命名预设失败: {}", e));
+        }
+    }
+
+    // 历史版本目录跟随预设改名，失败时仅丢失历史，不影响重命名结果
+    let old_history_dir = get_preset_history_dir(old_name)?;
+    if old_history_dir.exists() {
+        if let Err(e) = fs::rename(&old_history_dir, get_preset_history_dir(new_name)?) {
+            eprintln!("迁移预设历史版本失败: {}", e);
+        }
     }
 
     if get_active_preset().as_deref() == Some(old_name) {
@@ -316,6 +489,27 @@ pub fn rename_preset(old_name: &str, new_name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// 读取待重命名的预设，保留原有元数据并刷新 updated_at
+fn build_renamed_preset(preset_path: &PathBuf) -> Result<Value, String> {
+    let content =
+        fs::read_to_string(preset_path).map_err(|e| format!("读取预设文件失败: {}", e))?;
+    let mut preset: Value =
+        serde_json::from_str(&content).map_err(|e| format!("解析预设 JSON 失败: {}", e))?;
+
+    let mut meta = preset
+        .get(META_FIELD)
+        .and_then(PresetMeta::from_value)
+        .unwrap_or_default();
+    meta.update();
+
+    let obj = preset
+        .as_object_mut()
+        .ok_or_else(|| "预设文件格式无效".to_string())?;
+    obj.insert(META_FIELD.to_string(), meta.to_value());
+
+    Ok(preset)
+}
+
 fn is_case_only_rename(old_name: &str, new_name: &str) -> bool {
     old_name != new_name && old_name.to_lowercase() == new_name.to_lowercase()
 }
@@ -414,6 +608,7 @@ pub fn update_preset(name: &str) -> Result<(), String> {
     }
 
     let preset_with_meta = build_preset_with_meta(&config, &preset_path)?;
+    archive_preset_version(name, &preset_path)?;
 
     let json_string = serde_json::to_string_pretty(&preset_with_meta)
         .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
@@ -433,7 +628,11 @@ pub struct PresetUpdateRequest {
 
 /// 直接将模型更新应用到指定预设文件（不切换当前活动预设）
 /// 规则与 update_agents_batch 保持一致：同名会同时尝试更新 agents 与 categories。
-pub fn apply_updates_to_preset(name: &str, updates: &[PresetUpdateRequest]) -> Result<(), String> {
+/// model 为空或缺少 provider 前缀的更新会被跳过，返回值为这些更新的错误描述列表。
+pub fn apply_updates_to_preset(
+    name: &str,
+    updates: &[PresetUpdateRequest],
+) -> Result<Vec<String>, String> {
     if name.is_empty() {
         return Err(i18n::tr_current("preset_name_empty"));
     }
@@ -444,8 +643,19 @@ pub fn apply_updates_to_preset(name: &str, updates: &[PresetUpdateRequest]) -> R
     }
 
     let mut preset_config = get_preset_config(name)?;
+    let mut errors = Vec::new();
+    let mut applied = 0;
 
     for update in updates {
+        if !is_qualified_model(&update.model) {
+            errors.push(format!(
+                "{}: model \"{}\" 必须是 provider/model 格式的非空字符串",
+                update.agent_name, update.model
+            ));
+            continue;
+        }
+        applied += 1;
+
         if let Some(agents) = preset_config
             .get_mut("agents")
             .and_then(|a| a.as_object_mut())
@@ -483,12 +693,119 @@ pub fn apply_updates_to_preset(name: &str, updates: &[PresetUpdateRequest]) -> R
         }
     }
 
+    // 全部更新都不合法时不改动预设文件，避免产生无意义的历史版本
+    if applied == 0 {
+        return Ok(errors);
+    }
+
     let preset_with_meta = build_preset_with_meta(&preset_config, &preset_path)?;
+    archive_preset_version(name, &preset_path)?;
     let json_string = serde_json::to_string_pretty(&preset_with_meta)
         .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
     fs::write(&preset_path, json_string)
         .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
 
+    Ok(errors)
+}
+
+/// model 必须是 provider/model 形式，且两段均非空
+fn is_qualified_model(model: &str) -> bool {
+    model.split_once('/').is_some_and(|(provider, model_id)| {
+        !provider.trim().is_empty() && !model_id.trim().is_empty()
+    })
+}
+
+/// 预设历史版本目录: presets/.history/{name}/
+fn get_preset_history_dir(name: &str) -> Result<PathBuf, String> {
+    if name.contains('/') || name.contains('\\') {
+        return Err(i18n::tr_current("preset_name_invalid_path"));
+    }
+    Ok(get_presets_dir()?.join(PRESET_HISTORY_DIR).join(name))
+}
+
+/// 覆盖预设前把旧内容存为 {ts}.json，并只保留最近 PRESET_HISTORY_LIMIT 份
+fn archive_preset_version(name: &str, preset_path: &PathBuf) -> Result<(), String> {
+    if !preset_path.exists() {
+        return Ok(());
+    }
+
+    let history_dir = get_preset_history_dir(name)?;
+    fs::create_dir_all(&history_dir).map_err(|e| format!("创建预设历史目录失败: {}", e))?;
+
+    // 同一毫秒内多次覆盖时顺延时间戳，避免互相覆盖
+    let mut ts = current_timestamp_ms();
+    while history_dir.join(format!("{}.json", ts)).exists() {
+        ts += 1;
+    }
+    fs::copy(preset_path, history_dir.join(format!("{}.json", ts)))
+        .map_err(|e| format!("保存预设历史版本失败: {}", e))?;
+
+    for stale in list_preset_history(name)?
+        .into_iter()
+        .skip(PRESET_HISTORY_LIMIT)
+    {
+        let _ = fs::remove_file(history_dir.join(format!("{}.json", stale)));
+    }
+
+    Ok(())
+}
+
+/// 列出预设的历史版本时间戳（毫秒），按时间从新到旧排序
+pub fn list_preset_history(name: &str) -> Result<Vec<u64>, String> {
+    if name.is_empty() {
+        return Err(i18n::tr_current("preset_name_empty"));
+    }
+
+    let history_dir = get_preset_history_dir(name)?;
+    if !history_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions: Vec<u64> = fs::read_dir(&history_dir)
+        .map_err(|e| format!("读取预设历史目录失败: {}", e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|path| path.file_stem()?.to_str()?.parse().ok())
+        .collect();
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+
+    Ok(versions)
+}
+
+/// 将预设恢复到指定历史版本，恢复前的内容同样会存入历史，便于再次撤销
+pub fn restore_preset_version(name: &str, ts: u64) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(i18n::tr_current("preset_name_empty"));
+    }
+
+    let version_path = get_preset_history_dir(name)?.join(format!("{}.json", ts));
+    if !version_path.exists() {
+        return Err("预设历史版本不存在".to_string());
+    }
+
+    let content = fs::read_to_string(&version_path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("read_preset_file_failed"), e))?;
+    let mut preset: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("parse_preset_file_failed"), e))?;
+
+    let mut meta = preset
+        .get(META_FIELD)
+        .and_then(PresetMeta::from_value)
+        .unwrap_or_default();
+    meta.update();
+    let obj = preset
+        .as_object_mut()
+        .ok_or_else(|| "预设文件格式无效".to_string())?;
+    obj.insert(META_FIELD.to_string(), meta.to_value());
+
+    let preset_path = get_preset_path(name)?;
+    archive_preset_version(name, &preset_path)?;
+
+    let json_string = serde_json::to_string_pretty(&preset)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
+    fs::write(&preset_path, json_string)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
+
     Ok(())
 }
 
@@ -507,17 +824,341 @@ pub fn get_preset_meta(name: &str) -> Result<PresetMeta, String> {
     read_preset_meta_from_file(&preset_path)?.ok_or_else(|| "预设缺少元数据".to_string())
 }
 
+/// 设置预设描述，传入 None 或空白字符串时清除描述
+pub fn set_preset_description(name: &str, description: Option<&str>) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(i18n::tr_current("preset_name_empty"));
+    }
+
+    let preset_path = get_preset_path(name)?;
+    if !preset_path.exists() {
+        return Err(i18n::tr_current("preset_not_found"));
+    }
+
+    let content = fs::read_to_string(&preset_path)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("read_preset_file_failed"), e))?;
+    let mut preset: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("parse_preset_file_failed"), e))?;
+
+    let mut meta = preset
+        .get(META_FIELD)
+        .and_then(PresetMeta::from_value)
+        .unwrap_or_default();
+    meta.description = description
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string);
+
+    let obj = preset
+        .as_object_mut()
+        .ok_or_else(|| "预设文件格式无效".to_string())?;
+    obj.insert(META_FIELD.to_string(), meta.to_value());
+
+    let json_string = serde_json::to_string_pretty(&preset)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("serialize_json_failed"), e))?;
+    fs::write(&preset_path, json_string)
+        .map_err(|e| format!("{}: {}", i18n::tr_current("write_preset_file_failed"), e))?;
+
+    Ok(())
+}
+
 /// 同步预设从当前配置 - 用于"忽略"时同步元数据
 pub fn sync_preset_from_config(name: &str) -> Result<(), String> {
     update_preset(name)
 }
 
+/// 判断预设中的模型是否匹配查询
+/// 支持完整 `provider/model` 或仅 model 部分的精确/前缀匹配
+fn model_matches_query(model: &str, query: &str) -> bool {
+    let model_id = model.split_once('/').map(|(_, id)| id).unwrap_or(model);
+    model.starts_with(query) || model_id.starts_with(query)
+}
+
+/// 查找使用了指定模型的预设
+/// 扫描每个预设 agents/categories 下的 model 字段，无法解析的预设文件会被跳过
+pub fn find_presets_using_model(model: &str) -> Result<Vec<String>, String> {
+    let query = model.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut matched = Vec::new();
+    for name in list_presets()? {
+        let Ok(preset_config) = get_preset_config(&name) else {
+            continue;
+        };
+
+        let uses_model = ["agents", "categories"].iter().any(|section| {
+            preset_config
+                .get(*section)
+                .and_then(|v| v.as_object())
+                .map(|entries| {
+                    entries.values().any(|entry| {
+                        entry
+                            .get("model")
+                            .and_then(|m| m.as_str())
+                            .is_some_and(|m| model_matches_query(m, query))
+                    })
+                })
+                .unwrap_or(false)
+        });
+
+        if uses_model {
+            matched.push(name);
+        }
+    }
+
+    Ok(matched)
+}
+
+// ========== 预设成本估算 ==========
+
+/// models.dev 定价的计价单位（每 1K tokens，与模型浏览器的展示一致）
+const PRICING_TOKENS_UNIT: f64 = 1000.0;
+/// 按每月 30 天估算
+const DAYS_PER_MONTH: f64 = 30.0;
+
+/// 单个 agent 的月成本估算
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AgentCostEstimate {
+    pub agent: String,
+    pub model: Option<String>,
+    /// 月成本下限：全部 token 按较低的单价（通常为输入价）计算
+    pub monthly_min: Option<f64>,
+    /// 月成本上限：全部 token 按较高的单价（通常为输出价）计算
+    pub monthly_max: Option<f64>,
+    pub currency: Option<String>,
+    /// 是否找到定价，false 表示成本未知
+    pub pricing_known: bool,
+}
+
+/// 某一币种下的月成本合计
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CurrencyCostTotal {
+    pub currency: String,
+    pub total_min: f64,
+    pub total_max: f64,
+}
+
+/// 预设的月成本估算：按 agent 明细与总计（总计只累加定价已知的 agent）
+/// 不同币种的成本不能直接相加，总计按币种分组，顺序与 agent 首次出现一致
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PresetCostEstimate {
+    pub agents: Vec<AgentCostEstimate>,
+    pub totals: Vec<CurrencyCostTotal>,
+    /// 缺少定价、未计入总计的 agent
+    pub unknown_agents: Vec<String>,
+    pub assumed_tokens_per_day: u64,
+}
+
+/// 根据定价表估算预设中每个 agent 的月成本，每个 agent 按 tokens_per_day 日均用量计算
+fn estimate_cost_with_pricing(
+    preset_config: &Value,
+    pricing: &HashMap<String, ModelPricing>,
+    tokens_per_day: u64,
+) -> PresetCostEstimate {
+    let monthly_units = tokens_per_day as f64 * DAYS_PER_MONTH / PRICING_TOKENS_UNIT;
+    let mut estimate = PresetCostEstimate {
+        agents: Vec::new(),
+        totals: Vec::new(),
+        unknown_agents: Vec::new(),
+        assumed_tokens_per_day: tokens_per_day,
+    };
+
+    let Some(agents) = preset_config.get("agents").and_then(|v| v.as_object()) else {
+        return estimate;
+    };
+
+    for (agent, entry) in agents {
+        let model = entry
+            .get("model")
+            .and_then(|m| m.as_str())
+            .map(str::to_string);
+        let model_pricing = model.as_deref().and_then(|m| pricing.get(m));
+        // 只有一侧单价时上下限都使用该单价
+        let range = model_pricing.and_then(|p| match (p.prompt, p.completion) {
+            (Some(prompt), Some(completion)) => {
+                Some((prompt.min(completion), prompt.max(completion)))
+            }
+            (Some(price), None) | (None, Some(price)) => Some((price, price)),
+            (None, None) => None,
+        });
+
+        let agent_estimate = match range {
+            Some((min_price, max_price)) => {
+                let monthly_min = min_price * monthly_units;
+                let monthly_max = max_price * monthly_units;
+                // models.dev 未标注货币时按 USD 处理
+                let currency = model_pricing
+                    .and_then(|p| p.currency.as_deref())
+                    .unwrap_or(DEFAULT_PRICING_CURRENCY)
+                    .to_uppercase();
+                match estimate
+                    .totals
+                    .iter_mut()
+                    .find(|total| total.currency == currency)
+                {
+                    Some(total) => {
+                        total.total_min += monthly_min;
+                        total.total_max += monthly_max;
+                    }
+                    None => estimate.totals.push(CurrencyCostTotal {
+                        currency: currency.clone(),
+                        total_min: monthly_min,
+                        total_max: monthly_max,
+                    }),
+                }
+                AgentCostEstimate {
+                    agent: agent.clone(),
+                    model,
+                    monthly_min: Some(monthly_min),
+                    monthly_max: Some(monthly_max),
+                    currency: Some(currency),
+                    pricing_known: true,
+                }
+            }
+            None => {
+                estimate.unknown_agents.push(agent.clone());
+                AgentCostEstimate {
+                    agent: agent.clone(),
+                    model,
+                    monthly_min: None,
+                    monthly_max: None,
+                    currency: None,
+                    pricing_known: false,
+                }
+            }
+        };
+        estimate.agents.push(agent_estimate);
+    }
+
+    estimate
+}
+
+/// 结合 models.dev 定价，按日均 token 用量粗估预设的月成本区间
+pub fn estimate_preset_cost(
+    name: &str,
+    assumed_tokens_per_day: u64,
+) -> Result<PresetCostEstimate, String> {
+    let preset_config = get_preset_config(name)?;
+    let pricing: HashMap<String, ModelPricing> = fetch_models_dev()?
+        .into_iter()
+        .filter_map(|model| model.pricing.map(|pricing| (model.id, pricing)))
+        .collect();
+
+    Ok(estimate_cost_with_pricing(
+        &preset_config,
+        &pricing,
+        assumed_tokens_per_day,
+    ))
+}
+
+// ========== 当前激活预设管理 ==========
+
+/// 获取 active_preset 标记文件路径
+/// 返回 ~/.config/OMO-Switch/active_preset 的完整路径
+fn get_active_preset_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量")?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("OMO-Switch")
+        .join("active_preset"))
+}
+
+/// 获取当前激活的预设名称
+pub fn get_active_preset() -> Option<String> {
+    let path = get_active_preset_path().ok()?;
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 设置当前激活的预设名称
+pub fn set_active_preset(name: &str) -> Result<(), String> {
+    let path = get_active_preset_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    std::fs::write(&path, name).map_err(|e| format!("写入文件失败: {}", e))
+}
+
+/// 旧版本内置预设写入 active_preset 时使用的前缀，例如 `__builtin__economy`
+pub const BUILTIN_PRESET_MARKER_PREFIX: &str = "__builtin__";
+
+/// 迁移旧版本遗留的内置预设标记
+/// 若 active_preset 为 `__builtin__{id}`：存在 default 预设则切换到 default，否则清除标记
+/// 返回迁移后的 active_preset 值
+pub fn migrate_active_preset_marker() -> Option<String> {
+    let active = get_active_preset()?;
+    if !active.starts_with(BUILTIN_PRESET_MARKER_PREFIX) {
+        return Some(active);
+    }
+
+    let has_default = get_preset_path("default")
+        .map(|path| path.exists())
+        .unwrap_or(false);
+
+    if has_default {
+        match set_active_preset("default") {
+            Ok(()) => return Some("default".to_string()),
+            Err(err) => eprintln!("迁移内置预设标记失败: {}", err),
+        }
+    }
+
+    if let Err(err) = clear_active_preset() {
+        eprintln!("清除内置预设标记失败: {}", err);
+        return Some(active);
+    }
+    None
+}
+
+/// 清除当前激活的预设标记
+/// 用于当前配置不对应任何预设（例如手动编辑过）的场景，文件不存在时视为成功
+pub fn clear_active_preset() -> Result<(), String> {
+    let path = get_active_preset_path()?;
+    if !path.exists() {
+        return Ok(());
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("删除文件失败: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
+    use serial_test::serial;
     use std::fs;
 
+    /// 测试结束（包括断言失败 panic）时恢复 HOME 环境变量
+    struct HomeGuard(Option<String>);
+
+    impl Drop for HomeGuard {
+        fn drop(&mut self) {
+            match &self.0 {
+                Some(v) => {
+                    // SAFETY: 测试结束时恢复 HOME 环境变量
+                    unsafe { std::env::set_var("HOME", v) };
+                }
+                None => {
+                    // SAFETY: 测试结束时清理 HOME 环境变量
+                    unsafe { std::env::remove_var("HOME") };
+                }
+            }
+        }
+    }
+
+    /// 将 HOME 指向全新的临时目录，返回目录路径与恢复守卫
+    fn with_temp_home(name: &str) -> (PathBuf, HomeGuard) {
+        let original_home = std::env::var("HOME").ok();
+        let temp_home = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&temp_home);
+        fs::create_dir_all(&temp_home).unwrap();
+        // SAFETY: 测试中将 HOME 指向临时目录，避免污染真实用户数据
+        unsafe { std::env::set_var("HOME", &temp_home) };
+        (temp_home, HomeGuard(original_home))
+    }
+
     #[test]
     fn test_get_presets_dir() {
         let result = get_presets_dir();
@@ -660,30 +1301,620 @@ mod tests {
         assert!(!is_case_only_rename("minimax-All", "minimax-All"));
         assert!(!is_case_only_rename("minimax-All", "gpt-all"));
     }
-}
 
-// ========== 当前激活预设管理 ==========
+    #[test]
+    fn test_estimate_cost_with_pricing_sums_known_and_marks_unknown() {
+        let preset_config = serde_json::json!({
+            "agents": {
+                "sisyphus": {"model": "openai/gpt-5"},
+                "oracle": {"model": "anthropic/claude-opus-4"},
+                "explore": {"model": "local/unpriced"},
+                "librarian": {}
+            },
+            "categories": {"quick": {"model": "openai/gpt-5"}}
+        });
+        let price = |prompt: Option<f64>, completion: Option<f64>| ModelPricing {
+            prompt,
+            completion,
+            currency: Some("USD".to_string()),
+        };
+        let pricing = HashMap::from([
+            ("openai/gpt-5".to_string(), price(Some(0.001), Some(0.004))),
+            (
+                "anthropic/claude-opus-4".to_string(),
+                price(None, Some(0.002)),
+            ),
+            ("local/unpriced".to_string(), price(None, None)),
+        ]);
+
+        // 每天 10K tokens，一个月 300 个 1K 单位
+        let estimate = estimate_cost_with_pricing(&preset_config, &pricing, 10_000);
+
+        assert_eq!(estimate.assumed_tokens_per_day, 10_000);
+        assert_eq!(estimate.agents.len(), 4);
+
+        let sisyphus = &estimate.agents[0];
+        assert_eq!(sisyphus.agent, "sisyphus");
+        assert!(sisyphus.pricing_known);
+        assert!((sisyphus.monthly_min.unwrap() - 0.3).abs() < 1e-9);
+        assert!((sisyphus.monthly_max.unwrap() - 1.2).abs() < 1e-9);
+
+        // 只有输出价时上下限相同
+        let oracle = &estimate.agents[1];
+        assert_eq!(oracle.monthly_min, oracle.monthly_max);
+        assert!((oracle.monthly_max.unwrap() - 0.6).abs() < 1e-9);
+
+        // 无定价和未配置 model 的 agent 标注未知，不计入总计；categories 不参与估算
+        assert!(!estimate.agents[2].pricing_known);
+        assert_eq!(estimate.agents[2].model.as_deref(), Some("local/unpriced"));
+        assert!(estimate.agents[3].model.is_none());
+        assert_eq!(
+            estimate.unknown_agents,
+            vec!["explore".to_string(), "librarian".to_string()]
+        );
+        assert_eq!(estimate.totals.len(), 1);
+        assert_eq!(estimate.totals[0].currency, "USD");
+        assert!((estimate.totals[0].total_min - 0.9).abs() < 1e-9);
+        assert!((estimate.totals[0].total_max - 1.8).abs() < 1e-9);
+    }
 
-/// 获取当前激活的预设名称
-pub fn get_active_preset() -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    let path = std::path::PathBuf::from(home)
-        .join(".config")
-        .join("OMO-Switch")
-        .join("active_preset");
-    std::fs::read_to_string(path)
-        .ok()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-}
+    #[test]
+    fn test_estimate_cost_with_pricing_groups_totals_by_currency() {
+        let preset_config = serde_json::json!({
+            "agents": {
+                "sisyphus": {"model": "openai/gpt-5"},
+                "oracle": {"model": "deepseek/deepseek-v3"},
+                "explore": {"model": "openai/gpt-5-mini"},
+                "librarian": {"model": "moonshot/kimi-k2"}
+            }
+        });
+        let price = |value: f64, currency: Option<&str>| ModelPricing {
+            prompt: Some(value),
+            completion: Some(value),
+            currency: currency.map(str::to_string),
+        };
+        let pricing = HashMap::from([
+            ("openai/gpt-5".to_string(), price(0.01, None)),
+            ("openai/gpt-5-mini".to_string(), price(0.002, Some("usd"))),
+            (
+                "deepseek/deepseek-v3".to_string(),
+                price(0.004, Some("CNY")),
+            ),
+            ("moonshot/kimi-k2".to_string(), price(0.02, Some("CNY"))),
+        ]);
+
+        // 每天 1K tokens，一个月 30 个 1K 单位
+        let estimate = estimate_cost_with_pricing(&preset_config, &pricing, 1_000);
+
+        let currencies: Vec<&str> = estimate
+            .agents
+            .iter()
+            .map(|agent| agent.currency.as_deref().unwrap())
+            .collect();
+        assert_eq!(currencies, vec!["USD", "CNY", "USD", "CNY"]);
+
+        assert_eq!(estimate.totals.len(), 2);
+        assert_eq!(estimate.totals[0].currency, "USD");
+        assert!((estimate.totals[0].total_max - 0.36).abs() < 1e-9);
+        assert_eq!(estimate.totals[1].currency, "CNY");
+        assert!((estimate.totals[1].total_max - 0.72).abs() < 1e-9);
+        assert!(estimate.unknown_agents.is_empty());
+    }
 
-/// 设置当前激活的预设名称
-pub fn set_active_preset(name: &str) -> Result<(), String> {
-    let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量")?;
-    let dir = std::path::PathBuf::from(home)
-        .join(".config")
-        .join("OMO-Switch");
-    std::fs::create_dir_all(&dir).map_err(|e| format!("创建目录失败: {}", e))?;
-    let path = dir.join("active_preset");
-    std::fs::write(&path, name).map_err(|e| format!("写入文件失败: {}", e))
+    #[test]
+    #[serial]
+    fn test_find_presets_using_model() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_find_model_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("codex.json"),
+            r#"{"agents":{"sisyphus":{"model":"openai/gpt-5.3-codex"}},"categories":{}}"#,
+        )
+        .unwrap();
+        fs::write(
+            presets_dir.join("claude.json"),
+            r#"{"agents":{},"categories":{"quick":{"model":"anthropic/claude-haiku-4-5"}}}"#,
+        )
+        .unwrap();
+        fs::write(presets_dir.join("broken.json"), "{not json").unwrap();
+
+        assert_eq!(
+            find_presets_using_model("gpt-5.3-codex").unwrap(),
+            vec!["codex".to_string()]
+        );
+        assert_eq!(
+            find_presets_using_model("anthropic/").unwrap(),
+            vec!["claude".to_string()]
+        );
+        assert!(find_presets_using_model("gemini").unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_preview_apply_preset_matches_actual_changes() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_preview_apply_test");
+
+        let current = serde_json::json!({
+            "agents": {
+                "sisyphus": {"model": "anthropic/claude-opus-4-5"},
+                "oracle": {"model": "openai/gpt-5.2"}
+            },
+            "categories": {"quick": {"model": "anthropic/claude-haiku-4-5"}},
+            "__meta__": {"created_at": 1, "updated_at": 1, "version": 1}
+        });
+        write_omo_config(&current).unwrap();
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("codex.json"),
+            r#"{"agents":{"sisyphus":{"model":"openai/gpt-5.3-codex"}},"categories":{"quick":{"model":"anthropic/claude-haiku-4-5"},"writing":{"model":"openai/gpt-5.2"}},"__meta__":{"created_at":2,"updated_at":2,"version":1}}"#,
+        )
+        .unwrap();
+
+        let sorted = |changes: Vec<ConfigChange>| {
+            let mut values: Vec<Value> = changes
+                .into_iter()
+                .map(|change| serde_json::to_value(change).unwrap())
+                .collect();
+            values.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+            values
+        };
+
+        let preview = sorted(preview_apply_preset("codex").unwrap());
+        // 试运行不应修改磁盘上的配置
+        assert_eq!(read_omo_config().unwrap(), current);
+        assert!(!preview.is_empty());
+
+        load_preset("codex").unwrap();
+        let actual = sorted(compare_configs(&current, &read_omo_config().unwrap()));
+
+        assert_eq!(preview, actual);
+        assert!(preview.iter().any(|c| c["path"] == "__meta__"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_clear_active_preset() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_clear_active_test");
+
+        set_active_preset("economy").unwrap();
+        assert_eq!(get_active_preset().as_deref(), Some("economy"));
+
+        clear_active_preset().unwrap();
+        assert_eq!(get_active_preset(), None);
+
+        // 文件已不存在时再次清除应视为成功
+        assert!(clear_active_preset().is_ok());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_preset_creates_preset_backup() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_load_backup_test");
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("oh-my-openagent.json"),
+            r#"{"agents":{"oracle":{"model":"openai/gpt-5"}},"categories":{}}"#,
+        )
+        .unwrap();
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("economy.json"),
+            r#"{"agents":{"oracle":{"model":"openai/gpt-5-mini"}},"categories":{}}"#,
+        )
+        .unwrap();
+
+        load_preset("economy").unwrap();
+
+        let backups: Vec<String> = fs::read_dir(config_dir.join("backups"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert!(backups[0].starts_with("preset_"));
+        let backup: Value = serde_json::from_str(
+            &fs::read_to_string(config_dir.join("backups").join(&backups[0])).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(backup["agents"]["oracle"]["model"], "openai/gpt-5");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_then_import_preset_roundtrip() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_export_import_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("team.json"),
+            r#"{
+                "agents": {"oracle": {"model": "openai/gpt-5", "variant": "high"}},
+                "categories": {"quick": {"model": "openai/gpt-5-mini"}},
+                "__meta__": {"created_at": 1, "updated_at": 2, "version": 1}
+            }"#,
+        )
+        .unwrap();
+
+        let export_path = temp_dir.join("shared").join("team-export.json");
+        export_preset("team", export_path.to_str().unwrap()).unwrap();
+        let exported: Value =
+            serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+
+        import_preset(export_path.to_str().unwrap(), "team-copy").unwrap();
+        let invalid_name = import_preset(export_path.to_str().unwrap(), "a/b");
+        let duplicate = import_preset(export_path.to_str().unwrap(), "team");
+        let imported_config = get_preset_config("team-copy").unwrap();
+        let imported_meta = get_preset_meta("team-copy").unwrap();
+
+        assert!(exported.get(META_FIELD).is_none());
+        assert_eq!(imported_config, exported);
+        assert!(imported_meta.created_at > 2);
+        assert_eq!(
+            imported_meta.source.as_deref(),
+            Some(PRESET_SOURCE_IMPORTED)
+        );
+        assert_eq!(
+            invalid_name.unwrap_err(),
+            i18n::tr_current("preset_name_invalid_path")
+        );
+        assert!(duplicate.is_err());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_duplicate_preset_copies_config_with_fresh_meta() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_duplicate_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("base.json"),
+            r#"{
+                "agents": {"oracle": {"model": "openai/gpt-5", "variant": "high"}},
+                "categories": {"quick": {"model": "openai/gpt-5-mini"}},
+                "__meta__": {"created_at": 1, "updated_at": 2, "version": 1}
+            }"#,
+        )
+        .unwrap();
+
+        let duplicated = duplicate_preset("base", "base-copy");
+        let duplicate_again = duplicate_preset("base", "base-copy");
+        let missing = duplicate_preset("missing", "other");
+        let source_config = get_preset_config("base").unwrap();
+        let copy_config = get_preset_config("base-copy").unwrap();
+        let source_meta = get_preset_meta("base").unwrap();
+        let copy_meta = get_preset_meta("base-copy").unwrap();
+
+        assert!(duplicated.is_ok());
+        assert_eq!(copy_config, source_config);
+        assert_eq!(source_meta.created_at, 1);
+        assert_eq!(source_meta.updated_at, 2);
+        assert!(copy_meta.created_at > 2);
+        assert_eq!(copy_meta.created_at, copy_meta.updated_at);
+        assert_eq!(source_meta.source, None);
+        assert_eq!(copy_meta.source.as_deref(), Some(PRESET_SOURCE_MANUAL));
+        assert_eq!(duplicate_again.unwrap_err(), "预设名称已存在");
+        assert_eq!(missing.unwrap_err(), i18n::tr_current("preset_not_found"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_diff_presets_and_current_config() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_diff_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("fast.json"),
+            r#"{
+                "agents": {"oracle": {"model": "openai/gpt-5-mini"}},
+                "categories": {},
+                "__meta__": {"created_at": 1, "updated_at": 2, "version": 1}
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            presets_dir.join("smart.json"),
+            r#"{
+                "agents": {"oracle": {"model": "openai/gpt-5"}},
+                "categories": {},
+                "__meta__": {"created_at": 3, "updated_at": 4, "version": 1}
+            }"#,
+        )
+        .unwrap();
+        let config_dir = temp_dir.join(".config").join("opencode");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("oh-my-openagent.json"),
+            r#"{"agents":{"oracle":{"model":"openai/gpt-5"}},"categories":{}}"#,
+        )
+        .unwrap();
+
+        let same = diff_presets("fast", "fast").unwrap();
+        let changes = diff_presets("fast", "smart").unwrap();
+        let with_current = diff_preset_with_current("fast").unwrap();
+        let current_same = diff_preset_with_current("smart").unwrap();
+
+        assert!(same.is_empty());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "agents.oracle.model");
+        assert_eq!(changes[0].change_type, "modified");
+        assert_eq!(changes[0].old_value, Some(Value::from("openai/gpt-5-mini")));
+        assert_eq!(changes[0].new_value, Some(Value::from("openai/gpt-5")));
+        assert_eq!(with_current.len(), 1);
+        assert_eq!(
+            with_current[0].new_value,
+            Some(Value::from("openai/gpt-5-mini"))
+        );
+        assert!(current_same.is_empty());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_preset_meta_description_serde_compat() {
+        let legacy = serde_json::json!({"created_at": 1, "updated_at": 2, "version": 1});
+        let meta = PresetMeta::from_value(&legacy).unwrap();
+        assert_eq!(meta.description, None);
+        assert!(meta.to_value().get("description").is_none());
+
+        let mut meta = meta;
+        meta.description = Some("日常编码".to_string());
+        let value = meta.to_value();
+        assert_eq!(value["description"], "日常编码");
+        assert_eq!(
+            PresetMeta::from_value(&value)
+                .unwrap()
+                .description
+                .as_deref(),
+            Some("日常编码")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_preset_history_keeps_limited_versions_and_restores() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_history_test");
+
+        let config_with_model = |index: usize| {
+            serde_json::json!({
+                "agents": {"oracle": {"model": format!("openai/model-{}", index)}},
+                "categories": {}
+            })
+        };
+        write_omo_config(&config_with_model(0)).unwrap();
+        save_preset("work").unwrap();
+        for index in 1..=7 {
+            write_omo_config(&config_with_model(index)).unwrap();
+            update_preset("work").unwrap();
+        }
+
+        let history = list_preset_history("work").unwrap();
+        let newest = history[0];
+        let newest_content: Value = serde_json::from_str(
+            &fs::read_to_string(
+                get_preset_history_dir("work")
+                    .unwrap()
+                    .join(format!("{}.json", newest)),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        restore_preset_version("work", newest).unwrap();
+        let restored = get_preset_config("work").unwrap();
+        let history_after_restore = list_preset_history("work").unwrap();
+        let missing = restore_preset_version("work", 1);
+
+        // 7 次覆盖只保留最近 5 份，且按时间从新到旧排列
+        assert_eq!(history.len(), PRESET_HISTORY_LIMIT);
+        assert!(history.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(
+            newest_content["agents"]["oracle"]["model"],
+            "openai/model-6"
+        );
+        assert_eq!(restored, config_with_model(6));
+        assert_eq!(history_after_restore.len(), PRESET_HISTORY_LIMIT);
+        assert!(history_after_restore[0] > newest);
+        assert!(missing.is_err());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_apply_updates_to_preset_skips_invalid_models() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_apply_updates_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("work.json"),
+            r#"{
+                "agents": {
+                    "oracle": {"model": "openai/gpt-5"},
+                    "explore": {"model": "openai/gpt-5-mini"},
+                    "librarian": {"model": "openai/gpt-5-mini"}
+                },
+                "categories": {"quick": {"model": "openai/gpt-5-mini"}}
+            }"#,
+        )
+        .unwrap();
+
+        let update = |agent: &str, model: &str| PresetUpdateRequest {
+            agent_name: agent.to_string(),
+            model: model.to_string(),
+            variant: None,
+        };
+        let errors = apply_updates_to_preset(
+            "work",
+            &[
+                update("oracle", "anthropic/claude-opus-4-5"),
+                update("explore", ""),
+                update("librarian", "gpt-5.2"),
+                update("quick", "google/gemini-3-flash"),
+            ],
+        )
+        .unwrap();
+        let preset = get_preset_config("work").unwrap();
+        let all_invalid = apply_updates_to_preset("work", &[update("oracle", "/")]).unwrap();
+        let history = list_preset_history("work").unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("explore:"));
+        assert!(errors[1].starts_with("librarian:"));
+        assert_eq!(
+            preset["agents"]["oracle"]["model"],
+            "anthropic/claude-opus-4-5"
+        );
+        assert_eq!(preset["agents"]["explore"]["model"], "openai/gpt-5-mini");
+        assert_eq!(preset["agents"]["librarian"]["model"], "openai/gpt-5-mini");
+        assert_eq!(
+            preset["categories"]["quick"]["model"],
+            "google/gemini-3-flash"
+        );
+        // 全部不合法时不写入预设，也不产生新的历史版本
+        assert_eq!(all_invalid.len(), 1);
+        assert_eq!(history.len(), 1);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_preset_meta_source_serde_compat() {
+        let legacy = serde_json::json!({"created_at": 1, "updated_at": 2, "version": 1});
+        let meta = PresetMeta::from_value(&legacy).unwrap();
+        assert_eq!(meta.source, None);
+        assert!(meta.to_value().get("source").is_none());
+
+        let value = PresetMeta::with_source(PRESET_SOURCE_IMPORTED).to_value();
+        assert_eq!(value["source"], "imported");
+        assert_eq!(
+            PresetMeta::from_value(&value).unwrap().source.as_deref(),
+            Some(PRESET_SOURCE_IMPORTED)
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_preset_description() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_description_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("work.json"),
+            r#"{
+                "agents": {},
+                "categories": {},
+                "__meta__": {"created_at": 1, "updated_at": 2, "version": 1}
+            }"#,
+        )
+        .unwrap();
+
+        let before = get_preset_meta("work").unwrap();
+        set_preset_description("work", Some("  日常编码  ")).unwrap();
+        let described = get_preset_meta("work").unwrap();
+        set_preset_description("work", Some("   ")).unwrap();
+        let cleared = get_preset_meta("work").unwrap();
+        let missing = set_preset_description("missing", Some("x"));
+
+        assert_eq!(before.description, None);
+        assert_eq!(described.description.as_deref(), Some("日常编码"));
+        assert_eq!(described.created_at, 1);
+        assert_eq!(cleared.description, None);
+        assert_eq!(missing.unwrap_err(), i18n::tr_current("preset_not_found"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_rename_preset_keeps_meta_and_active_marker() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_rename_test");
+
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("work.json"),
+            r#"{
+                "agents": {"oracle": {"model": "openai/gpt-5"}},
+                "categories": {},
+                "__meta__": {"created_at": 1, "updated_at": 2, "version": 1}
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            presets_dir.join("home.json"),
+            r#"{"agents":{},"categories":{}}"#,
+        )
+        .unwrap();
+        set_active_preset("work").unwrap();
+
+        let renamed = rename_preset("work", "office");
+        let conflict = rename_preset("office", "home");
+        let meta = get_preset_meta("office").unwrap();
+        let config = get_preset_config("office").unwrap();
+        let active = get_active_preset();
+
+        assert!(renamed.is_ok());
+        assert!(!presets_dir.join("work.json").exists());
+        assert_eq!(meta.created_at, 1);
+        assert!(meta.updated_at > 2);
+        assert_eq!(config["agents"]["oracle"]["model"], "openai/gpt-5");
+        assert_eq!(active.as_deref(), Some("office"));
+        assert_eq!(conflict.unwrap_err(), "预设名称已存在");
+        assert!(presets_dir.join("office.json").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_migrate_active_preset_marker() {
+        let (temp_dir, _home) = with_temp_home("omo_preset_migrate_marker_test");
+
+        // 普通预设名保持不变
+        set_active_preset("my-preset").unwrap();
+        assert_eq!(migrate_active_preset_marker().as_deref(), Some("my-preset"));
+
+        // 无 default 预设时清除旧标记
+        set_active_preset("__builtin__economy").unwrap();
+        assert_eq!(migrate_active_preset_marker(), None);
+        assert_eq!(get_active_preset(), None);
+
+        // 存在 default 预设时回退到 default
+        let presets_dir = get_presets_dir().unwrap();
+        fs::create_dir_all(&presets_dir).unwrap();
+        fs::write(
+            presets_dir.join("default.json"),
+            r#"{"agents":{},"categories":{}}"#,
+        )
+        .unwrap();
+        set_active_preset("__builtin__economy").unwrap();
+        assert_eq!(migrate_active_preset_marker().as_deref(), Some("default"));
+        assert_eq!(get_active_preset().as_deref(), Some("default"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
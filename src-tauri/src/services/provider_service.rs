@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use crate::i18n;
 use crate::services::provider_store;
 use crate::services::provider_store::AuthEntry;
 
@@ -22,6 +23,76 @@ const BASE_URL_COMPATIBLE_PROVIDERS: &[&str] = &[
     "minimax-cn-coding-plan",
 ];
 
+/// 并发刷新自定义供应商模型列表时的最大并行请求数
+const CUSTOM_PROVIDER_REFRESH_CONCURRENCY: usize = 4;
+/// 已知供应商的显示名称：(id, 英文名, 简体中文名, 繁体中文名)
+const PROVIDER_DISPLAY_NAMES: &[(&str, &str, &str, &str)] = &[
+    ("anthropic", "Anthropic", "Anthropic", "Anthropic"),
+    ("openai", "OpenAI", "OpenAI", "OpenAI"),
+    ("google", "Google Gemini", "谷歌 Gemini", "谷歌 Gemini"),
+    ("deepseek", "DeepSeek", "深度求索", "深度求索"),
+    ("groq", "Groq", "Groq", "Groq"),
+    ("openrouter", "OpenRouter", "OpenRouter", "OpenRouter"),
+    ("xai", "xAI", "xAI", "xAI"),
+    ("mistral", "Mistral AI", "Mistral AI", "Mistral AI"),
+    (
+        "github-copilot",
+        "GitHub Copilot",
+        "GitHub Copilot",
+        "GitHub Copilot",
+    ),
+    ("opencode", "OpenCode Zen", "OpenCode Zen", "OpenCode Zen"),
+    (
+        "amazon-bedrock",
+        "Amazon Bedrock",
+        "亚马逊 Bedrock",
+        "亞馬遜 Bedrock",
+    ),
+    ("azure", "Azure OpenAI", "Azure OpenAI", "Azure OpenAI"),
+    (
+        "kimi-for-coding",
+        "Kimi for Coding",
+        "Kimi 编程版",
+        "Kimi 編程版",
+    ),
+    ("moonshotai", "Moonshot AI", "月之暗面", "月之暗面"),
+    (
+        "moonshotai-cn",
+        "Moonshot AI (China)",
+        "月之暗面（国内）",
+        "月之暗面（國內）",
+    ),
+    ("zhipuai", "Zhipu AI", "智谱 AI", "智譜 AI"),
+    (
+        "zhipuai-coding-plan",
+        "Zhipu AI Coding Plan",
+        "智谱 AI 编程套餐",
+        "智譜 AI 編程套餐",
+    ),
+    ("minimax", "MiniMax", "MiniMax", "MiniMax"),
+    (
+        "minimax-cn",
+        "MiniMax (China)",
+        "MiniMax（国内）",
+        "MiniMax（國內）",
+    ),
+];
+
+/// 供应商 HTTP 请求（/models 刷新、连接测试）的超时时间（秒）
+const PROVIDER_HTTP_TIMEOUT_SECS: u64 = 10;
+/// 连接测试允许自定义的超时范围（秒）
+const CONNECTION_TEST_TIMEOUT_MIN_SECS: u64 = 1;
+const CONNECTION_TEST_TIMEOUT_MAX_SECS: u64 = 60;
+/// 需要真正发起请求做连接测试的供应商类型（各自需要特定的鉴权 header/参数）
+const NETWORK_TESTED_PROVIDER_NPMS: &[&str] = &[
+    "@ai-sdk/anthropic",
+    "@ai-sdk/google",
+    "@ai-sdk/openai-compatible",
+];
+const ANTHROPIC_DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1";
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+const GOOGLE_DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderInfo {
     pub id: String,
@@ -33,6 +104,7 @@ pub struct ProviderInfo {
     pub supports_base_url: bool,
     pub supports_connection_test: bool,
     pub can_delete_auth: bool,
+    pub is_disabled: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +115,22 @@ pub struct ProviderConfigSnapshot {
     pub default_provider_type: String,
 }
 
+/// 保存 API Key 的结果：格式可疑时仍会保存，但附带非阻断警告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeySaveResult {
+    pub saved: bool,
+    pub warning: Option<String>,
+}
+
+/// 从环境变量批量导入 API Key 的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvKeyImportResult {
+    /// 已写入 auth.json 的供应商 ID
+    pub imported: Vec<String>,
+    /// 带前缀但无法识别为供应商（或值为空）而被忽略的环境变量名
+    pub skipped: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionTestResult {
     pub success: bool,
@@ -70,12 +158,41 @@ pub fn provider_default_npm(provider_id: &str) -> &'static str {
     }
 }
 
+/// 将 provider_id 转为 title-case，例如 my-proxy -> My Proxy
+fn title_case_provider_id(provider_id: &str) -> String {
+    provider_id
+        .split(['-', '_'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 查询已知供应商的显示名称，简体/繁体中文环境返回对应的中文名
+pub fn provider_display_name(provider_id: &str, locale: &str) -> Option<&'static str> {
+    PROVIDER_DISPLAY_NAMES
+        .iter()
+        .find(|(id, _, _, _)| *id == provider_id)
+        .map(|(_, en, zh_cn, zh_tw)| match locale {
+            "zh-TW" => *zh_tw,
+            _ if locale.starts_with("zh") => *zh_cn,
+            _ => *en,
+        })
+}
+
 pub fn provider_supports_base_url(provider_id: &str) -> bool {
     provider_id != "opencode"
 }
 
 pub fn provider_supports_connection_test(provider_id: &str) -> bool {
     BASE_URL_COMPATIBLE_PROVIDERS.contains(&provider_id)
+        || matches!(provider_id, "anthropic" | "google")
 }
 
 pub fn is_valid_base_url(url: &str) -> bool {
@@ -108,7 +225,21 @@ fn get_provider_npm(provider_id: &str, config: &Value) -> Option<String> {
         .map(ToString::to_string)
 }
 
-pub fn get_provider_status() -> Result<Vec<ProviderInfo>, String> {
+/// 供应商列表排序：`name` 仅按名称；`configured_first` 先按是否已配置，再按名称
+/// 未知的排序模式按 `name` 处理
+fn sort_providers(providers: &mut [ProviderInfo], sort: &str) {
+    let configured_first = sort == "configured_first";
+    providers.sort_by(|a, b| {
+        let by_configured = if configured_first {
+            b.is_configured.cmp(&a.is_configured)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        by_configured.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+pub fn get_provider_status(sort: &str) -> Result<Vec<ProviderInfo>, String> {
     let provider_models = provider_store::read_provider_models()?;
     let connected = provider_store::read_connected_providers()?;
     let auth_data = match provider_store::read_auth_file() {
@@ -129,6 +260,8 @@ pub fn get_provider_status() -> Result<Vec<ProviderInfo>, String> {
         }
     };
     let builtin_presets = provider_store::load_builtin_provider_presets();
+    let disabled_providers = provider_store::read_disabled_providers();
+    let locale = i18n::get_locale();
 
     let mut provider_ids: std::collections::HashSet<String> =
         builtin_presets.keys().cloned().collect();
@@ -144,9 +277,10 @@ pub fn get_provider_status() -> Result<Vec<ProviderInfo>, String> {
         let is_configured = connected.contains(&provider_id) || has_auth;
         providers.push(ProviderInfo {
             id: provider_id.clone(),
-            name: preset
-                .map(|entry| entry.name.clone())
-                .unwrap_or_else(|| provider_id.clone()),
+            name: provider_display_name(&provider_id, &locale)
+                .map(ToString::to_string)
+                .or_else(|| preset.map(|entry| entry.name.clone()))
+                .unwrap_or_else(|| title_case_provider_id(&provider_id)),
             npm: preset.and_then(|entry| entry.npm.clone()),
             website_url: preset.and_then(|entry| entry.website_url.clone()),
             is_configured,
@@ -154,10 +288,11 @@ pub fn get_provider_status() -> Result<Vec<ProviderInfo>, String> {
             supports_base_url: provider_supports_base_url(&provider_id),
             supports_connection_test: provider_supports_connection_test(&provider_id),
             can_delete_auth: has_auth,
+            is_disabled: disabled_providers.contains(&provider_id),
         });
     }
 
-    providers.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    sort_providers(&mut providers, sort);
     Ok(providers)
 }
 
@@ -185,10 +320,19 @@ pub fn get_provider_config(provider_id: String) -> Result<ProviderConfigSnapshot
     })
 }
 
+/// 连接测试超时：未指定时使用默认值，越界值收敛到 1–60 秒
+fn normalize_connection_timeout(timeout_secs: Option<u64>) -> u64 {
+    timeout_secs.unwrap_or(PROVIDER_HTTP_TIMEOUT_SECS).clamp(
+        CONNECTION_TEST_TIMEOUT_MIN_SECS,
+        CONNECTION_TEST_TIMEOUT_MAX_SECS,
+    )
+}
+
 pub fn test_provider_connection(
     npm: String,
     base_url: Option<String>,
     api_key: String,
+    timeout_secs: Option<u64>,
 ) -> Result<ConnectionTestResult, String> {
     if api_key.trim().is_empty() {
         return Ok(ConnectionTestResult {
@@ -217,21 +361,190 @@ pub fn test_provider_connection(
         });
     }
 
+    if let Some(probe) = build_connection_probe(npm.trim(), base_url.as_deref(), api_key.trim()) {
+        return Ok(run_connection_probe(
+            &probe,
+            normalize_connection_timeout(timeout_secs),
+        ));
+    }
+
     Ok(ConnectionTestResult {
         success: true,
         message: "配置校验通过".to_string(),
     })
 }
 
+/// 连接测试请求描述
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConnectionProbe {
+    url: String,
+    headers: Vec<(&'static str, String)>,
+    /// `url` 返回 404 时回退探测的地址（没有 /models 端点的 openai-compatible 网关）
+    fallback_url: Option<String>,
+}
+
+/// 取 base URL 的根路径，例如 `https://gw.example.com/v1` → `https://gw.example.com/`
+fn base_url_root(base_url: &str) -> Option<String> {
+    let (scheme, rest) = base_url.split_once("://")?;
+    let host = rest.split('/').next().filter(|host| !host.is_empty())?;
+    Some(format!("{}://{}/", scheme, host))
+}
+
+/// 为白名单内的供应商类型构建连接测试请求，其余类型返回 None（仅做配置校验）
+fn build_connection_probe(
+    npm: &str,
+    base_url: Option<&str>,
+    api_key: &str,
+) -> Option<ConnectionProbe> {
+    if !NETWORK_TESTED_PROVIDER_NPMS.contains(&npm) {
+        return None;
+    }
+
+    let custom_base = base_url
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.trim_end_matches('/'));
+
+    match npm {
+        "@ai-sdk/anthropic" => Some(ConnectionProbe {
+            url: format!(
+                "{}/models",
+                custom_base.unwrap_or(ANTHROPIC_DEFAULT_BASE_URL)
+            ),
+            headers: vec![
+                ("x-api-key", api_key.to_string()),
+                ("anthropic-version", ANTHROPIC_API_VERSION.to_string()),
+            ],
+            fallback_url: None,
+        }),
+        // key 放在请求头而非查询参数：ureq 的连接错误信息会带上完整 URL
+        "@ai-sdk/google" => Some(ConnectionProbe {
+            url: format!("{}/models", custom_base.unwrap_or(GOOGLE_DEFAULT_BASE_URL)),
+            headers: vec![("x-goog-api-key", api_key.to_string())],
+            fallback_url: None,
+        }),
+        // openai-compatible 没有公认的默认地址，未填写 base URL 时只做配置校验
+        "@ai-sdk/openai-compatible" => custom_base.map(|base| ConnectionProbe {
+            url: format!("{}/models", base),
+            headers: vec![("Authorization", format!("Bearer {}", api_key))],
+            fallback_url: base_url_root(base),
+        }),
+        _ => None,
+    }
+}
+
+/// 将 HTTP 状态码转换为连接测试结果
+fn connection_result_from_status(status: u16) -> ConnectionTestResult {
+    match status {
+        200..=299 => ConnectionTestResult {
+            success: true,
+            message: "连接成功".to_string(),
+        },
+        401 | 403 => ConnectionTestResult {
+            success: false,
+            message: format!("API Key 无效或无权限（HTTP {}）", status),
+        },
+        _ => ConnectionTestResult {
+            success: false,
+            message: format!("连接失败（HTTP {}）", status),
+        },
+    }
+}
+
+/// 发送一次 GET 请求，返回 HTTP 状态码；无法建立连接时返回错误信息
+fn send_probe_request(
+    url: &str,
+    headers: &[(&'static str, String)],
+    timeout_secs: u64,
+) -> Result<u16, String> {
+    let mut request = ureq::get(url).timeout(std::time::Duration::from_secs(timeout_secs));
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    match request.call() {
+        Ok(resp) => Ok(resp.status()),
+        Err(ureq::Error::Status(status, _)) => Ok(status),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn run_connection_probe(probe: &ConnectionProbe, timeout_secs: u64) -> ConnectionTestResult {
+    run_connection_probe_with(probe, |url| {
+        send_probe_request(url, &probe.headers, timeout_secs)
+    })
+}
+
+fn run_connection_probe_with<F>(probe: &ConnectionProbe, send: F) -> ConnectionTestResult
+where
+    F: Fn(&str) -> Result<u16, String>,
+{
+    let connection_failed = |err: String| ConnectionTestResult {
+        success: false,
+        message: format!("连接失败: {}", err),
+    };
+
+    match send(&probe.url) {
+        Ok(404) => {
+            let Some(fallback_url) = probe.fallback_url.as_deref() else {
+                return connection_result_from_status(404);
+            };
+            // 网关没有 /models 端点：根路径能响应且非 5xx 即视为可达，但无法验证 key
+            match send(fallback_url) {
+                Ok(status) if status < 500 => ConnectionTestResult {
+                    success: true,
+                    message: "服务可达，但该网关没有 /models 端点，未验证 API Key".to_string(),
+                },
+                Ok(status) => connection_result_from_status(status),
+                Err(err) => connection_failed(err),
+            }
+        }
+        Ok(status) => connection_result_from_status(status),
+        Err(err) => connection_failed(err),
+    }
+}
+
+/// 已知供应商的 API Key 前缀（更具体的前缀需排在前面）
+const KNOWN_KEY_PREFIXES: &[(&str, &str)] = &[
+    ("anthropic", "sk-ant-"),
+    ("openrouter", "sk-or-"),
+    ("openai", "sk-"),
+    ("deepseek", "sk-"),
+    ("groq", "gsk_"),
+    ("xai", "xai-"),
+    ("google", "AIza"),
+];
+
+/// 对 API Key 做轻量格式校验，不通过时返回警告文案
+/// 只做提示不做拦截：第三方中转常使用自定义格式的 key
+fn looks_like_valid_key(provider_id: &str, key: &str) -> Result<(), String> {
+    if key.chars().any(char::is_whitespace) {
+        return Err("API Key 中包含空白字符，可能是粘贴时混入了换行或空格".to_string());
+    }
+
+    if let Some((_, prefix)) = KNOWN_KEY_PREFIXES.iter().find(|(id, _)| *id == provider_id) {
+        if !key.starts_with(prefix) {
+            return Err(format!(
+                "{} 的 API Key 通常以 {} 开头，请确认是否粘贴正确",
+                provider_id, prefix
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn set_provider_api_key(
     provider_id: String,
     api_key: String,
     base_url: Option<String>,
     provider_type: Option<String>,
-) -> Result<(), String> {
-    if api_key.trim().is_empty() {
+) -> Result<ApiKeySaveResult, String> {
+    let api_key = api_key.trim().to_string();
+    if api_key.is_empty() {
         return Err("API Key 不能为空".to_string());
     }
+    let warning = looks_like_valid_key(&provider_id, &api_key).err();
 
     let provider_id_for_config = provider_id.clone();
     let auth_existed = provider_store::get_auth_file_path()?.exists();
@@ -301,22 +614,134 @@ pub fn set_provider_api_key(
         }
     }
 
-    Ok(())
+    Ok(ApiKeySaveResult {
+        saved: true,
+        warning,
+    })
+}
+
+/// 将 `{prefix}_KIMI_FOR_CODING` 形式的环境变量映射为 (provider_id, key)
+/// 后缀转小写、下划线转连字符后必须命中已知供应商，否则记入忽略列表
+fn collect_env_keys(
+    vars: impl IntoIterator<Item = (String, String)>,
+    prefix: &str,
+    known_ids: &HashSet<String>,
+) -> (Vec<(String, String)>, Vec<String>) {
+    let var_prefix = format!("{}_", prefix);
+    let mut keys = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, value) in vars {
+        let Some(suffix) = name.strip_prefix(&var_prefix) else {
+            continue;
+        };
+        let provider_id = suffix.to_lowercase().replace('_', "-");
+        let value = value.trim();
+        if known_ids.contains(&provider_id) && !value.is_empty() {
+            keys.push((provider_id, value.to_string()));
+        } else {
+            skipped.push(name);
+        }
+    }
+    keys.sort();
+    skipped.sort();
+    (keys, skipped)
+}
+
+/// 扫描 `{prefix}_OPENAI`、`{prefix}_ANTHROPIC` 等环境变量，把 key 批量写入 auth.json
+/// 只更新 auth.json，不修改 opencode.json 中的供应商配置
+pub fn import_keys_from_env(prefix: String) -> Result<EnvKeyImportResult, String> {
+    let prefix = prefix.trim().trim_end_matches('_').to_uppercase();
+    if prefix.is_empty() {
+        return Err("环境变量前缀不能为空".to_string());
+    }
+
+    let mut known_ids: HashSet<String> = provider_store::load_builtin_provider_presets()
+        .into_keys()
+        .collect();
+    known_ids.extend(
+        PROVIDER_DISPLAY_NAMES
+            .iter()
+            .map(|(id, _, _, _)| id.to_string()),
+    );
+    known_ids.extend(provider_store::read_config_provider_ids().unwrap_or_default());
+
+    let (keys, skipped) = collect_env_keys(std::env::vars(), &prefix, &known_ids);
+    for name in &skipped {
+        eprintln!("警告：忽略无法识别的环境变量 {}", name);
+    }
+    if keys.is_empty() {
+        return Ok(EnvKeyImportResult {
+            imported: Vec::new(),
+            skipped,
+        });
+    }
+
+    let mut auth_data = provider_store::read_auth_file()?;
+    let mut imported = Vec::new();
+    for (provider_id, key) in keys {
+        auth_data.insert(
+            provider_id.clone(),
+            AuthEntry {
+                auth_type: Some("api".to_string()),
+                key: Some(key),
+                extra: HashMap::new(),
+            },
+        );
+        imported.push(provider_id);
+    }
+    provider_store::write_auth_file(&auth_data)?;
+
+    Ok(EnvKeyImportResult { imported, skipped })
 }
 
-pub fn delete_provider_auth(provider_id: String) -> Result<(), String> {
+/// 删除供应商的 auth 凭据
+/// remove_from_config 为 true 且为自定义（非内置）供应商时，同时移除 opencode.json 中的 provider.{id} 配置块；
+/// 内置供应商始终只删除 auth
+pub fn delete_provider_auth(provider_id: String, remove_from_config: bool) -> Result<(), String> {
     let mut auth_data = provider_store::read_auth_file()?;
-    if auth_data.remove(&provider_id).is_none() {
+    if auth_data.remove(&provider_id).is_some() {
+        provider_store::write_auth_file(&auth_data)?;
+    }
+
+    let is_builtin = provider_store::load_builtin_provider_presets().contains_key(&provider_id);
+    if remove_from_config && !is_builtin {
+        remove_provider_from_config(&provider_id)?;
+    }
+    Ok(())
+}
+
+/// 从 opencode.json 中移除 provider.{id} 配置块，不存在时跳过
+fn remove_provider_from_config(provider_id: &str) -> Result<(), String> {
+    let mut config = provider_store::read_opencode_config()?;
+    let removed = config
+        .get_mut("provider")
+        .and_then(Value::as_object_mut)
+        .and_then(|providers| providers.remove(provider_id));
+    if removed.is_none() {
         return Ok(());
     }
-    provider_store::write_auth_file(&auth_data)
+    provider_store::write_opencode_config(&config)
 }
 
+/// 未指定 npm 包时自定义供应商使用的默认类型
+const DEFAULT_CUSTOM_PROVIDER_NPM: &str = "@ai-sdk/openai-compatible";
+
+/// 添加自定义供应商，npm 为空时默认使用 openai-compatible
 pub fn add_custom_provider(
     name: String,
     api_key: String,
     base_url: String,
+    npm: Option<String>,
 ) -> Result<ProviderInfo, String> {
+    let npm = npm
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_CUSTOM_PROVIDER_NPM)
+        .to_string();
+    if !npm.starts_with('@') {
+        return Err("Provider npm 标识格式无效".to_string());
+    }
     if name.trim().is_empty() {
         return Err("Provider 名称不能为空".to_string());
     }
@@ -338,7 +763,7 @@ pub fn add_custom_provider(
     }
 
     config["provider"][&provider_key] = json!({
-        "npm": "@ai-sdk/openai-compatible",
+        "npm": npm,
         "options": { "baseURL": base_url }
     });
 
@@ -364,27 +789,441 @@ pub fn add_custom_provider(
     Ok(ProviderInfo {
         id: provider_key,
         name,
-        npm: Some("@ai-sdk/openai-compatible".to_string()),
+        npm: Some(npm),
         website_url: Some(base_url),
         is_configured: true,
         is_builtin: false,
         supports_base_url: true,
         supports_connection_test: true,
         can_delete_auth: true,
+        is_disabled: false,
+    })
+}
+
+/// 更新已存在的自定义供应商：修改 baseURL/名称，并更新 auth.json 中的 key
+/// api_key 为空时保留原有 key；provider 不存在时直接报错，不会隐式创建
+pub fn update_custom_provider(
+    provider_id: String,
+    name: String,
+    api_key: String,
+    base_url: String,
+) -> Result<ProviderInfo, String> {
+    if !is_valid_base_url(&base_url) {
+        return Err("Base URL 必须以 http:// 或 https:// 开头".to_string());
+    }
+
+    let config_existed = provider_store::get_opencode_config_path()?.exists();
+    let original_config = provider_store::read_opencode_config()?;
+    let mut config = original_config.clone();
+    let Some(provider) = config
+        .get_mut("provider")
+        .and_then(|providers| providers.get_mut(&provider_id))
+        .and_then(Value::as_object_mut)
+    else {
+        return Err(format!("供应商 {} 不存在", provider_id));
+    };
+
+    // 只修改 options.baseURL 与 name，其余字段（尤其是 models）保持原样
+    let options = provider
+        .entry("options".to_string())
+        .or_insert_with(|| json!({}));
+    if !options.is_object() {
+        *options = json!({});
+    }
+    if let Some(options) = options.as_object_mut() {
+        options.remove("baseUrl");
+        options.insert("baseURL".to_string(), json!(base_url.trim()));
+    }
+    let display_name = name.trim();
+    if !display_name.is_empty() {
+        provider.insert("name".to_string(), json!(display_name));
+    }
+    let npm = provider
+        .get("npm")
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
+
+    provider_store::write_opencode_config(&config)?;
+
+    if !api_key.trim().is_empty() {
+        let auth_existed = provider_store::get_auth_file_path()?.exists();
+        let original_auth = provider_store::read_auth_file()?;
+        let mut auth_data = original_auth.clone();
+        let entry = auth_data
+            .entry(provider_id.clone())
+            .or_insert_with(|| AuthEntry {
+                auth_type: Some("api".to_string()),
+                key: None,
+                extra: HashMap::new(),
+            });
+        entry.key = Some(api_key.trim().to_string());
+
+        if let Err(err) = provider_store::write_auth_file(&auth_data) {
+            provider_store::restore_opencode_config_state(config_existed, &original_config)?;
+            provider_store::restore_auth_state(auth_existed, &original_auth)?;
+            return Err(err);
+        }
+    }
+
+    Ok(ProviderInfo {
+        id: provider_id.clone(),
+        name: if display_name.is_empty() {
+            provider_id.clone()
+        } else {
+            display_name.to_string()
+        },
+        npm,
+        website_url: Some(base_url.trim().to_string()),
+        is_configured: true,
+        is_builtin: false,
+        supports_base_url: true,
+        supports_connection_test: true,
+        can_delete_auth: true,
+        is_disabled: provider_store::read_disabled_providers().contains(&provider_id),
     })
 }
 
+/// 临时禁用供应商：保留 auth.json 中的密钥，仅在托盘/模型选择中隐藏
+pub fn disable_provider(provider_id: String) -> Result<(), String> {
+    if provider_id.trim().is_empty() {
+        return Err("Provider ID 不能为空".to_string());
+    }
+    let mut disabled = provider_store::read_disabled_providers();
+    if disabled.insert(provider_id) {
+        provider_store::write_disabled_providers(&disabled)?;
+    }
+    Ok(())
+}
+
+/// 重新启用之前被禁用的供应商
+pub fn enable_provider(provider_id: String) -> Result<(), String> {
+    let mut disabled = provider_store::read_disabled_providers();
+    if disabled.remove(&provider_id) {
+        provider_store::write_disabled_providers(&disabled)?;
+    }
+    Ok(())
+}
+
+/// 列出当前被禁用的供应商（按名称排序）
+pub fn list_disabled_providers() -> Vec<String> {
+    provider_store::read_disabled_providers()
+        .into_iter()
+        .collect()
+}
+
+/// 供应商状态诊断的综合判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderDiagnosticStatus {
+    /// 三处状态一致，可正常使用
+    Healthy,
+    /// auth.json 中有 key，但 OMO 未检测到连接
+    AuthNotConnected,
+    /// 已连接但缺少模型列表缓存
+    MissingModels,
+    /// 已连接但 auth.json 中没有 key（环境变量等方式提供凭据）
+    ConnectedWithoutAuth,
+    /// 只在模型列表缓存中出现，既无 key 也未连接
+    StaleModels,
+}
+
+/// 单个供应商在 auth.json / connected-providers.json / provider-models.json 中的状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDiagnostic {
+    pub provider_id: String,
+    pub in_auth: bool,
+    pub in_connected: bool,
+    pub in_models: bool,
+    pub status: ProviderDiagnosticStatus,
+    pub suggestion: String,
+}
+
+/// 根据三个文件中的存在情况给出综合判定与建议文案
+fn classify_provider_state(
+    in_auth: bool,
+    in_connected: bool,
+    in_models: bool,
+) -> (ProviderDiagnosticStatus, &'static str) {
+    match (in_auth, in_connected, in_models) {
+        (true, true, true) => (ProviderDiagnosticStatus::Healthy, "状态正常"),
+        (false, true, true) => (
+            ProviderDiagnosticStatus::ConnectedWithoutAuth,
+            "已连接但 auth.json 中没有 key，凭据可能来自环境变量；如需在此管理请重新填写 API Key",
+        ),
+        (_, true, false) => (
+            ProviderDiagnosticStatus::MissingModels,
+            "已连接但缺少模型列表缓存，请运行一次 opencode 或在设置中刷新模型列表",
+        ),
+        (true, false, _) => (
+            ProviderDiagnosticStatus::AuthNotConnected,
+            "已配置 API Key 但 OMO 未检测到连接，请重新启动 opencode 以刷新连接状态，并确认 Key 有效",
+        ),
+        (false, false, _) => (
+            ProviderDiagnosticStatus::StaleModels,
+            "仅存在于模型列表缓存中，未配置 Key 也未连接；如需使用请先配置 API Key",
+        ),
+    }
+}
+
+/// 汇总各供应商在三个状态文件中的情况，结果按供应商 ID 排序
+fn build_provider_diagnostics(
+    auth_ids: &HashSet<String>,
+    connected: &HashSet<String>,
+    model_ids: &HashSet<String>,
+) -> Vec<ProviderDiagnostic> {
+    let provider_ids: BTreeSet<&String> = auth_ids
+        .iter()
+        .chain(connected.iter())
+        .chain(model_ids.iter())
+        .collect();
+
+    provider_ids
+        .into_iter()
+        .map(|provider_id| {
+            let in_auth = auth_ids.contains(provider_id);
+            let in_connected = connected.contains(provider_id);
+            let in_models = model_ids.contains(provider_id);
+            let (status, suggestion) = classify_provider_state(in_auth, in_connected, in_models);
+            ProviderDiagnostic {
+                provider_id: provider_id.clone(),
+                in_auth,
+                in_connected,
+                in_models,
+                status,
+                suggestion: suggestion.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// 诊断供应商状态：聚合 auth.json、connected-providers.json 与 provider-models.json
+/// 用于排查“已配置 key 但显示未连接”等问题；单个文件读取失败时按空数据处理
+pub fn diagnose_providers() -> Vec<ProviderDiagnostic> {
+    let auth_ids: HashSet<String> = provider_store::get_auth_provider_ids()
+        .into_iter()
+        .collect();
+    let connected = provider_store::read_connected_providers().unwrap_or_else(|err| {
+        eprintln!("警告：读取 connected-providers.json 失败: {}", err);
+        HashSet::new()
+    });
+    let model_ids: HashSet<String> = provider_store::read_provider_models()
+        .unwrap_or_else(|err| {
+            eprintln!("警告：读取 provider-models.json 失败: {}", err);
+            HashMap::new()
+        })
+        .into_keys()
+        .collect();
+
+    build_provider_diagnostics(&auth_ids, &connected, &model_ids)
+}
+
+/// 待刷新模型列表的自定义供应商
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CustomProviderEndpoint {
+    id: String,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+/// 从 opencode.json 中筛选出配置了 baseURL 的自定义（非内置）供应商
+fn collect_custom_provider_endpoints(
+    config: &Value,
+    auth_data: &HashMap<String, AuthEntry>,
+    builtin_ids: &std::collections::HashSet<String>,
+) -> Vec<CustomProviderEndpoint> {
+    let Some(providers) = config.get("provider").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    providers
+        .keys()
+        .filter(|id| !builtin_ids.contains(*id))
+        .filter_map(|id| {
+            let base_url = get_provider_base_url(id, config)?;
+            let api_key = auth_data
+                .get(id)
+                .and_then(|entry| entry.key.clone())
+                .or_else(|| {
+                    config["provider"][id]["options"]["apiKey"]
+                        .as_str()
+                        .map(ToString::to_string)
+                })
+                .filter(|key| !key.trim().is_empty());
+            Some(CustomProviderEndpoint {
+                id: id.clone(),
+                base_url,
+                api_key,
+            })
+        })
+        .collect()
+}
+
+/// 解析 OpenAI 兼容 /models 响应中的模型 ID（{"data": [{"id": ...}]}）
+fn parse_models_response(body: &Value) -> Result<Vec<String>, String> {
+    let data = body
+        .get("data")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "响应缺少 data 数组".to_string())?;
+
+    let mut ids: Vec<String> = data
+        .iter()
+        .filter_map(|entry| entry.get("id").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// 请求单个供应商的 /models 接口
+fn fetch_custom_provider_models(endpoint: &CustomProviderEndpoint) -> Result<Vec<String>, String> {
+    let url = format!("{}/models", endpoint.base_url.trim_end_matches('/'));
+    let mut request =
+        ureq::get(&url).timeout(std::time::Duration::from_secs(PROVIDER_HTTP_TIMEOUT_SECS));
+    if let Some(key) = &endpoint.api_key {
+        request = request.set("Authorization", &format!("Bearer {}", key));
+    }
+
+    let body: Value = request
+        .call()
+        .map_err(|e| format!("请求 {} 失败: {}", url, e))?
+        .into_json()
+        .map_err(|e| format!("解析 {} 响应失败: {}", url, e))?;
+    parse_models_response(&body)
+}
+
+/// 将发现的模型 ID 合并到 opencode.json 的 provider.models 中，返回是否有新增
+fn merge_discovered_models(config: &mut Value, discovered: &HashMap<String, Vec<String>>) -> bool {
+    let mut changed = false;
+    for (provider_id, model_ids) in discovered {
+        let Some(provider) = config
+            .get_mut("provider")
+            .and_then(|providers| providers.get_mut(provider_id))
+            .and_then(Value::as_object_mut)
+        else {
+            continue;
+        };
+
+        let models = provider
+            .entry("models".to_string())
+            .or_insert_with(|| json!({}));
+        let Some(models) = models.as_object_mut() else {
+            continue;
+        };
+
+        for model_id in model_ids {
+            if !models.contains_key(model_id) {
+                models.insert(model_id.clone(), json!({}));
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// 并发刷新所有自定义供应商的模型列表
+/// 每个供应商独立处理失败，单个供应商出错不会中断整体刷新
+pub fn refresh_all_custom_provider_models() -> Result<HashMap<String, Vec<String>>, String> {
+    let mut config = provider_store::read_opencode_config()?;
+    let auth_data = provider_store::read_auth_file().unwrap_or_else(|err| {
+        eprintln!("警告：读取 auth.json 失败，降级为空认证数据: {}", err);
+        HashMap::new()
+    });
+    let builtin_ids = provider_store::load_builtin_provider_presets()
+        .into_keys()
+        .collect();
+    let endpoints = collect_custom_provider_endpoints(&config, &auth_data, &builtin_ids);
+
+    let mut discovered = HashMap::new();
+    for batch in endpoints.chunks(CUSTOM_PROVIDER_REFRESH_CONCURRENCY) {
+        let results: Vec<(String, Result<Vec<String>, String>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|endpoint| {
+                    scope.spawn(move || {
+                        (endpoint.id.clone(), fetch_custom_provider_models(endpoint))
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().ok())
+                .collect()
+        });
+
+        for (provider_id, result) in results {
+            match result {
+                Ok(models) => {
+                    discovered.insert(provider_id, models);
+                }
+                Err(err) => {
+                    eprintln!("警告：刷新供应商 {} 的模型列表失败: {}", provider_id, err);
+                }
+            }
+        }
+    }
+
+    if merge_discovered_models(&mut config, &discovered) {
+        provider_store::write_opencode_config(&config)?;
+    }
+
+    Ok(discovered)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serial_test::serial;
 
+    fn provider_info(name: &str, is_configured: bool) -> ProviderInfo {
+        ProviderInfo {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            npm: None,
+            website_url: None,
+            is_configured,
+            is_builtin: false,
+            supports_base_url: false,
+            supports_connection_test: false,
+            can_delete_auth: false,
+            is_disabled: false,
+        }
+    }
+
+    #[test]
+    fn test_sort_providers_configured_first() {
+        let mut providers = vec![
+            provider_info("Zhipu", true),
+            provider_info("anthropic", false),
+            provider_info("OpenAI", true),
+            provider_info("Google", false),
+        ];
+        let names = |providers: &[ProviderInfo]| -> Vec<String> {
+            providers.iter().map(|p| p.name.clone()).collect()
+        };
+
+        sort_providers(&mut providers, "configured_first");
+        assert_eq!(
+            names(&providers),
+            vec!["OpenAI", "Zhipu", "anthropic", "Google"]
+        );
+
+        sort_providers(&mut providers, "name");
+        assert_eq!(
+            names(&providers),
+            vec!["anthropic", "Google", "OpenAI", "Zhipu"]
+        );
+    }
+
     #[test]
     fn test_test_provider_connection_uses_validation_wording() {
         let result = test_provider_connection(
             "@ai-sdk/openai".to_string(),
             Some("https://api.openai.com/v1".to_string()),
             "sk-test".to_string(),
+            None,
         )
         .unwrap();
 
@@ -392,10 +1231,101 @@ mod tests {
         assert_eq!(result.message, "配置校验通过");
     }
 
+    #[test]
+    fn test_normalize_connection_timeout_clamps() {
+        assert_eq!(normalize_connection_timeout(None), 10);
+        assert_eq!(normalize_connection_timeout(Some(0)), 1);
+        assert_eq!(normalize_connection_timeout(Some(999)), 60);
+        assert_eq!(normalize_connection_timeout(Some(30)), 30);
+    }
+
+    #[test]
+    fn test_provider_display_name_hit_uses_locale() {
+        assert_eq!(
+            provider_display_name("kimi-for-coding", "en"),
+            Some("Kimi for Coding")
+        );
+        assert_eq!(provider_display_name("zhipuai", "zh-CN"), Some("智谱 AI"));
+        assert_eq!(provider_display_name("zhipuai", "en"), Some("Zhipu AI"));
+        assert_eq!(provider_display_name("zhipuai", "zh-TW"), Some("智譜 AI"));
+    }
+
+    #[test]
+    fn test_provider_display_name_miss_falls_back_to_title_case() {
+        assert_eq!(provider_display_name("my-proxy", "en"), None);
+        assert_eq!(title_case_provider_id("my-proxy"), "My Proxy");
+        assert_eq!(title_case_provider_id("corp_gateway-v2"), "Corp Gateway V2");
+    }
+
+    #[test]
+    fn test_classify_provider_state_combinations() {
+        use ProviderDiagnosticStatus::*;
+
+        let cases = [
+            ((true, true, true), Healthy),
+            ((false, true, true), ConnectedWithoutAuth),
+            ((true, true, false), MissingModels),
+            ((false, true, false), MissingModels),
+            ((true, false, true), AuthNotConnected),
+            ((true, false, false), AuthNotConnected),
+            ((false, false, true), StaleModels),
+        ];
+        for ((in_auth, in_connected, in_models), expected) in cases {
+            let (status, suggestion) = classify_provider_state(in_auth, in_connected, in_models);
+            assert_eq!(
+                status, expected,
+                "auth={} connected={} models={}",
+                in_auth, in_connected, in_models
+            );
+            assert!(!suggestion.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_build_provider_diagnostics_merges_sources() {
+        let to_set = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<HashSet<_>>();
+        let diagnostics = build_provider_diagnostics(
+            &to_set(&["openai", "deepseek"]),
+            &to_set(&["openai", "anthropic"]),
+            &to_set(&["openai", "anthropic", "groq"]),
+        );
+
+        let summary: Vec<(&str, ProviderDiagnosticStatus)> = diagnostics
+            .iter()
+            .map(|d| (d.provider_id.as_str(), d.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("anthropic", ProviderDiagnosticStatus::ConnectedWithoutAuth),
+                ("deepseek", ProviderDiagnosticStatus::AuthNotConnected),
+                ("groq", ProviderDiagnosticStatus::StaleModels),
+                ("openai", ProviderDiagnosticStatus::Healthy),
+            ]
+        );
+        assert!(
+            diagnostics[1].in_auth && !diagnostics[1].in_connected && !diagnostics[1].in_models
+        );
+    }
+
+    #[test]
+    fn test_looks_like_valid_key() {
+        assert!(looks_like_valid_key("anthropic", "sk-ant-api03-abc").is_ok());
+        assert!(looks_like_valid_key("openai", "sk-proj-abc").is_ok());
+        assert!(looks_like_valid_key("my-proxy", "anything-goes").is_ok());
+
+        let whitespace = looks_like_valid_key("openai", "sk-abc\ndef").unwrap_err();
+        assert!(whitespace.contains("空白"));
+
+        let prefix = looks_like_valid_key("anthropic", "sk-proj-abc").unwrap_err();
+        assert!(prefix.contains("sk-ant-"));
+        assert!(looks_like_valid_key("groq", "sk-abc").is_err());
+    }
+
     #[test]
     #[serial]
-    fn test_get_provider_config_reads_legacy_base_url_key() {
-        let temp_dir = std::env::temp_dir().join("omo-provider-service-legacy-baseurl-test");
+    fn test_set_provider_api_key_trims_and_warns() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-key-format-test");
         let _ = std::fs::remove_dir_all(&temp_dir);
         std::fs::create_dir_all(&temp_dir).unwrap();
 
@@ -404,16 +1334,161 @@ mod tests {
             std::env::set_var("HOME", &temp_dir);
         }
 
-        let config_dir = temp_dir.join(".config").join("opencode");
-        std::fs::create_dir_all(&config_dir).unwrap();
-        std::fs::write(
-            config_dir.join("opencode.json"),
-            r#"{
-              "provider": {
-                "openai": {
-                  "npm": "@ai-sdk/openai",
-                  "options": { "baseUrl": "https://legacy.example.com/v1" }
-                }
+        let trimmed = set_provider_api_key(
+            "anthropic".to_string(),
+            "  sk-ant-api03-abc\n".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(trimmed.saved);
+        assert!(trimmed.warning.is_none());
+        let auth = provider_store::read_auth_file().unwrap();
+        assert_eq!(
+            auth.get("anthropic").and_then(|e| e.key.as_deref()),
+            Some("sk-ant-api03-abc")
+        );
+
+        let mismatched = set_provider_api_key(
+            "anthropic".to_string(),
+            "sk-proj-abc".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(mismatched.saved);
+        assert!(mismatched.warning.is_some());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_build_connection_probe_for_whitelisted_npms() {
+        let anthropic = build_connection_probe("@ai-sdk/anthropic", None, "sk-ant").unwrap();
+        assert_eq!(anthropic.url, "https://api.anthropic.com/v1/models");
+        assert!(anthropic
+            .headers
+            .contains(&("x-api-key", "sk-ant".to_string())));
+        assert!(anthropic
+            .headers
+            .iter()
+            .any(|(name, _)| *name == "anthropic-version"));
+
+        let proxied = build_connection_probe(
+            "@ai-sdk/anthropic",
+            Some("https://api.deepseek.com/anthropic/"),
+            "sk-ds",
+        )
+        .unwrap();
+        assert_eq!(proxied.url, "https://api.deepseek.com/anthropic/models");
+
+        let google = build_connection_probe("@ai-sdk/google", None, "g-key").unwrap();
+        assert_eq!(
+            google.url,
+            "https://generativelanguage.googleapis.com/v1beta/models"
+        );
+        assert!(!google.url.contains("g-key"));
+        assert_eq!(
+            google.headers,
+            vec![("x-goog-api-key", "g-key".to_string())]
+        );
+
+        assert!(build_connection_probe("@ai-sdk/openai", None, "sk-test").is_none());
+        assert!(build_connection_probe("@ai-sdk/openai-compatible", None, "sk-test").is_none());
+    }
+
+    #[test]
+    fn test_openai_compatible_probe_falls_back_on_404() {
+        let probe = build_connection_probe(
+            "@ai-sdk/openai-compatible",
+            Some("https://gw.example.com/v1/"),
+            "sk-gw",
+        )
+        .unwrap();
+        assert_eq!(probe.url, "https://gw.example.com/v1/models");
+        assert_eq!(
+            probe.fallback_url.as_deref(),
+            Some("https://gw.example.com/")
+        );
+        assert!(probe
+            .headers
+            .contains(&("Authorization", "Bearer sk-gw".to_string())));
+
+        let fake = |models: u16, root: Result<u16, String>| {
+            move |url: &str| {
+                if url.ends_with("/models") {
+                    Ok(models)
+                } else {
+                    root.clone()
+                }
+            }
+        };
+
+        let reachable = run_connection_probe_with(&probe, fake(404, Ok(200)));
+        assert!(reachable.success);
+        assert!(reachable.message.contains("未验证"));
+
+        let root_not_found = run_connection_probe_with(&probe, fake(404, Ok(404)));
+        assert!(root_not_found.success);
+
+        let server_error = run_connection_probe_with(&probe, fake(404, Ok(502)));
+        assert!(!server_error.success);
+        assert!(server_error.message.contains("502"));
+
+        let unreachable =
+            run_connection_probe_with(&probe, fake(404, Err("tls handshake".to_string())));
+        assert!(!unreachable.success);
+
+        let verified = run_connection_probe_with(&probe, fake(200, Ok(500)));
+        assert!(verified.success);
+        assert_eq!(verified.message, "连接成功");
+    }
+
+    #[test]
+    fn test_connection_result_from_status() {
+        assert!(connection_result_from_status(200).success);
+
+        let unauthorized = connection_result_from_status(401);
+        assert!(!unauthorized.success);
+        assert!(unauthorized.message.contains("401"));
+
+        let forbidden = connection_result_from_status(403);
+        assert!(!forbidden.success);
+        assert!(forbidden.message.contains("403"));
+
+        assert!(!connection_result_from_status(500).success);
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_provider_config_reads_legacy_base_url_key() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-legacy-baseurl-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join("opencode.json"),
+            r#"{
+              "provider": {
+                "openai": {
+                  "npm": "@ai-sdk/openai",
+                  "options": { "baseUrl": "https://legacy.example.com/v1" }
+                }
               }
             }"#,
         )
@@ -445,4 +1520,399 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_collect_custom_provider_endpoints_skips_builtin_and_missing_base_url() {
+        let config = json!({
+            "provider": {
+                "openai": {
+                    "options": { "baseURL": "https://api.openai.com/v1" }
+                },
+                "my-proxy": {
+                    "npm": "@ai-sdk/openai-compatible",
+                    "options": { "baseURL": "https://proxy.example.com/v1" }
+                },
+                "local": {
+                    "options": { "baseUrl": "http://localhost:8080/v1", "apiKey": "sk-local" }
+                },
+                "no-url": { "npm": "@ai-sdk/openai-compatible" }
+            }
+        });
+        let mut auth_data = HashMap::new();
+        auth_data.insert(
+            "my-proxy".to_string(),
+            AuthEntry {
+                auth_type: Some("api".to_string()),
+                key: Some("sk-proxy".to_string()),
+                extra: HashMap::new(),
+            },
+        );
+        let builtin_ids = ["openai".to_string()].into_iter().collect();
+
+        let endpoints = collect_custom_provider_endpoints(&config, &auth_data, &builtin_ids);
+
+        assert_eq!(
+            endpoints,
+            vec![
+                CustomProviderEndpoint {
+                    id: "my-proxy".to_string(),
+                    base_url: "https://proxy.example.com/v1".to_string(),
+                    api_key: Some("sk-proxy".to_string()),
+                },
+                CustomProviderEndpoint {
+                    id: "local".to_string(),
+                    base_url: "http://localhost:8080/v1".to_string(),
+                    api_key: Some("sk-local".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_models_response_and_merge() {
+        let body = json!({
+            "object": "list",
+            "data": [{ "id": "qwen-max" }, { "id": "deepseek-v3" }, { "id": "" }]
+        });
+        let ids = parse_models_response(&body).unwrap();
+        assert_eq!(ids, vec!["deepseek-v3".to_string(), "qwen-max".to_string()]);
+        assert!(parse_models_response(&json!({})).is_err());
+
+        let mut config = json!({
+            "provider": {
+                "my-proxy": { "models": { "qwen-max": { "name": "Qwen" } } }
+            }
+        });
+        let discovered = HashMap::from([("my-proxy".to_string(), ids)]);
+
+        assert!(merge_discovered_models(&mut config, &discovered));
+        assert_eq!(
+            config["provider"]["my-proxy"]["models"]["qwen-max"]["name"],
+            "Qwen"
+        );
+        assert!(config["provider"]["my-proxy"]["models"]
+            .get("deepseek-v3")
+            .is_some());
+        assert!(!merge_discovered_models(&mut config, &discovered));
+    }
+
+    #[test]
+    #[serial]
+    fn test_import_keys_from_env_fills_auth() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-env-keys-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        let test_vars = [
+            ("OMO_TEST_KEYS_OPENAI", "sk-env-openai"),
+            ("OMO_TEST_KEYS_ANTHROPIC", " sk-ant-env "),
+            ("OMO_TEST_KEYS_KIMI_FOR_CODING", "sk-kimi"),
+            ("OMO_TEST_KEYS_NOT_A_PROVIDER", "sk-unknown"),
+        ];
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+            for (name, value) in test_vars {
+                std::env::set_var(name, value);
+            }
+        }
+
+        let auth_dir = temp_dir.join(".local").join("share").join("opencode");
+        std::fs::create_dir_all(&auth_dir).unwrap();
+        std::fs::write(
+            auth_dir.join("auth.json"),
+            r#"{"groq":{"type":"api","key":"gsk-keep"}}"#,
+        )
+        .unwrap();
+
+        let result = import_keys_from_env("omo_test_keys_".to_string());
+        let auth = provider_store::read_auth_file();
+
+        unsafe {
+            for (name, _) in test_vars {
+                std::env::remove_var(name);
+            }
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let result = result.unwrap();
+        assert_eq!(
+            result.imported,
+            vec!["anthropic", "kimi-for-coding", "openai"]
+        );
+        assert_eq!(result.skipped, vec!["OMO_TEST_KEYS_NOT_A_PROVIDER"]);
+
+        let auth = auth.unwrap();
+        let key_of = |id: &str| auth.get(id).and_then(|e| e.key.clone());
+        assert_eq!(key_of("openai").as_deref(), Some("sk-env-openai"));
+        assert_eq!(key_of("anthropic").as_deref(), Some("sk-ant-env"));
+        assert_eq!(key_of("kimi-for-coding").as_deref(), Some("sk-kimi"));
+        assert_eq!(key_of("groq").as_deref(), Some("gsk-keep"));
+        assert!(!auth.contains_key("not-a-provider"));
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_disable_and_enable_provider_keeps_auth() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-disable-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let auth_dir = temp_dir.join(".local").join("share").join("opencode");
+        std::fs::create_dir_all(&auth_dir).unwrap();
+        std::fs::write(
+            auth_dir.join("auth.json"),
+            r#"{"openai":{"type":"api","key":"sk-keep"}}"#,
+        )
+        .unwrap();
+
+        disable_provider("openai".to_string()).unwrap();
+        disable_provider("openai".to_string()).unwrap();
+        assert_eq!(list_disabled_providers(), vec!["openai".to_string()]);
+
+        let providers = get_provider_status("name").unwrap();
+        let openai = providers.iter().find(|p| p.id == "openai").unwrap();
+        assert!(openai.is_disabled);
+        assert!(openai.can_delete_auth);
+
+        enable_provider("openai".to_string()).unwrap();
+        assert!(list_disabled_providers().is_empty());
+        let auth = provider_store::read_auth_file().unwrap();
+        assert_eq!(
+            auth.get("openai").and_then(|e| e.key.as_deref()),
+            Some("sk-keep")
+        );
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_delete_provider_auth_removes_custom_provider_config_only() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-delete-auth-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let auth_dir = temp_dir.join(".local").join("share").join("opencode");
+        std::fs::create_dir_all(&auth_dir).unwrap();
+        std::fs::write(
+            auth_dir.join("auth.json"),
+            r#"{"openai":{"type":"api","key":"sk-a"},"my-proxy":{"type":"api","key":"sk-b"}}"#,
+        )
+        .unwrap();
+        provider_store::write_opencode_config(&json!({
+            "provider": {
+                "openai": { "options": { "baseURL": "https://example.com/v1" } },
+                "my-proxy": { "npm": "@ai-sdk/openai-compatible", "models": { "m1": {} } }
+            }
+        }))
+        .unwrap();
+
+        // 自定义供应商：auth 与 config 一并清理
+        delete_provider_auth("my-proxy".to_string(), true).unwrap();
+        // 内置供应商：即使要求清理也只删除 auth
+        delete_provider_auth("openai".to_string(), true).unwrap();
+
+        let auth = provider_store::read_auth_file().unwrap();
+        let config = provider_store::read_opencode_config().unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(auth.is_empty());
+        assert!(config["provider"].get("my-proxy").is_none());
+        assert!(config["provider"].get("openai").is_some());
+    }
+
+    #[test]
+    #[serial]
+    fn test_add_custom_provider_with_anthropic_npm() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-add-custom-npm-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let info = add_custom_provider(
+            "Claude Gateway".to_string(),
+            "sk-ant-test".to_string(),
+            "https://gateway.example.com/v1".to_string(),
+            Some("@ai-sdk/anthropic".to_string()),
+        )
+        .unwrap();
+        let default_info = add_custom_provider(
+            "Plain Proxy".to_string(),
+            "sk-test".to_string(),
+            "https://proxy.example.com/v1".to_string(),
+            None,
+        )
+        .unwrap();
+        let invalid = add_custom_provider(
+            "Bad".to_string(),
+            "sk-test".to_string(),
+            "https://bad.example.com/v1".to_string(),
+            Some("anthropic".to_string()),
+        );
+
+        let config = provider_store::read_opencode_config().unwrap();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(info.id, "claude-gateway");
+        assert_eq!(info.npm.as_deref(), Some("@ai-sdk/anthropic"));
+        let provider = &config["provider"]["claude-gateway"];
+        assert_eq!(provider["npm"], "@ai-sdk/anthropic");
+        assert_eq!(
+            provider["options"]["baseURL"],
+            "https://gateway.example.com/v1"
+        );
+        assert_eq!(
+            default_info.npm.as_deref(),
+            Some(DEFAULT_CUSTOM_PROVIDER_NPM)
+        );
+        assert_eq!(
+            config["provider"]["plain-proxy"]["npm"],
+            DEFAULT_CUSTOM_PROVIDER_NPM
+        );
+        assert!(invalid.is_err());
+        assert!(config["provider"].get("bad").is_none());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_update_custom_provider_preserves_models() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-update-custom-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join("opencode.json"),
+            r#"{
+              "provider": {
+                "my-proxy": {
+                  "npm": "@ai-sdk/openai-compatible",
+                  "options": { "baseURL": "https://wrong.example.com/v1" },
+                  "models": { "qwen-max": { "name": "Qwen Max" } }
+                }
+              }
+            }"#,
+        )
+        .unwrap();
+
+        let info = update_custom_provider(
+            "my-proxy".to_string(),
+            "My Proxy".to_string(),
+            "sk-new".to_string(),
+            "https://right.example.com/v1".to_string(),
+        )
+        .unwrap();
+        assert_eq!(info.id, "my-proxy");
+
+        let config = provider_store::read_opencode_config().unwrap();
+        let provider = &config["provider"]["my-proxy"];
+        assert_eq!(
+            provider["options"]["baseURL"],
+            "https://right.example.com/v1"
+        );
+        assert_eq!(provider["models"]["qwen-max"]["name"], "Qwen Max");
+        assert_eq!(provider["npm"], "@ai-sdk/openai-compatible");
+
+        let auth = provider_store::read_auth_file().unwrap();
+        assert_eq!(
+            auth.get("my-proxy").and_then(|e| e.key.as_deref()),
+            Some("sk-new")
+        );
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_update_custom_provider_errors_when_missing() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-service-update-missing-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let result = update_custom_provider(
+            "ghost".to_string(),
+            "Ghost".to_string(),
+            "sk-ghost".to_string(),
+            "https://ghost.example.com/v1".to_string(),
+        );
+
+        assert!(result.unwrap_err().contains("ghost"));
+        assert!(!provider_store::get_opencode_config_path().unwrap().exists());
+        assert!(!provider_store::get_auth_file_path().unwrap().exists());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::services::config_service::write_string_atomically;
 
+/// 供应商图标缓存支持的文件扩展名
+const PROVIDER_ICON_EXTENSIONS: [&str; 3] = ["png", "svg", "ico"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthEntry {
     #[serde(rename = "type")]
@@ -69,26 +72,164 @@ pub fn get_opencode_config_path() -> Result<PathBuf, String> {
         .join("opencode.json"))
 }
 
-fn get_omo_cache_dir() -> Result<PathBuf, String> {
+/// OMO 缓存目录的唯一解析入口：~/.cache/oh-my-opencode/
+/// 与 oh-my-opencode CLI 保持一致，所有子系统（模型缓存、快照、图标）都应通过这里获取，
+/// 不使用 dirs::cache_dir()（macOS 上会解析到 ~/Library/Caches）
+pub fn omo_cache_dir() -> Result<PathBuf, String> {
     let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
     Ok(PathBuf::from(home).join(".cache").join("oh-my-opencode"))
 }
 
 pub fn get_provider_models_path() -> Result<PathBuf, String> {
-    Ok(get_omo_cache_dir()?.join("provider-models.json"))
+    Ok(omo_cache_dir()?.join("provider-models.json"))
 }
 
 pub fn get_connected_providers_path() -> Result<PathBuf, String> {
-    Ok(get_omo_cache_dir()?.join("connected-providers.json"))
+    Ok(omo_cache_dir()?.join("connected-providers.json"))
+}
+
+pub fn get_provider_icon_cache_dir() -> Result<PathBuf, String> {
+    Ok(omo_cache_dir()?.join("provider-icons"))
+}
+
+pub fn get_provider_icon_cache_path(provider_id: &str, ext: &str) -> Result<PathBuf, String> {
+    Ok(get_provider_icon_cache_dir()?.join(format!("{}.{}", provider_id, ext)))
+}
+
+/// 内置占位图标的落盘位置，放在图标缓存目录之外，避免被清理与容量统计计入
+pub fn get_provider_icon_placeholder_path() -> Result<PathBuf, String> {
+    Ok(omo_cache_dir()?.join("provider-icon-placeholder.png"))
+}
+
+/// 查找已缓存的供应商图标（png/svg/ico），空文件视为无效并删除，以便重新获取
+pub fn find_cached_provider_icon(provider_id: &str) -> Result<Option<PathBuf>, String> {
+    for ext in PROVIDER_ICON_EXTENSIONS {
+        let path = get_provider_icon_cache_path(provider_id, ext)?;
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.len() > 0 {
+            return Ok(Some(path));
+        }
+        let _ = fs::remove_file(&path);
+    }
+    Ok(None)
+}
+
+/// 列出图标缓存目录中的所有图标文件，目录不存在时返回空列表
+fn list_cached_provider_icons() -> Result<Vec<PathBuf>, String> {
+    let cache_dir = get_provider_icon_cache_dir()?;
+    if !cache_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&cache_dir).map_err(|e| format!("读取图标缓存目录失败: {}", e))?;
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| PROVIDER_ICON_EXTENSIONS.contains(&ext))
+        })
+        .collect())
+}
+
+/// 清空供应商图标缓存，返回删除的文件数量
+/// 图标在下次获取时会重新下载，因此清理是安全的
+pub fn clear_provider_icon_cache() -> Result<usize, String> {
+    let mut removed = 0;
+    for path in list_cached_provider_icons()? {
+        fs::remove_file(&path).map_err(|e| format!("删除图标缓存失败: {}", e))?;
+        removed += 1;
+    }
+    Ok(removed)
 }
 
-pub fn get_provider_icon_cache_path(provider_id: &str) -> Result<PathBuf, String> {
+/// 计算供应商图标缓存占用的总字节数
+pub fn get_provider_icon_cache_size() -> Result<u64, String> {
+    Ok(list_cached_provider_icons()?
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum())
+}
+
+pub fn get_disabled_providers_path() -> Result<PathBuf, String> {
     let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
     Ok(PathBuf::from(home)
-        .join(".cache")
-        .join("oh-my-opencode")
-        .join("provider-icons")
-        .join(format!("{}.png", provider_id)))
+        .join(".config")
+        .join("OMO-Switch")
+        .join("disabled-providers.json"))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DisabledProvidersFile {
+    #[serde(default)]
+    disabled_providers: BTreeSet<String>,
+}
+
+/// 读取被临时禁用的供应商集合，文件缺失或损坏时视为空集合
+pub fn read_disabled_providers() -> BTreeSet<String> {
+    let Ok(path) = get_disabled_providers_path() else {
+        return BTreeSet::new();
+    };
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<DisabledProvidersFile>(&content).ok())
+        .map(|file| file.disabled_providers)
+        .unwrap_or_default()
+}
+
+pub fn write_disabled_providers(disabled: &BTreeSet<String>) -> Result<(), String> {
+    let path = get_disabled_providers_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建设置目录失败: {}", e))?;
+    }
+
+    let payload = DisabledProvidersFile {
+        disabled_providers: disabled.clone(),
+    };
+    let content = serde_json::to_string_pretty(&payload)
+        .map_err(|e| format!("序列化禁用供应商列表失败: {}", e))?;
+    write_string_atomically(&path, &content, "写入禁用供应商列表失败")
+}
+
+pub fn get_recent_models_path() -> Result<PathBuf, String> {
+    Ok(omo_cache_dir()?.join("recent-models.json"))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RecentModelsFile {
+    #[serde(default)]
+    models: Vec<String>,
+}
+
+/// 读取最近使用的模型（最近的在前），文件缺失或损坏时视为空列表
+pub fn read_recent_models() -> Vec<String> {
+    let Ok(path) = get_recent_models_path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<RecentModelsFile>(&content).ok())
+        .map(|file| file.models)
+        .unwrap_or_default()
+}
+
+pub fn write_recent_models(models: &[String]) -> Result<(), String> {
+    let path = get_recent_models_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建缓存目录失败: {}", e))?;
+    }
+    let payload = RecentModelsFile {
+        models: models.to_vec(),
+    };
+    let content = serde_json::to_string_pretty(&payload)
+        .map_err(|e| format!("序列化最近使用模型失败: {}", e))?;
+    write_string_atomically(&path, &content, "写入最近使用模型失败")
 }
 
 pub fn read_auth_file() -> Result<HashMap<String, AuthEntry>, String> {
@@ -112,9 +253,31 @@ pub fn write_auth_file(auth: &HashMap<String, AuthEntry>) -> Result<(), String>
         fs::create_dir_all(parent).map_err(|e| format!("创建认证文件目录失败: {}", e))?;
     }
 
+    // 覆盖前保留一份 .bak，仅在写入并回读校验成功前存在；
+    // 校验通过后删除，避免明文密钥（包括已删除的密钥）长期残留在备份中
+    let backup_path = auth_path.with_extension("json.bak");
+    if auth_path.exists() {
+        if let Err(e) = fs::copy(&auth_path, &backup_path) {
+            eprintln!("警告：备份 auth.json 失败: {}", e);
+        }
+    }
+
     let json_string =
         serde_json::to_string_pretty(auth).map_err(|e| format!("序列化 auth.json 失败: {}", e))?;
-    write_string_atomically(&auth_path, &json_string, "写入 auth.json 失败")
+    write_string_atomically(&auth_path, &json_string, "写入 auth.json 失败")?;
+
+    let written =
+        fs::read_to_string(&auth_path).map_err(|e| format!("回读 auth.json 失败: {}", e))?;
+    if written != json_string {
+        return Err(format!(
+            "auth.json 写入校验失败，原文件已备份至 {}",
+            backup_path.display()
+        ));
+    }
+    if backup_path.exists() {
+        fs::remove_file(&backup_path).map_err(|e| format!("删除 auth.json 备份失败: {}", e))?;
+    }
+    Ok(())
 }
 
 pub fn read_opencode_config() -> Result<Value, String> {
@@ -128,12 +291,40 @@ pub fn read_opencode_config() -> Result<Value, String> {
     serde_json::from_str(&content).map_err(|e| format!("解析 JSON 失败: {}", e))
 }
 
+/// 磁盘上现有的 opencode.json 是否已配置了非空的 provider
+fn existing_config_has_providers(config_path: &Path) -> bool {
+    config_path.exists()
+        && read_opencode_config()
+            .ok()
+            .and_then(|existing| {
+                existing
+                    .get("provider")
+                    .and_then(|p| p.as_object())
+                    .cloned()
+            })
+            .is_some_and(|providers| !providers.is_empty())
+}
+
+/// 写入 opencode.json
+/// 缺少 provider 字段时：若现有文件已配置 provider 则拒绝写入以防止数据丢失，
+/// 否则（全新文件或原本就没有 provider）自动补空的 `provider: {}`
 pub fn write_opencode_config(config: &Value) -> Result<(), String> {
     let config_path = get_opencode_config_path()?;
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("创建配置目录失败: {}", e))?;
     }
 
+    let mut config = config.clone();
+    if config.get("provider").is_none() {
+        if existing_config_has_providers(&config_path) {
+            return Err("配置缺少 provider 字段，拒绝写入以防止数据丢失".to_string());
+        }
+        config
+            .as_object_mut()
+            .ok_or("配置文件根节点必须是对象")?
+            .insert("provider".to_string(), json!({}));
+    }
+
     if config_path.exists() {
         let backup_path = config_path.with_extension("json.bak");
         if let Err(e) = fs::copy(&config_path, &backup_path) {
@@ -141,12 +332,8 @@ pub fn write_opencode_config(config: &Value) -> Result<(), String> {
         }
     }
 
-    if config.get("provider").is_none() {
-        return Err("配置缺少 provider 字段，拒绝写入以防止数据丢失".to_string());
-    }
-
     let json_string =
-        serde_json::to_string_pretty(config).map_err(|e| format!("序列化 JSON 失败: {}", e))?;
+        serde_json::to_string_pretty(&config).map_err(|e| format!("序列化 JSON 失败: {}", e))?;
     write_string_atomically(&config_path, &json_string, "写入配置文件失败")
 }
 
@@ -227,14 +414,8 @@ pub fn read_connected_providers() -> Result<HashSet<String>, String> {
     Ok(cache.connected.into_iter().collect())
 }
 
-pub fn read_provider_models() -> Result<HashMap<String, Vec<String>>, String> {
-    let path = get_provider_models_path()?;
-    if !path.exists() {
-        return Ok(HashMap::new());
-    }
-    let content =
-        fs::read_to_string(&path).map_err(|e| format!("读取 provider-models.json 失败: {}", e))?;
-    let cache: ProviderModelsCache = serde_json::from_str(&content)
+fn parse_provider_models(content: &str) -> Result<HashMap<String, Vec<String>>, String> {
+    let cache: ProviderModelsCache = serde_json::from_str(content)
         .map_err(|e| format!("解析 provider-models.json 失败: {}", e))?;
     Ok(cache
         .models
@@ -258,6 +439,50 @@ pub fn read_provider_models() -> Result<HashMap<String, Vec<String>>, String> {
         .collect())
 }
 
+/// provider-models.json 是否可读且能正确解析（不做任何降级处理）
+pub fn is_provider_models_file_valid(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .is_some_and(|content| parse_provider_models(&content).is_ok())
+}
+
+/// 将损坏的缓存文件重命名为 `*.corrupt` 保留现场，opencode 下次运行时会重建缓存
+/// 返回移动后的路径，移动失败时返回 None（均已记录日志）
+pub fn quarantine_corrupt_cache(path: &Path, reason: &str) -> Option<PathBuf> {
+    let mut corrupt_path = path.as_os_str().to_owned();
+    corrupt_path.push(".corrupt");
+    match fs::rename(path, &corrupt_path) {
+        Ok(()) => {
+            eprintln!(
+                "警告：{:?} 已损坏（{}），已移动到 {:?}",
+                path, reason, corrupt_path
+            );
+            Some(PathBuf::from(corrupt_path))
+        }
+        Err(e) => {
+            eprintln!("警告：{:?} 已损坏（{}），移动失败: {}", path, reason, e);
+            None
+        }
+    }
+}
+
+/// 读取 provider-models.json；文件损坏时移动为 .corrupt 并降级为空结果
+pub fn read_provider_models() -> Result<HashMap<String, Vec<String>>, String> {
+    let path = get_provider_models_path()?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("读取 provider-models.json 失败: {}", e))?;
+    match parse_provider_models(&content) {
+        Ok(models) => Ok(models),
+        Err(err) => {
+            quarantine_corrupt_cache(&path, &err);
+            Ok(HashMap::new())
+        }
+    }
+}
+
 pub fn get_auth_provider_ids() -> Vec<String> {
     read_auth_file()
         .map(|auth| auth.keys().cloned().collect())
@@ -276,6 +501,7 @@ pub fn get_opencode_config_provider_ids() -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// 读取 opencode.json 中各 provider 自定义的模型 ID，跳过 enabled 为 false 的模型
 pub fn get_custom_models() -> HashMap<String, Vec<String>> {
     let mut result = HashMap::new();
     let Ok(config) = read_opencode_config() else {
@@ -291,7 +517,14 @@ pub fn get_custom_models() -> HashMap<String, Vec<String>> {
                 .get("models")
                 .and_then(|models| models.as_object())
             {
-                let model_ids: Vec<String> = models_obj.keys().cloned().collect();
+                // enabled: false 表示用户已禁用该模型，缺省视为启用
+                let model_ids: Vec<String> = models_obj
+                    .iter()
+                    .filter(|(_, model)| {
+                        model.get("enabled").and_then(|enabled| enabled.as_bool()) != Some(false)
+                    })
+                    .map(|(model_id, _)| model_id.clone())
+                    .collect();
                 if !model_ids.is_empty() {
                     result.insert(provider_id.clone(), model_ids);
                 }
@@ -354,6 +587,80 @@ mod tests {
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
 
+    #[test]
+    #[serial]
+    fn test_read_provider_models_quarantines_corrupt_file() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-corrupt-models-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let cache_dir = temp_dir.join(".cache").join("oh-my-opencode");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        let models_path = cache_dir.join("provider-models.json");
+        std::fs::write(&models_path, "{\"models\": {broken").unwrap();
+
+        let models = read_provider_models();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert!(models.unwrap().is_empty());
+        assert!(!models_path.exists());
+        assert_eq!(
+            std::fs::read_to_string(cache_dir.join("provider-models.json.corrupt")).unwrap(),
+            "{\"models\": {broken"
+        );
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_write_and_read_recent_models() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-recent-models-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let empty = read_recent_models();
+        let models = vec![
+            "openai/gpt-5".to_string(),
+            "anthropic/claude-sonnet-4-5".to_string(),
+        ];
+        write_recent_models(&models).unwrap();
+        let recent = read_recent_models();
+        std::fs::write(get_recent_models_path().unwrap(), "{broken").unwrap();
+        let corrupted = read_recent_models();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert!(empty.is_empty());
+        assert_eq!(recent, models);
+        assert!(corrupted.is_empty());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
     #[test]
     #[serial]
     fn test_get_auth_provider_ids_returns_empty_on_invalid_json() {
@@ -427,4 +734,184 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    #[serial]
+    fn test_write_opencode_config_fills_missing_provider_only_when_safe() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-write-config-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        // 全新文件：缺少 provider 时自动补空对象
+        write_opencode_config(&json!({ "model": "openai/gpt-5" })).unwrap();
+        let written = read_opencode_config().unwrap();
+        assert_eq!(written["model"], "openai/gpt-5");
+        assert_eq!(written["provider"], json!({}));
+
+        // 已有 provider 配置时，缺少 provider 的写入仍被拒绝
+        write_opencode_config(&json!({ "provider": { "openai": {} } })).unwrap();
+        assert!(write_opencode_config(&json!({ "model": "openai/gpt-5" })).is_err());
+        assert_eq!(
+            read_opencode_config().unwrap()["provider"],
+            json!({ "openai": {} })
+        );
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_custom_models_skips_disabled_models() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-disabled-models-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join("opencode.json"),
+            r#"{
+              "provider": {
+                "openai": {
+                  "models": {
+                    "gpt-5": {},
+                    "gpt-4.1": { "enabled": false },
+                    "o3": { "enabled": true }
+                  }
+                },
+                "legacy": { "models": { "old-model": { "enabled": false } } }
+              }
+            }"#,
+        )
+        .unwrap();
+
+        let custom_models = get_custom_models();
+
+        assert_eq!(
+            custom_models.get("openai").cloned(),
+            Some(vec!["gpt-5".to_string(), "o3".to_string()])
+        );
+        // 全部模型被禁用的 provider 不出现在结果中
+        assert!(!custom_models.contains_key("legacy"));
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_provider_icon_cache_size_and_clear() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-icon-cache-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        assert_eq!(get_provider_icon_cache_size().unwrap(), 0);
+        assert_eq!(clear_provider_icon_cache().unwrap(), 0);
+
+        let icon_dir = get_provider_icon_cache_dir().unwrap();
+        std::fs::create_dir_all(&icon_dir).unwrap();
+        std::fs::write(icon_dir.join("openai.png"), [0u8; 10]).unwrap();
+        std::fs::write(icon_dir.join("anthropic.png"), [0u8; 5]).unwrap();
+        std::fs::write(icon_dir.join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(get_provider_icon_cache_size().unwrap(), 15);
+        assert_eq!(clear_provider_icon_cache().unwrap(), 2);
+        assert_eq!(get_provider_icon_cache_size().unwrap(), 0);
+        assert!(icon_dir.join("notes.txt").exists());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    #[serial]
+    fn test_write_auth_file_is_atomic_and_removes_backup() {
+        let temp_dir = std::env::temp_dir().join("omo-provider-store-auth-atomic-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let entry = |key: &str| AuthEntry {
+            auth_type: Some("api".to_string()),
+            key: Some(key.to_string()),
+            extra: HashMap::new(),
+        };
+
+        write_auth_file(&HashMap::from([
+            ("openai".to_string(), entry("sk-old")),
+            ("anthropic".to_string(), entry("sk-ant-deleted")),
+        ]))
+        .unwrap();
+        // 删除 anthropic 并更新 openai 的密钥
+        write_auth_file(&HashMap::from([("openai".to_string(), entry("sk-new"))])).unwrap();
+
+        let auth_path = get_auth_file_path().unwrap();
+        let auth_dir = auth_path.parent().unwrap();
+        let leftovers: Vec<_> = std::fs::read_dir(auth_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        let auth = read_auth_file().unwrap();
+        assert_eq!(
+            auth.get("openai").and_then(|e| e.key.as_deref()),
+            Some("sk-new")
+        );
+        assert!(!auth.contains_key("anthropic"));
+        // 写入校验通过后不保留明文备份，已删除的密钥不会残留
+        assert!(!auth_path.with_extension("json.bak").exists());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
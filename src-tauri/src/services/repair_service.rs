@@ -0,0 +1,579 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::i18n;
+use crate::services::{
+    config_service, import_export_service, model_service, preset_service, provider_store,
+};
+
+/// 合法的 variant 取值（"none" 表示移除 variant 字段）
+const VALID_VARIANTS: [&str; 5] = ["low", "medium", "high", "xhigh", "max"];
+/// 原子写入残留的临时文件只有超过该时长才会被清理，避免误删正在写入的文件
+const STALE_TEMP_FILE_SECS: u64 = 60;
+/// oh-my-opencode 历次改名中废弃的 agent 名 → 当前 agent 名（按顺序依次迁移）
+const OLD_TO_NEW_AGENT: [(&str, &str); 8] = [
+    ("OmO", "sisyphus"),
+    ("Sisyphus", "sisyphus"),
+    ("build", "sisyphus"),
+    ("OmO-Plan", "prometheus"),
+    ("Planner-Sisyphus", "prometheus"),
+    ("plan", "prometheus"),
+    ("Sisyphus-Junior", "sisyphus-junior"),
+    ("orchestrator-sisyphus", "atlas"),
+];
+
+/// 一键修复的结果报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RepairReport {
+    /// 实际执行的修复动作（未执行任何修复时为空）
+    pub actions: Vec<String>,
+    /// 修改配置前创建的备份路径
+    pub backup_path: Option<String>,
+}
+
+/// 一次 agent 改名迁移
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AgentRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// 配置迁移结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    /// 已从旧名迁移到新名的 agent
+    pub migrated: Vec<AgentRename>,
+    /// 新名已存在而跳过的旧 agent（旧条目保持原样）
+    pub skipped: Vec<AgentRename>,
+    /// 修改配置前创建的备份路径
+    pub backup_path: Option<String>,
+}
+
+/// 把废弃 agent 名下的配置迁移到新名；新名已存在时保留新值并跳过
+fn apply_agent_renames(config: &mut Value) -> (Vec<AgentRename>, Vec<AgentRename>) {
+    let mut migrated = Vec::new();
+    let mut skipped = Vec::new();
+    let Some(agents) = config.get_mut("agents").and_then(|v| v.as_object_mut()) else {
+        return (migrated, skipped);
+    };
+
+    for (old_name, new_name) in OLD_TO_NEW_AGENT {
+        if !agents.contains_key(old_name) {
+            continue;
+        }
+        let rename = AgentRename {
+            from: old_name.to_string(),
+            to: new_name.to_string(),
+        };
+        if agents.contains_key(new_name) {
+            skipped.push(rename);
+            continue;
+        }
+        if let Some(entry) = agents.remove(old_name) {
+            agents.insert(new_name.to_string(), entry);
+            migrated.push(rename);
+        }
+    }
+    (migrated, skipped)
+}
+
+/// 迁移配置中的废弃 agent 名，有改动时先备份再写入
+pub fn migrate_config() -> Result<MigrationReport, String> {
+    config_service::with_config_lock(|| {
+        let mut config = config_service::read_omo_config()?;
+        let (migrated, skipped) = apply_agent_renames(&mut config);
+        let mut report = MigrationReport {
+            migrated,
+            skipped,
+            backup_path: None,
+        };
+        if report.migrated.is_empty() {
+            return Ok(report);
+        }
+
+        let backup_path =
+            import_export_service::backup_current_config_with_prefix("oh-my-openagent")?;
+        report.backup_path = Some(backup_path.to_string_lossy().to_string());
+        config_service::write_omo_config(&config)?;
+        Ok(report)
+    })
+}
+
+/// 健康检查条目的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// 健康检查发现的单个问题
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthIssue {
+    pub severity: HealthSeverity,
+    /// 问题所在的配置路径，例如 "agents.oracle"；根结构问题为空字符串
+    pub path: String,
+    pub message: String,
+}
+
+/// 配置健康检查汇总（只读，不修改任何文件）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthReport {
+    pub issues: Vec<HealthIssue>,
+}
+
+impl HealthReport {
+    fn push(&mut self, severity: HealthSeverity, path: String, message: String) {
+        self.issues.push(HealthIssue {
+            severity,
+            path,
+            message,
+        });
+    }
+}
+
+/// 根据配置、可用模型与已连接供应商生成健康检查条目
+fn build_health_report(
+    config: &Value,
+    available: &HashMap<String, Vec<String>>,
+    connected: &HashSet<String>,
+) -> HealthReport {
+    let mut report = HealthReport::default();
+    if !config.is_object() {
+        report.push(
+            HealthSeverity::Error,
+            String::new(),
+            i18n::tr_current("config_root_must_be_object"),
+        );
+        return report;
+    }
+
+    for (section, missing_key) in [
+        ("agents", "config_missing_agents"),
+        ("categories", "config_missing_categories"),
+    ] {
+        let entries = match config.get(section) {
+            None => {
+                report.push(
+                    HealthSeverity::Error,
+                    String::new(),
+                    i18n::tr_current(missing_key),
+                );
+                continue;
+            }
+            Some(Value::Object(entries)) => entries,
+            Some(_) => {
+                report.push(
+                    HealthSeverity::Error,
+                    section.to_string(),
+                    format!("'{}' 字段必须是对象", section),
+                );
+                continue;
+            }
+        };
+        for (name, entry) in entries {
+            let path = format!("{}.{}", section, name);
+            let model = match entry.get("model") {
+                None => {
+                    report.push(
+                        HealthSeverity::Info,
+                        path,
+                        "未配置模型，将使用默认模型".to_string(),
+                    );
+                    continue;
+                }
+                Some(Value::String(model)) => model.trim(),
+                Some(_) => {
+                    report.push(
+                        HealthSeverity::Error,
+                        path,
+                        "model 必须是字符串".to_string(),
+                    );
+                    continue;
+                }
+            };
+            if model.is_empty() {
+                report.push(HealthSeverity::Warning, path, "model 为空".to_string());
+                continue;
+            }
+
+            let Some((provider_id, model_id)) = model.split_once('/') else {
+                report.push(
+                    HealthSeverity::Error,
+                    path,
+                    format!("模型 {} 不是 provider/model 格式", model),
+                );
+                continue;
+            };
+            if !connected.contains(provider_id) {
+                report.push(
+                    HealthSeverity::Warning,
+                    path,
+                    format!("模型 {} 所属的供应商 {} 未连接", model, provider_id),
+                );
+                continue;
+            }
+            let is_available = available
+                .get(provider_id)
+                .is_some_and(|models| models.iter().any(|m| m == model_id));
+            if !is_available {
+                report.push(
+                    HealthSeverity::Error,
+                    path,
+                    format!("模型 {} 不在当前可用模型列表中", model),
+                );
+            }
+        }
+    }
+    report
+}
+
+/// 一键检查配置健康状况：根结构、模型是否可用、供应商是否已连接、是否存在空 model
+pub fn config_health_check() -> Result<HealthReport, String> {
+    let config = config_service::read_omo_config()?;
+    let available = model_service::get_available_models()?;
+    let connected: HashSet<String> = model_service::get_connected_providers()?
+        .into_iter()
+        .collect();
+    Ok(build_health_report(&config, &available, &connected))
+}
+
+/// 移除没有配置模型的 agent/category 上残留的 variant，返回清理数量
+fn remove_orphan_variants(config: &mut Value) -> usize {
+    let mut removed = 0;
+    for section in ["agents", "categories"] {
+        let Some(entries) = config.get_mut(section).and_then(|v| v.as_object_mut()) else {
+            continue;
+        };
+        for entry in entries.values_mut() {
+            let Some(obj) = entry.as_object_mut() else {
+                continue;
+            };
+            let has_model = obj
+                .get("model")
+                .and_then(|m| m.as_str())
+                .is_some_and(|m| !m.trim().is_empty());
+            if !has_model && obj.remove("variant").is_some() {
+                removed += 1;
+            }
+        }
+    }
+    removed
+}
+
+/// 统一 variant 的大小写与空白，并移除 "none"/空字符串，返回修改数量
+/// 无法识别的取值保持原样，避免误删上游新增的 variant
+fn normalize_variants(config: &mut Value) -> usize {
+    let mut changed = 0;
+    for section in ["agents", "categories"] {
+        let Some(entries) = config.get_mut(section).and_then(|v| v.as_object_mut()) else {
+            continue;
+        };
+        for entry in entries.values_mut() {
+            let Some(obj) = entry.as_object_mut() else {
+                continue;
+            };
+            let Some(raw) = obj.get("variant").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let normalized = raw.trim().to_lowercase();
+            if normalized.is_empty() || normalized == "none" {
+                obj.remove("variant");
+                changed += 1;
+            } else if normalized != raw && VALID_VARIANTS.contains(&normalized.as_str()) {
+                obj.insert("variant".to_string(), Value::String(normalized));
+                changed += 1;
+            }
+        }
+    }
+    changed
+}
+
+/// 删除目录下超时残留的 *.json.tmp / *.jsonc.tmp 文件，返回删除数量
+fn remove_stale_temp_files(dir: &Path, now: SystemTime) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if !path.is_file() || !(name.ends_with(".json.tmp") || name.ends_with(".jsonc.tmp")) {
+            continue;
+        }
+        let is_stale = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= Duration::from_secs(STALE_TEMP_FILE_SECS));
+        if is_stale && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn get_temp_artifact_dirs() -> Result<Vec<PathBuf>, String> {
+    let home = std::env::var("HOME").map_err(|_| "无法获取 HOME 环境变量".to_string())?;
+    let home = PathBuf::from(home);
+    Ok(vec![
+        home.join(".config").join("opencode"),
+        home.join(".config").join("OMO-Switch"),
+        preset_service::get_presets_dir()?,
+        home.join(".local").join("share").join("opencode"),
+    ])
+}
+
+/// 校验 provider-models.json，损坏时移动为 .corrupt 以便 opencode 重新生成
+fn verify_provider_models_cache() -> Result<Option<String>, String> {
+    let path = provider_store::get_provider_models_path()?;
+    if !path.exists() || provider_store::is_provider_models_file_valid(&path) {
+        return Ok(None);
+    }
+
+    let corrupt_path = provider_store::quarantine_corrupt_cache(&path, "修复时校验失败")
+        .ok_or_else(|| "移动损坏的模型缓存失败".to_string())?;
+    Ok(Some(corrupt_path.to_string_lossy().to_string()))
+}
+
+/// 依次执行各项修复，可重复运行；输入已健康的步骤会被跳过
+pub fn repair_all() -> Result<RepairReport, String> {
+    let mut report = RepairReport::default();
+
+    // 1-3 为读取-修改-写入，整体持有配置锁
+    config_service::with_config_lock(|| {
+        // 1. 配置文件缺失时初始化为空结构
+        if !config_service::get_config_path()?.exists() {
+            config_service::write_omo_config(&json!({ "agents": {}, "categories": {} }))?;
+            report.actions.push("已初始化缺失的配置文件".to_string());
+        }
+
+        // 2/3. 清理孤立 variant 并统一 variant 格式，修改前先备份
+        let original = config_service::read_omo_config()?;
+        let mut config = original.clone();
+        let orphan_count = remove_orphan_variants(&mut config);
+        let normalized_count = normalize_variants(&mut config);
+        if config != original {
+            let backup_path =
+                import_export_service::backup_current_config_with_prefix("oh-my-openagent")?;
+            report.backup_path = Some(backup_path.to_string_lossy().to_string());
+            config_service::write_omo_config(&config)?;
+            if orphan_count > 0 {
+                report
+                    .actions
+                    .push(format!("已清理 {} 个孤立的 variant", orphan_count));
+            }
+            if normalized_count > 0 {
+                report
+                    .actions
+                    .push(format!("已规范化 {} 个 variant", normalized_count));
+            }
+        }
+        Ok(())
+    })?;
+
+    // 4. 迁移旧版内置预设标记
+    let marker_before = preset_service::get_active_preset();
+    let marker_after = preset_service::migrate_active_preset_marker();
+    if marker_before != marker_after {
+        report.actions.push(match marker_after {
+            Some(name) => format!("已将激活预设标记迁移为 {}", name),
+            None => "已清除失效的激活预设标记".to_string(),
+        });
+    }
+
+    // 5. 清理原子写入残留的临时文件
+    let now = SystemTime::now();
+    let temp_removed: usize = get_temp_artifact_dirs()?
+        .iter()
+        .map(|dir| remove_stale_temp_files(dir, now))
+        .sum();
+    if temp_removed > 0 {
+        report
+            .actions
+            .push(format!("已清理 {} 个残留临时文件", temp_removed));
+    }
+
+    // 6. 校验供应商模型缓存
+    if let Some(backup_path) = verify_provider_models_cache()? {
+        report
+            .actions
+            .push(format!("模型缓存已损坏，已移动到 {}", backup_path));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn test_remove_orphan_variants_and_normalize() {
+        let mut config = json!({
+            "agents": {
+                "oracle": { "model": "openai/gpt-5", "variant": " HIGH " },
+                "explore": { "variant": "low" },
+                "librarian": { "model": "openai/gpt-5", "variant": "none" }
+            },
+            "categories": {
+                "quick": { "model": "openai/gpt-5", "variant": "custom" }
+            }
+        });
+
+        assert_eq!(remove_orphan_variants(&mut config), 1);
+        assert_eq!(normalize_variants(&mut config), 2);
+        assert!(config["agents"]["explore"].get("variant").is_none());
+        assert_eq!(config["agents"]["oracle"]["variant"], "high");
+        assert!(config["agents"]["librarian"].get("variant").is_none());
+        assert_eq!(config["categories"]["quick"]["variant"], "custom");
+
+        assert_eq!(remove_orphan_variants(&mut config), 0);
+        assert_eq!(normalize_variants(&mut config), 0);
+    }
+
+    #[test]
+    fn test_health_report_warns_on_disconnected_provider() {
+        let config = json!({
+            "agents": {
+                "oracle": { "model": "openai/gpt-5" },
+                "librarian": { "model": "google/gemini-3-pro" },
+                "explore": { "model": "openai/o9" },
+                "atlas": { "model": "" }
+            },
+            "categories": {
+                "quick": {}
+            }
+        });
+        let available = HashMap::from([
+            ("openai".to_string(), vec!["gpt-5".to_string()]),
+            ("google".to_string(), vec!["gemini-3-pro".to_string()]),
+        ]);
+        let connected = HashSet::from(["openai".to_string()]);
+
+        let report = build_health_report(&config, &available, &connected);
+        let severity_of = |path: &str| {
+            report
+                .issues
+                .iter()
+                .find(|issue| issue.path == path)
+                .map(|issue| issue.severity)
+        };
+        assert_eq!(
+            severity_of("agents.librarian"),
+            Some(HealthSeverity::Warning)
+        );
+        assert_eq!(severity_of("agents.explore"), Some(HealthSeverity::Error));
+        assert_eq!(severity_of("agents.atlas"), Some(HealthSeverity::Warning));
+        assert_eq!(severity_of("categories.quick"), Some(HealthSeverity::Info));
+        assert_eq!(severity_of("agents.oracle"), None);
+        assert_eq!(severity_of(""), None);
+    }
+
+    #[test]
+    fn test_apply_agent_renames_moves_old_name() {
+        let mut config = json!({
+            "agents": {
+                "OmO": { "model": "anthropic/claude-opus-4-5", "variant": "max" },
+                "oracle": { "model": "openai/gpt-5" }
+            }
+        });
+
+        let (migrated, skipped) = apply_agent_renames(&mut config);
+        assert_eq!(
+            migrated,
+            vec![AgentRename {
+                from: "OmO".to_string(),
+                to: "sisyphus".to_string(),
+            }]
+        );
+        assert!(skipped.is_empty());
+        assert!(config["agents"].get("OmO").is_none());
+        assert_eq!(
+            config["agents"]["sisyphus"]["model"],
+            "anthropic/claude-opus-4-5"
+        );
+        assert_eq!(config["agents"]["sisyphus"]["variant"], "max");
+        assert_eq!(config["agents"]["oracle"]["model"], "openai/gpt-5");
+    }
+
+    #[test]
+    fn test_apply_agent_renames_skips_existing_new_name() {
+        let mut config = json!({
+            "agents": {
+                "plan": { "model": "openai/gpt-4o" },
+                "prometheus": { "model": "openai/gpt-5", "variant": "high" }
+            }
+        });
+
+        let (migrated, skipped) = apply_agent_renames(&mut config);
+        assert!(migrated.is_empty());
+        assert_eq!(
+            skipped,
+            vec![AgentRename {
+                from: "plan".to_string(),
+                to: "prometheus".to_string(),
+            }]
+        );
+        assert_eq!(config["agents"]["prometheus"]["model"], "openai/gpt-5");
+        assert_eq!(config["agents"]["prometheus"]["variant"], "high");
+        assert_eq!(config["agents"]["plan"]["model"], "openai/gpt-4o");
+    }
+
+    #[test]
+    #[serial]
+    fn test_repair_all_is_idempotent() {
+        let temp_dir = std::env::temp_dir().join("omo-repair-service-repair-all-test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let config_dir = temp_dir.join(".config").join("opencode");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join("oh-my-openagent.json"),
+            r#"{"agents":{"explore":{"variant":"low"}},"categories":{}}"#,
+        )
+        .unwrap();
+        let cache_dir = temp_dir.join(".cache").join("oh-my-opencode");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::fs::write(cache_dir.join("provider-models.json"), "{broken").unwrap();
+
+        let first = repair_all().unwrap();
+        assert_eq!(first.actions.len(), 2);
+        assert!(first.backup_path.is_some());
+        assert!(cache_dir.join("provider-models.json.corrupt").exists());
+        let config = config_service::read_omo_config().unwrap();
+        assert!(config["agents"]["explore"].get("variant").is_none());
+
+        let second = repair_all().unwrap();
+        assert!(second.actions.is_empty());
+        assert!(second.backup_path.is_none());
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,11 +1,20 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::path::Path;
 use std::process::{Command, Stdio};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::services::config_service;
+use crate::services::provider_store;
 
 const OMO_PLUGIN_NAMES: [&str; 2] = ["oh-my-openagent", "oh-my-opencode"];
 const OMO_PACKAGE_NAMES: [&str; 2] = ["oh-my-openagent", "oh-my-opencode"];
 const OMO_UPDATE_PACKAGE_NAME: &str = "oh-my-opencode";
+/// GitHub repos publishing OpenCode releases, in lookup order (official repo first, then mirror)
+const OPENCODE_RELEASE_REPOS: [&str; 2] = ["sst/opencode", "anomalyco/opencode"];
+/// Latest-version lookups are cached for an hour to avoid hitting npm/GitHub on every check
+const VERSION_CHECK_CACHE_TTL_SECS: u64 = 60 * 60;
+const VERSION_CHECK_CACHE_FILE: &str = "version-check.json";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VersionInfo {
@@ -29,20 +38,80 @@ struct InstallDetection {
     detected_from: String,
 }
 
+/// 无需全局安装即可运行 npm 包的执行器，按 bunx → npx → pnpm dlx 顺序探测
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageRunner {
+    Bunx,
+    Npx,
+    PnpmDlx,
+}
+
+const PACKAGE_RUNNERS: [PackageRunner; 3] = [
+    PackageRunner::Bunx,
+    PackageRunner::Npx,
+    PackageRunner::PnpmDlx,
+];
+
+impl PackageRunner {
+    fn program(self) -> &'static str {
+        match self {
+            PackageRunner::Bunx => "bunx",
+            PackageRunner::Npx => "npx",
+            PackageRunner::PnpmDlx => "pnpm",
+        }
+    }
+
+    /// 探测版本时在包名之前附加的参数，只使用本地已有的包、不触发下载；
+    /// bunx 与 pnpm dlx 总会按需下载包，无法离线探测，返回 None
+    fn probe_args(self) -> Option<&'static [&'static str]> {
+        match self {
+            PackageRunner::Npx => Some(&["--no"]),
+            PackageRunner::Bunx | PackageRunner::PnpmDlx => None,
+        }
+    }
+
+    /// 展示给用户的命令前缀
+    fn command_prefix(self) -> &'static str {
+        match self {
+            PackageRunner::Bunx => "bunx",
+            PackageRunner::Npx => "npx",
+            PackageRunner::PnpmDlx => "pnpm dlx",
+        }
+    }
+
+    fn install_source(self) -> &'static str {
+        match self {
+            PackageRunner::Bunx => "bunx",
+            PackageRunner::Npx => "npx",
+            PackageRunner::PnpmDlx => "pnpm_dlx",
+        }
+    }
+
+    fn from_install_source(source: &str) -> Option<Self> {
+        PACKAGE_RUNNERS
+            .into_iter()
+            .find(|runner| runner.install_source() == source)
+    }
+}
+
 /// Get opencode current version by executing ~/.opencode/bin/opencode --version
 /// 添加 3 秒超时机制，防止命令卡住阻塞 UI
 pub fn get_opencode_version() -> Option<String> {
     let home = std::env::var("HOME").ok()?;
     let bin_path = format!("{}/.opencode/bin/opencode", home);
 
-    let mut child = Command::new(&bin_path)
-        .arg("--version")
+    run_version_command(&bin_path, &["--version"], Duration::from_secs(3))
+}
+
+/// 执行版本命令并返回去除首尾空白的 stdout，超时或失败时返回 None
+fn run_version_command(program: &str, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
         .spawn()
         .ok()?;
 
-    let timeout = Duration::from_secs(3);
     let start = Instant::now();
 
     loop {
@@ -191,9 +260,50 @@ fn detect_omo_install() -> Option<InstallDetection> {
         }
     }
 
+    // 8. 通过包执行器探测已缓存的包（仅 npx --no，不会下载安装）
+    for runner in PACKAGE_RUNNERS {
+        let Some(probe_args) = runner.probe_args() else {
+            continue;
+        };
+        let mut args = probe_args.to_vec();
+        args.extend([OMO_UPDATE_PACKAGE_NAME, "--version"]);
+        let Some(output) = run_version_command(runner.program(), &args, Duration::from_secs(5))
+        else {
+            continue;
+        };
+        if let Some(version) = parse_cli_version_output(&output) {
+            return Some(InstallDetection {
+                version: Some(version),
+                install_source: runner.install_source().to_string(),
+                install_path: runner.command_prefix().to_string(),
+                detected_from: format!(
+                    "{} {} --version",
+                    runner.command_prefix(),
+                    OMO_UPDATE_PACKAGE_NAME
+                ),
+            });
+        }
+    }
+
     None
 }
 
+/// 从 CLI 的 --version 输出中提取版本号（兼容 "oh-my-opencode v3.5.0" 形式）
+fn parse_cli_version_output(output: &str) -> Option<String> {
+    let token = output.lines().last()?.split_whitespace().last()?;
+    let version = token.trim_start_matches('v');
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
+/// 探测本机可用的包执行器（仅检查执行器本身能否运行）
+fn detect_available_runner() -> Option<PackageRunner> {
+    PACKAGE_RUNNERS.into_iter().find(|runner| {
+        run_version_command(runner.program(), &["--version"], Duration::from_secs(3)).is_some()
+    })
+}
+
 fn read_pkg_version(path: &str) -> Option<String> {
     let content = std::fs::read_to_string(path).ok()?;
     let pkg: serde_json::Value = serde_json::from_str(&content).ok()?;
@@ -259,8 +369,8 @@ fn get_npm_global_root() -> Option<String> {
     }
 }
 
-fn is_omo_installed() -> bool {
-    if detect_omo_install().is_some() {
+fn is_omo_installed(detection: Option<&InstallDetection>) -> bool {
+    if detection.is_some() {
         return true;
     }
 
@@ -273,7 +383,22 @@ fn is_omo_installed() -> bool {
         .any(|path| is_plugin_declared_in_config(path, &OMO_PLUGIN_NAMES))
 }
 
-fn build_omo_update_command(install_source: Option<&str>) -> (String, String) {
+/// 根据安装来源生成升级命令；来源未知时优先使用探测到的包执行器
+fn build_omo_update_command(
+    install_source: Option<&str>,
+    available_runner: Option<PackageRunner>,
+) -> (String, String) {
+    if let Some(runner) = install_source.and_then(PackageRunner::from_install_source) {
+        return (
+            format!(
+                "{} {} install",
+                runner.command_prefix(),
+                OMO_UPDATE_PACKAGE_NAME
+            ),
+            format!("通过 {} 重新安装：", runner.command_prefix()),
+        );
+    }
+
     match install_source {
         Some("opencode_runtime") => (
             format!(
@@ -311,13 +436,23 @@ fn build_omo_update_command(install_source: Option<&str>) -> (String, String) {
             ),
             "检测到缓存版本，建议在实际运行目录重新安装：".to_string(),
         ),
-        _ => (
-            format!(
-                "cd ~/.opencode && npm install {}@latest",
-                OMO_UPDATE_PACKAGE_NAME
+        _ => match available_runner {
+            Some(runner) => (
+                format!(
+                    "{} {} install",
+                    runner.command_prefix(),
+                    OMO_UPDATE_PACKAGE_NAME
+                ),
+                format!("检测到 {}，建议通过它安装：", runner.command_prefix()),
             ),
-            "建议在 opencode 运行目录安装/升级：".to_string(),
-        ),
+            None => (
+                format!(
+                    "cd ~/.opencode && npm install {}@latest",
+                    OMO_UPDATE_PACKAGE_NAME
+                ),
+                "建议在 opencode 运行目录安装/升级：".to_string(),
+            ),
+        },
     }
 }
 
@@ -360,34 +495,294 @@ pub fn get_omo_latest_version() -> Option<String> {
     get_npm_latest_version("oh-my-openagent").or_else(|| get_npm_latest_version("oh-my-opencode"))
 }
 
-/// Get OpenCode latest version from GitHub Releases
+/// Get OpenCode latest version from GitHub Releases.
+/// Tries the official repo first and falls back to the mirror only when the repo returns 404.
 pub fn get_opencode_latest_version() -> Option<String> {
-    let resp = ureq::get("https://api.github.com/repos/anomalyco/opencode/releases/latest")
-        .set("User-Agent", "OMO-Switch")
-        .timeout(std::time::Duration::from_secs(3))
-        .call()
-        .ok()?;
-    let json: serde_json::Value = resp.into_json().ok()?;
-    json.get("tag_name")?
-        .as_str()
-        .map(|s| s.trim_start_matches('v').to_string())
+    for repo in OPENCODE_RELEASE_REPOS {
+        let resp = match ureq::get(&opencode_latest_release_url(repo))
+            .set("User-Agent", "OMO-Switch")
+            .timeout(std::time::Duration::from_secs(3))
+            .call()
+        {
+            Ok(resp) => resp,
+            Err(ureq::Error::Status(404, _)) => continue,
+            Err(_) => return None,
+        };
+        let json: serde_json::Value = resp.into_json().ok()?;
+        return json
+            .get("tag_name")?
+            .as_str()
+            .map(|s| s.trim_start_matches('v').to_string());
+    }
+    None
+}
+
+fn opencode_latest_release_url(repo: &str) -> String {
+    format!("https://api.github.com/repos/{}/releases/latest", repo)
+}
+
+/// Parsed semver: numeric core (missing parts treated as 0) plus optional pre-release identifiers
+#[derive(Debug, PartialEq, Eq)]
+struct SemVer {
+    core: [u64; 3],
+    pre: Vec<String>,
 }
 
-/// Simple semver comparison: returns true if latest > current
+impl SemVer {
+    /// Accepts a leading `v`, short versions like `3.5`, and ignores `+build` metadata
+    fn parse(version: &str) -> Self {
+        let version = version.trim();
+        let version = version
+            .strip_prefix('v')
+            .or_else(|| version.strip_prefix('V'))
+            .unwrap_or(version);
+        let version = version.split('+').next().unwrap_or_default();
+        let (core_part, pre_part) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+
+        let mut core = [0u64; 3];
+        for (slot, part) in core.iter_mut().zip(core_part.split('.')) {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            *slot = digits.parse().unwrap_or(0);
+        }
+
+        let pre = pre_part
+            .filter(|p| !p.is_empty())
+            .map(|p| p.split('.').map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Self { core, pre }
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+
+        self.core.cmp(&other.core).then_with(|| {
+            // A pre-release has lower precedence than the matching release
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    for (a, b) in self.pre.iter().zip(other.pre.iter()) {
+                        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                            (Ok(a), Ok(b)) => a.cmp(&b),
+                            (Ok(_), Err(_)) => Ordering::Less,
+                            (Err(_), Ok(_)) => Ordering::Greater,
+                            (Err(_), Err(_)) => a.cmp(b),
+                        };
+                        if ordering != Ordering::Equal {
+                            return ordering;
+                        }
+                    }
+                    self.pre.len().cmp(&other.pre.len())
+                }
+            }
+        })
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Semver comparison: returns true if latest > current
 pub fn has_newer_version(current: &str, latest: &str) -> bool {
-    let parse = |v: &str| -> Vec<u32> { v.split('.').filter_map(|s| s.parse().ok()).collect() };
-    let c = parse(current);
-    let l = parse(latest);
-    l > c
+    SemVer::parse(latest) > SemVer::parse(current)
+}
+
+/// Result of comparing the installed plugin version with the agent config's schema version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigCompatibilityStatus {
+    /// Plugin and config target the same major.minor schema
+    Compatible,
+    /// Plugin is newer than the config schema; the config should be migrated
+    NeedsMigration,
+    /// Config schema is newer than the plugin; the plugin should be upgraded
+    PluginOutdated,
+    /// Either version could not be determined
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigCompatibility {
+    pub status: ConfigCompatibilityStatus,
+    pub plugin_version: Option<String>,
+    pub config_version: Option<String>,
+    pub hint: String,
+}
+
+/// Only strings starting with a digit (after an optional `v`) count as versions; tags like `latest` do not
+fn is_concrete_version(version: &str) -> bool {
+    let version = version.trim();
+    version
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(version)
+        .starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Version recorded in oh-my-opencode.json: an explicit `version` field, otherwise a
+/// version segment in the `$schema` URL (e.g. `.../v3.1.0/assets/...` or `...@3.1.0/...`)
+fn extract_config_schema_version(config: &Value) -> Option<String> {
+    if let Some(version) = config.get("version").and_then(|v| v.as_str()) {
+        return is_concrete_version(version).then(|| version.trim().to_string());
+    }
+
+    let schema = config.get("$schema")?.as_str()?;
+    schema
+        .split(['/', '@'])
+        .find(|segment| is_concrete_version(segment) && segment.contains('.'))
+        .map(|segment| segment.trim_end_matches(".json").to_string())
 }
 
-/// Check all versions
+/// Compares versions by major.minor only; patch releases never change the config schema
+fn compare_config_compatibility(
+    plugin_version: Option<&str>,
+    config_version: Option<&str>,
+) -> ConfigCompatibilityStatus {
+    let (Some(plugin), Some(config)) = (
+        plugin_version.filter(|v| is_concrete_version(v)),
+        config_version.filter(|v| is_concrete_version(v)),
+    ) else {
+        return ConfigCompatibilityStatus::Unknown;
+    };
+
+    let plugin = SemVer::parse(plugin).core;
+    let config = SemVer::parse(config).core;
+    match plugin[..2].cmp(&config[..2]) {
+        std::cmp::Ordering::Equal => ConfigCompatibilityStatus::Compatible,
+        std::cmp::Ordering::Greater => ConfigCompatibilityStatus::NeedsMigration,
+        std::cmp::Ordering::Less => ConfigCompatibilityStatus::PluginOutdated,
+    }
+}
+
+fn compatibility_hint(status: ConfigCompatibilityStatus) -> &'static str {
+    match status {
+        ConfigCompatibilityStatus::Compatible => "配置版本与已安装的插件一致",
+        ConfigCompatibilityStatus::NeedsMigration => {
+            "插件版本高于 Agent 配置版本，请迁移 oh-my-opencode.json"
+        }
+        ConfigCompatibilityStatus::PluginOutdated => {
+            "Agent 配置需要更新的插件版本，请升级 oh-my-opencode 插件"
+        }
+        ConfigCompatibilityStatus::Unknown => "未找到版本信息",
+    }
+}
+
+/// Checks whether the plugin version declared in opencode.json matches the schema/version
+/// recorded in oh-my-opencode.json; missing information yields `Unknown` instead of an error
+pub fn check_config_compatibility() -> ConfigCompatibility {
+    let plugin_version = std::env::var("HOME").ok().and_then(|home| {
+        get_opencode_config_candidates(&home)
+            .iter()
+            .find_map(|path| read_plugin_version_from_config(path, &OMO_PLUGIN_NAMES))
+    });
+    let config_version = config_service::read_omo_config()
+        .ok()
+        .and_then(|config| extract_config_schema_version(&config));
+
+    let status = compare_config_compatibility(plugin_version.as_deref(), config_version.as_deref());
+    ConfigCompatibility {
+        status,
+        plugin_version,
+        config_version,
+        hint: compatibility_hint(status).to_string(),
+    }
+}
+
+/// Latest upstream versions, cached in ~/.cache/oh-my-opencode/version-check.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LatestVersions {
+    /// Unix timestamp (seconds) of the network check
+    checked_at: u64,
+    opencode: Option<String>,
+    omo: Option<String>,
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn fetch_latest_versions() -> LatestVersions {
+    LatestVersions {
+        checked_at: now_unix_secs(),
+        opencode: get_opencode_latest_version(),
+        omo: get_omo_latest_version(),
+    }
+}
+
+/// Return cached latest versions while within the TTL, otherwise call `fetch` and refresh the cache.
+/// Results where every lookup failed are not cached so the next check retries the network.
+fn get_latest_versions_with<F>(
+    cache_path: &Path,
+    ttl_secs: u64,
+    force: bool,
+    fetch: F,
+) -> LatestVersions
+where
+    F: FnOnce() -> LatestVersions,
+{
+    if !force {
+        let cached = std::fs::read_to_string(cache_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<LatestVersions>(&content).ok());
+        if let Some(cached) = cached {
+            if now_unix_secs().saturating_sub(cached.checked_at) < ttl_secs {
+                return cached;
+            }
+        }
+    }
+
+    let latest = fetch();
+    if latest.opencode.is_some() || latest.omo.is_some() {
+        if let Some(parent) = cache_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&latest) {
+            let _ = std::fs::write(cache_path, json);
+        }
+    }
+    latest
+}
+
+fn get_latest_versions(force: bool) -> LatestVersions {
+    match provider_store::omo_cache_dir() {
+        Ok(dir) => get_latest_versions_with(
+            &dir.join(VERSION_CHECK_CACHE_FILE),
+            VERSION_CHECK_CACHE_TTL_SECS,
+            force,
+            fetch_latest_versions,
+        ),
+        Err(_) => fetch_latest_versions(),
+    }
+}
+
+/// Check all versions (latest versions served from a 1-hour local cache)
 pub fn check_all_versions() -> Vec<VersionInfo> {
+    build_version_infos(get_latest_versions(false))
+}
+
+/// Check all versions, bypassing the local cache
+pub fn check_all_versions_force() -> Vec<VersionInfo> {
+    build_version_infos(get_latest_versions(true))
+}
+
+fn build_version_infos(latest: LatestVersions) -> Vec<VersionInfo> {
     let mut results = Vec::new();
 
     // OpenCode
     let oc_current = get_opencode_version();
-    let oc_latest = get_opencode_latest_version();
+    let oc_latest = latest.opencode;
     results.push(VersionInfo {
         name: "OpenCode".to_string(),
         installed: oc_current.is_some(),
@@ -411,16 +806,23 @@ pub fn check_all_versions() -> Vec<VersionInfo> {
     // Oh My OpenAgent
     let omo_detection = detect_omo_install();
     let omo_current = omo_detection.as_ref().and_then(|d| d.version.clone());
-    let omo_latest = get_omo_latest_version();
+    let omo_latest = latest.omo;
     let has_update = match (&omo_current, &omo_latest) {
         (Some(c), Some(l)) => has_newer_version(c, l),
         _ => false,
     };
-    let (update_command, update_hint) =
-        build_omo_update_command(omo_detection.as_ref().map(|d| d.install_source.as_str()));
+    let available_runner = if omo_detection.is_none() {
+        detect_available_runner()
+    } else {
+        None
+    };
+    let (update_command, update_hint) = build_omo_update_command(
+        omo_detection.as_ref().map(|d| d.install_source.as_str()),
+        available_runner,
+    );
     results.push(VersionInfo {
         name: "Oh My OpenAgent".to_string(),
-        installed: is_omo_installed(),
+        installed: is_omo_installed(omo_detection.as_ref()),
         current_version: omo_current.clone(),
         latest_version: omo_latest.clone(),
         has_update,
@@ -438,6 +840,52 @@ pub fn check_all_versions() -> Vec<VersionInfo> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compare_config_compatibility() {
+        use ConfigCompatibilityStatus::*;
+
+        assert_eq!(
+            compare_config_compatibility(Some("3.1.4"), Some("3.1.0")),
+            Compatible
+        );
+        assert_eq!(
+            compare_config_compatibility(Some("v3.2.0"), Some("3.1.9")),
+            NeedsMigration
+        );
+        assert_eq!(
+            compare_config_compatibility(Some("2.9.0"), Some("3.0.0")),
+            PluginOutdated
+        );
+        assert_eq!(
+            compare_config_compatibility(Some("latest"), Some("3.0.0")),
+            Unknown
+        );
+        assert_eq!(compare_config_compatibility(None, Some("3.0.0")), Unknown);
+        assert_eq!(compare_config_compatibility(Some("3.0.0"), None), Unknown);
+    }
+
+    #[test]
+    fn test_extract_config_schema_version() {
+        let explicit = serde_json::json!({ "version": "3.1.0" });
+        assert_eq!(
+            extract_config_schema_version(&explicit).as_deref(),
+            Some("3.1.0")
+        );
+
+        let from_schema = serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/code-yeongyu/oh-my-opencode/v3.2.1/assets/oh-my-opencode.schema.json"
+        });
+        assert_eq!(
+            extract_config_schema_version(&from_schema).as_deref(),
+            Some("v3.2.1")
+        );
+
+        let unversioned = serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/code-yeongyu/oh-my-opencode/master/assets/oh-my-opencode.schema.json"
+        });
+        assert_eq!(extract_config_schema_version(&unversioned), None);
+    }
+
     #[test]
     fn test_has_newer_version() {
         assert!(has_newer_version("3.5.2", "3.5.3"));
@@ -445,4 +893,113 @@ mod tests {
         assert!(!has_newer_version("3.5.3", "3.5.2"));
         assert!(has_newer_version("3.4.0", "3.5.0"));
     }
+
+    #[test]
+    fn test_build_omo_update_command_follows_runner() {
+        assert_eq!(
+            build_omo_update_command(Some("npx"), None).0,
+            "npx oh-my-opencode install"
+        );
+        assert_eq!(
+            build_omo_update_command(Some("pnpm_dlx"), None).0,
+            "pnpm dlx oh-my-opencode install"
+        );
+        assert_eq!(
+            build_omo_update_command(None, Some(PackageRunner::Bunx)).0,
+            "bunx oh-my-opencode install"
+        );
+        assert_eq!(
+            build_omo_update_command(None, Some(PackageRunner::Npx)).0,
+            "npx oh-my-opencode install"
+        );
+        assert_eq!(
+            build_omo_update_command(None, None).0,
+            "cd ~/.opencode && npm install oh-my-opencode@latest"
+        );
+        assert_eq!(
+            build_omo_update_command(Some("npm_global"), Some(PackageRunner::Bunx)).0,
+            "npm install -g oh-my-opencode@latest"
+        );
+    }
+
+    #[test]
+    fn test_package_runner_probe_never_installs() {
+        assert_eq!(PackageRunner::Npx.probe_args(), Some(&["--no"][..]));
+        assert_eq!(PackageRunner::Bunx.probe_args(), None);
+        assert_eq!(PackageRunner::PnpmDlx.probe_args(), None);
+    }
+
+    #[test]
+    fn test_parse_cli_version_output() {
+        assert_eq!(
+            parse_cli_version_output("3.5.0\n").as_deref(),
+            Some("3.5.0")
+        );
+        assert_eq!(
+            parse_cli_version_output("oh-my-opencode v3.5.0").as_deref(),
+            Some("3.5.0")
+        );
+        assert_eq!(parse_cli_version_output("command not found"), None);
+        assert_eq!(parse_cli_version_output(""), None);
+    }
+
+    #[test]
+    fn test_opencode_latest_release_url() {
+        assert_eq!(OPENCODE_RELEASE_REPOS[0], "sst/opencode");
+        assert_eq!(
+            opencode_latest_release_url(OPENCODE_RELEASE_REPOS[0]),
+            "https://api.github.com/repos/sst/opencode/releases/latest"
+        );
+    }
+
+    #[test]
+    fn test_latest_versions_cache_skips_fetch_within_ttl() {
+        let temp_dir = std::env::temp_dir().join("omo_version_check_cache_test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let cache_path = temp_dir.join(VERSION_CHECK_CACHE_FILE);
+        let fetched = std::cell::Cell::new(0);
+        let fetch = || {
+            fetched.set(fetched.get() + 1);
+            LatestVersions {
+                checked_at: now_unix_secs(),
+                opencode: Some("1.0.0".to_string()),
+                omo: Some("3.5.0".to_string()),
+            }
+        };
+
+        let first = get_latest_versions_with(&cache_path, 3600, false, fetch);
+        let second = get_latest_versions_with(&cache_path, 3600, false, fetch);
+        assert_eq!(fetched.get(), 1);
+        assert_eq!(second, first);
+
+        get_latest_versions_with(&cache_path, 3600, true, fetch);
+        assert_eq!(fetched.get(), 2);
+
+        get_latest_versions_with(&cache_path, 0, false, fetch);
+        assert_eq!(fetched.get(), 3);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_has_newer_version_prerelease() {
+        assert!(has_newer_version("3.5.0-beta.1", "3.5.0"));
+        assert!(!has_newer_version("3.5.0", "3.5.0-beta.1"));
+        assert!(has_newer_version("3.5.0-beta.1", "3.5.0-beta.2"));
+        assert!(has_newer_version("3.5.0-beta.2", "3.5.0-beta.10"));
+        assert!(has_newer_version("3.5.0-alpha", "3.5.0-beta"));
+        assert!(has_newer_version("3.5.0-beta", "3.5.0-beta.1"));
+        assert!(has_newer_version("3.5.0-1", "3.5.0-alpha"));
+        assert!(has_newer_version("3.4.9", "3.5.0-beta.1"));
+    }
+
+    #[test]
+    fn test_has_newer_version_uneven_length_and_prefix() {
+        assert!(!has_newer_version("3.5", "3.5.0"));
+        assert!(!has_newer_version("3.5.0", "3.5"));
+        assert!(has_newer_version("3.5", "3.5.1"));
+        assert!(!has_newer_version("v3.5.3", "3.5.3"));
+        assert!(has_newer_version("v3.5.2", "V3.5.3"));
+        assert!(!has_newer_version("3.5.3+build.7", "3.5.3"));
+    }
 }
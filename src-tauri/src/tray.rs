@@ -1,5 +1,7 @@
 use crate::services::{config_service, model_service, preset_service};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use tauri::{
     image::Image,
     menu::{CheckMenuItemBuilder, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
@@ -11,7 +13,22 @@ const TRAY_ID: &str = "omo-tray";
 const ACTION_PREFIX: &str = "set_model";
 const ACTION_OPEN: &str = "open_omo_switch";
 const ACTION_SET_PRESET: &str = "set_preset";
+const ACTION_REFRESH: &str = "refresh_models";
 const ACTION_QUIT: &str = "quit_omo_switch";
+/// 托盘可选的 variant（强度），"none" 表示移除 variant 字段
+const TRAY_VARIANTS: [&str; 6] = ["none", "low", "medium", "high", "xhigh", "max"];
+/// 单个 provider 的模型超过该数量时折叠到“更多…”子菜单
+const TRAY_PROVIDER_MODEL_LIMIT: usize = 15;
+/// 折叠时直接展示的常用/最近使用模型数量
+const TRAY_PINNED_MODEL_COUNT: usize = 5;
+
+/// 托盘中正在编辑的 agent/category 及其当前模型选择
+struct TrayTarget<'a> {
+    /// agent 名称，category 使用 `cat:` 前缀
+    key: &'a str,
+    current_model: &'a str,
+    current_variant: Option<&'a str>,
+}
 
 const AGENT_NAME_ZH_CN: [(&str, &str); 17] = [
     ("sisyphus", "西西弗斯"),
@@ -33,6 +50,66 @@ const AGENT_NAME_ZH_CN: [(&str, &str); 17] = [
     ("document-writer", "文档撰写者"),
 ];
 
+const AGENT_NAME_ZH_TW: [(&str, &str); 17] = [
+    ("sisyphus", "西西弗斯"),
+    ("hephaestus", "赫菲斯托斯"),
+    ("oracle", "神諭者"),
+    ("librarian", "圖書管理員"),
+    ("explore", "探索者"),
+    ("multimodal-looker", "多模態觀察者"),
+    ("prometheus", "普羅米修斯"),
+    ("metis", "墨提斯"),
+    ("momus", "摩摩斯"),
+    ("atlas", "阿特拉斯"),
+    ("build", "建構者"),
+    ("plan", "規劃者"),
+    ("sisyphus-junior", "小西西弗斯"),
+    ("OpenCode-Builder", "OpenCode建構者"),
+    ("general", "通用代理"),
+    ("frontend-ui-ux-engineer", "前端工程師"),
+    ("document-writer", "文件撰寫者"),
+];
+
+const AGENT_NAME_JA: [(&str, &str); 17] = [
+    ("sisyphus", "シーシュポス"),
+    ("hephaestus", "ヘーパイストス"),
+    ("oracle", "神託者"),
+    ("librarian", "司書"),
+    ("explore", "探索者"),
+    ("multimodal-looker", "マルチモーダル観察者"),
+    ("prometheus", "プロメテウス"),
+    ("metis", "メーティス"),
+    ("momus", "モーモス"),
+    ("atlas", "アトラス"),
+    ("build", "ビルダー"),
+    ("plan", "プランナー"),
+    ("sisyphus-junior", "シーシュポス・ジュニア"),
+    ("OpenCode-Builder", "OpenCodeビルダー"),
+    ("general", "汎用エージェント"),
+    ("frontend-ui-ux-engineer", "フロントエンドエンジニア"),
+    ("document-writer", "ドキュメント作成者"),
+];
+
+const AGENT_NAME_KO: [(&str, &str); 17] = [
+    ("sisyphus", "시시포스"),
+    ("hephaestus", "헤파이스토스"),
+    ("oracle", "신탁자"),
+    ("librarian", "사서"),
+    ("explore", "탐색자"),
+    ("multimodal-looker", "멀티모달 관찰자"),
+    ("prometheus", "프로메테우스"),
+    ("metis", "메티스"),
+    ("momus", "모모스"),
+    ("atlas", "아틀라스"),
+    ("build", "빌더"),
+    ("plan", "플래너"),
+    ("sisyphus-junior", "주니어 시시포스"),
+    ("OpenCode-Builder", "OpenCode 빌더"),
+    ("general", "범용 에이전트"),
+    ("frontend-ui-ux-engineer", "프론트엔드 엔지니어"),
+    ("document-writer", "문서 작성자"),
+];
+
 const CATEGORY_NAMES_ZH: [(&str, &str); 5] = [
     ("quick", "快速任务"),
     ("visual-engineering", "视觉工程"),
@@ -41,6 +118,30 @@ const CATEGORY_NAMES_ZH: [(&str, &str); 5] = [
     ("general", "通用"),
 ];
 
+const CATEGORY_NAMES_ZH_TW: [(&str, &str); 5] = [
+    ("quick", "快速任務"),
+    ("visual-engineering", "視覺工程"),
+    ("plan", "規劃"),
+    ("build", "建構"),
+    ("general", "通用"),
+];
+
+const CATEGORY_NAMES_JA: [(&str, &str); 5] = [
+    ("quick", "クイックタスク"),
+    ("visual-engineering", "ビジュアルエンジニアリング"),
+    ("plan", "計画"),
+    ("build", "ビルド"),
+    ("general", "汎用"),
+];
+
+const CATEGORY_NAMES_KO: [(&str, &str); 5] = [
+    ("quick", "빠른 작업"),
+    ("visual-engineering", "비주얼 엔지니어링"),
+    ("plan", "계획"),
+    ("build", "빌드"),
+    ("general", "범용"),
+];
+
 /// 加载 macOS 专用的托盘图标（模板图标）
 /// 模板图标会自动适配深色/浅色模式
 #[cfg(target_os = "macos")]
@@ -87,19 +188,39 @@ pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                 return;
             }
 
+            if id == ACTION_REFRESH {
+                // `opencode models` 可能耗时数秒，放到后台线程避免阻塞菜单事件
+                let app_handle = app_handle.clone();
+                std::thread::spawn(move || {
+                    if let Err(err) = model_service::refresh_verified_available_models() {
+                        eprintln!("托盘刷新模型列表失败: {}", err);
+                    }
+                    if let Err(err) = rebuild_tray_menu(&app_handle) {
+                        eprintln!("托盘菜单刷新失败: {}", err);
+                    }
+                });
+                return;
+            }
+
             if id == ACTION_QUIT {
                 app_handle.exit(0);
                 return;
             }
 
-            let Some((agent, model)) = parse_action_id(id) else {
+            let Some(action) = parse_action_id(id) else {
                 return;
             };
 
-            if let Err(err) = update_agent_model(&agent, &model) {
+            if let Err(err) =
+                update_agent_model(&action.agent, &action.model, action.variant.as_deref())
+            {
                 eprintln!("托盘切换模型失败: {}", err);
                 return;
             }
+            // 使用菜单所属的 provider 记录，模型 ID 可能不带前缀或本身含有 '/'
+            if let Err(err) = model_service::record_recent_model(&action.provider, &action.model) {
+                eprintln!("记录最近使用模型失败: {}", err);
+            }
 
             if let Err(err) = rebuild_tray_menu(app_handle) {
                 eprintln!("托盘菜单刷新失败: {}", err);
@@ -139,7 +260,10 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
 
     let connected_providers = model_service::get_connected_providers().unwrap_or_default();
 
-    let provider_models = model_service::get_available_models().unwrap_or_default();
+    let mut provider_models = model_service::get_available_models().unwrap_or_default();
+    // 被临时禁用的供应商不出现在托盘模型列表中
+    model_service::remove_disabled_providers(&mut provider_models);
+    let recent_models = model_service::get_recent_models();
 
     let empty_agents: serde_json::Map<String, Value> = serde_json::Map::new();
     let agents = config
@@ -147,7 +271,8 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
         .and_then(|v| v.as_object())
         .unwrap_or(&empty_agents);
 
-    let locale = detect_locale();
+    let locale: &str = &tray_locale();
+    let agent_aliases = load_agent_aliases();
     let mut menu_builder = MenuBuilder::new(manager);
 
     // Agents 分组标题
@@ -184,10 +309,11 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
                 .get("model")
                 .and_then(|v| v.as_str())
                 .unwrap_or("未配置");
+            let current_variant = agent_config.get("variant").and_then(|v| v.as_str());
 
             let agent_title = format!(
                 "{} [{}]",
-                build_agent_display_name(agent_name, locale),
+                build_agent_display_name(agent_name, locale, &agent_aliases),
                 short_model_label(current_model)
             );
             let mut agent_submenu = SubmenuBuilder::new(manager, agent_title);
@@ -197,18 +323,19 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
                     continue;
                 };
 
-                let mut provider_submenu = SubmenuBuilder::new(manager, provider);
-                for model in models {
-                    let item_id = build_action_id(agent_name, provider, model);
-                    let is_current = model == current_model;
-
-                    let model_item = CheckMenuItemBuilder::with_id(item_id, model)
-                        .checked(is_current)
-                        .build(manager)?;
-                    provider_submenu = provider_submenu.item(&model_item);
-                }
-
-                let provider_menu = provider_submenu.build()?;
+                let target = TrayTarget {
+                    key: agent_name,
+                    current_model,
+                    current_variant,
+                };
+                let provider_menu = build_provider_submenu(
+                    manager,
+                    &target,
+                    provider,
+                    models,
+                    &recent_models,
+                    locale,
+                )?;
                 agent_submenu = agent_submenu.item(&provider_menu);
             }
 
@@ -242,6 +369,7 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
                 .get("model")
                 .and_then(|v| v.as_str())
                 .unwrap_or("未配置");
+            let current_variant = category_config.get("variant").and_then(|v| v.as_str());
 
             let category_title = format!(
                 "{} [{}]",
@@ -255,19 +383,20 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
                     continue;
                 };
 
-                let mut provider_submenu = SubmenuBuilder::new(manager, provider);
-                for model in models {
-                    let item_id =
-                        build_action_id(&format!("cat:{}", category_name), provider, model);
-                    let is_current = model == current_model;
-
-                    let model_item = CheckMenuItemBuilder::with_id(item_id, model)
-                        .checked(is_current)
-                        .build(manager)?;
-                    provider_submenu = provider_submenu.item(&model_item);
-                }
-
-                let provider_menu = provider_submenu.build()?;
+                let key = format!("cat:{}", category_name);
+                let target = TrayTarget {
+                    key: &key,
+                    current_model,
+                    current_variant,
+                };
+                let provider_menu = build_provider_submenu(
+                    manager,
+                    &target,
+                    provider,
+                    models,
+                    &recent_models,
+                    locale,
+                )?;
                 category_submenu = category_submenu.item(&provider_menu);
             }
 
@@ -280,14 +409,13 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
     menu_builder = menu_builder.separator();
 
     // 预设标题
-    let presets_label = crate::i18n::tr_current("tray_presets");
+    let active_preset = preset_service::get_active_preset();
+    let presets_label = build_presets_header_label(active_preset.as_deref(), locale);
     let presets_header = MenuItemBuilder::with_id("presets_header", presets_label)
         .enabled(false)
         .build(manager)?;
     menu_builder = menu_builder.item(&presets_header);
 
-    let active_preset = preset_service::get_active_preset();
-
     // 用户预设
     let user_presets = preset_service::list_presets().unwrap_or_default();
     for preset_name in &user_presets {
@@ -302,6 +430,20 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
 
         let preset_item = MenuItemBuilder::with_id(item_id, display_name).build(manager)?;
         menu_builder = menu_builder.item(&preset_item);
+
+        // 有描述时在预设下方追加一行不可点击的说明
+        let description = preset_service::get_preset_meta(preset_name)
+            .ok()
+            .and_then(|meta| meta.description);
+        if let Some(description) = description {
+            let description_item = MenuItemBuilder::with_id(
+                format!("preset_description:{}", preset_name),
+                format!("      {}", description),
+            )
+            .enabled(false)
+            .build(manager)?;
+            menu_builder = menu_builder.item(&description_item);
+        }
     }
 
     menu_builder = menu_builder.separator();
@@ -316,6 +458,16 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
         "Open OMO Switch"
     };
 
+    let refresh_label = if locale == "zh-CN" {
+        "重新加载模型列表"
+    } else if locale == "ja" {
+        "モデル一覧を再読み込み"
+    } else if locale == "ko" {
+        "모델 목록 다시 불러오기"
+    } else {
+        "Reload Model List"
+    };
+
     let quit_label = if locale == "zh-CN" {
         "退出"
     } else if locale == "ja" {
@@ -327,15 +479,141 @@ fn build_tray_menu<R: Runtime, M: Manager<R>>(
     };
 
     let open_item = MenuItemBuilder::with_id(ACTION_OPEN, open_label).build(manager)?;
+    let refresh_item = MenuItemBuilder::with_id(ACTION_REFRESH, refresh_label).build(manager)?;
     let quit_item = MenuItemBuilder::with_id(ACTION_QUIT, quit_label).build(manager)?;
 
     menu_builder = menu_builder.item(&open_item);
+    menu_builder = menu_builder.item(&refresh_item);
     menu_builder = menu_builder.item(&quit_item);
 
     Ok(menu_builder.build()?)
 }
 
-fn rebuild_tray_menu<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> Result<(), String> {
+/// 构建 provider 子菜单；模型过多时只展示常用/最近使用的模型，其余收进“更多…”
+fn build_provider_submenu<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    target: &TrayTarget,
+    provider: &str,
+    models: &[String],
+    recent_models: &[String],
+    locale: &str,
+) -> Result<tauri::menu::Submenu<R>, Box<dyn std::error::Error>> {
+    let (pinned, rest) = split_tray_models(
+        provider,
+        models,
+        target.current_model,
+        recent_models,
+        TRAY_PROVIDER_MODEL_LIMIT,
+        TRAY_PINNED_MODEL_COUNT,
+    );
+
+    let mut provider_submenu = SubmenuBuilder::new(manager, provider);
+    for model in pinned {
+        let model_menu = build_model_submenu(manager, target, provider, model)?;
+        provider_submenu = provider_submenu.item(&model_menu);
+    }
+
+    if !rest.is_empty() {
+        let more_label = if locale == "zh-CN" {
+            "更多…"
+        } else if locale == "ja" {
+            "その他…"
+        } else if locale == "ko" {
+            "더 보기…"
+        } else {
+            "More…"
+        };
+        let mut more_submenu = SubmenuBuilder::new(manager, more_label);
+        for model in rest {
+            let model_menu = build_model_submenu(manager, target, provider, model)?;
+            more_submenu = more_submenu.item(&model_menu);
+        }
+        let more_menu = more_submenu.build()?;
+        provider_submenu = provider_submenu.separator().item(&more_menu);
+    }
+
+    Ok(provider_submenu.build()?)
+}
+
+/// 拆分 provider 的模型列表：未超过 limit 时全部直接展示；
+/// 否则依次取当前模型、最近使用模型、列表靠前的模型凑满 pinned_count 个，其余放入“更多…”
+/// 缓存中的模型 ID 可能不带 provider 前缀，比较前统一规范为 `provider/model`
+fn split_tray_models<'a>(
+    provider: &str,
+    models: &'a [String],
+    current_model: &str,
+    recent_models: &[String],
+    limit: usize,
+    pinned_count: usize,
+) -> (Vec<&'a String>, Vec<&'a String>) {
+    if models.len() <= limit {
+        return (models.iter().collect(), Vec::new());
+    }
+
+    let qualified: Vec<String> = models
+        .iter()
+        .map(|model| model_service::qualify_model_id(provider, model))
+        .collect();
+    let preferred = std::iter::once(current_model)
+        .chain(recent_models.iter().map(String::as_str))
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let name = model_service::qualify_model_id(provider, name);
+            qualified
+                .iter()
+                .position(|model| *model == name)
+                .map(|idx| &models[idx])
+        })
+        .chain(models.iter());
+
+    let mut pinned: Vec<&String> = Vec::new();
+    for model in preferred {
+        if pinned.len() >= pinned_count {
+            break;
+        }
+        if !pinned.contains(&model) {
+            pinned.push(model);
+        }
+    }
+
+    let rest = models
+        .iter()
+        .filter(|model| !pinned.contains(model))
+        .collect();
+    (pinned, rest)
+}
+
+/// 构建单个模型的 variant 子菜单，选中任一 variant 时同时写入 model 与 variant
+fn build_model_submenu<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    target: &TrayTarget,
+    provider: &str,
+    model: &str,
+) -> Result<tauri::menu::Submenu<R>, Box<dyn std::error::Error>> {
+    let is_current_model = model == target.current_model;
+    let title = if is_current_model {
+        format!("✓ {}", model)
+    } else {
+        model.to_string()
+    };
+
+    let mut model_submenu = SubmenuBuilder::new(manager, title);
+    for variant in TRAY_VARIANTS {
+        let item_id = build_action_id(target.key, provider, model, Some(variant));
+        let is_current = is_current_model && target.current_variant.unwrap_or("none") == variant;
+
+        let variant_item = CheckMenuItemBuilder::with_id(item_id, variant)
+            .checked(is_current)
+            .build(manager)?;
+        model_submenu = model_submenu.item(&variant_item);
+    }
+
+    Ok(model_submenu.build()?)
+}
+
+pub(crate) fn rebuild_tray_menu<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<(), String> {
     let tray = app_handle
         .tray_by_id(TRAY_ID)
         .ok_or("未找到托盘图标".to_string())?;
@@ -351,75 +629,169 @@ fn open_main_window<R: Runtime>(app_handle: &tauri::AppHandle<R>) {
     }
 }
 
-fn update_agent_model(key: &str, model: &str) -> Result<(), String> {
-    let mut config = config_service::read_omo_config()?;
+/// 更新 agent/category 的模型；variant 为 "none" 时移除字段，为 None 时保持不变
+fn update_agent_model(key: &str, model: &str, variant: Option<&str>) -> Result<(), String> {
+    config_service::with_config_lock(|| {
+        let mut config = config_service::read_omo_config()?;
 
-    let (is_category, name) = if key.starts_with("cat:") {
-        (true, key.strip_prefix("cat:").unwrap())
-    } else {
-        (false, key)
-    };
+        let (is_category, name) = if key.starts_with("cat:") {
+            (true, key.strip_prefix("cat:").unwrap())
+        } else {
+            (false, key)
+        };
 
-    let target = if is_category {
-        config
-            .get_mut("categories")
-            .and_then(|v| v.as_object_mut())
-            .and_then(|cats| cats.get_mut(name))
-    } else {
-        config
-            .get_mut("agents")
+        let target = if is_category {
+            config
+                .get_mut("categories")
+                .and_then(|v| v.as_object_mut())
+                .and_then(|cats| cats.get_mut(name))
+        } else {
+            config
+                .get_mut("agents")
+                .and_then(|v| v.as_object_mut())
+                .and_then(|agents| agents.get_mut(name))
+        };
+
+        let target_obj = target
             .and_then(|v| v.as_object_mut())
-            .and_then(|agents| agents.get_mut(name))
-    };
+            .ok_or(format!("未找到: {}", key))?;
+        target_obj.insert("model".to_string(), Value::String(model.to_string()));
+        match variant {
+            Some("none") => {
+                target_obj.remove("variant");
+            }
+            Some(variant) => {
+                target_obj.insert("variant".to_string(), Value::String(variant.to_string()));
+            }
+            None => {}
+        }
 
-    let target_obj = target
-        .and_then(|v| v.as_object_mut())
-        .ok_or(format!("未找到: {}", key))?;
-    target_obj.insert("model".to_string(), Value::String(model.to_string()));
+        config_service::write_omo_config(&config)
+    })
+}
 
-    config_service::write_omo_config(&config)
+/// 托盘语言与主界面保持一致；无持久化设置时的系统语言推断已由 `i18n::init_locale` 在启动时完成
+fn tray_locale() -> String {
+    crate::i18n::get_locale()
 }
 
-fn detect_locale() -> &'static str {
-    let language = std::env::var("LC_ALL")
-        .or_else(|_| std::env::var("LANG"))
-        .unwrap_or_default()
-        .to_lowercase();
-
-    if language.starts_with("zh") {
-        "zh-CN"
-    } else if language.starts_with("ja") {
-        "ja"
-    } else if language.starts_with("ko") {
-        "ko"
+/// 预设分组标题，附带当前激活预设名；旧版 `__builtin__` 内置预设显示其本地化名称
+fn build_presets_header_label(active_preset: Option<&str>, locale: &str) -> String {
+    let label = crate::i18n::tr("tray_presets", locale);
+    let Some(active) = active_preset else {
+        return label;
+    };
+
+    let display_name = match active.strip_prefix(preset_service::BUILTIN_PRESET_MARKER_PREFIX) {
+        Some(builtin_id) => match builtin_id {
+            "official_default" | "economy" | "high_performance" => {
+                crate::i18n::tr(&format!("preset_{}", builtin_id), locale)
+            }
+            _ => builtin_id.to_string(),
+        },
+        None => active.to_string(),
+    };
+    let current_label = crate::i18n::tr("tray_current_preset", locale);
+
+    if locale.starts_with("zh") {
+        format!("{}（{}：{}）", label, current_label, display_name)
     } else {
-        "en"
+        format!("{} ({}: {})", label, current_label, display_name)
     }
 }
 
-fn build_agent_display_name(agent_name: &str, locale: &str) -> String {
-    let english_name = format_agent_english_name(agent_name);
+/// 应用内已收录显示名的 agent 列表
+pub(crate) fn app_agent_names() -> Vec<&'static str> {
+    AGENT_NAME_ZH_CN.iter().map(|(name, _)| *name).collect()
+}
 
-    if locale == "en" {
-        return english_name;
+/// 按 locale 选择 agent 译名表，无对应表时返回空表（仅显示英文）
+fn agent_name_table(locale: &str) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        "zh-CN" => &AGENT_NAME_ZH_CN,
+        "zh-TW" => &AGENT_NAME_ZH_TW,
+        "ja" => &AGENT_NAME_JA,
+        "ko" => &AGENT_NAME_KO,
+        _ => &[],
     }
+}
 
-    let localized_name = AGENT_NAME_ZH_CN
+/// 按 locale 选择分类译名表，无对应表时返回空表（仅显示英文 id）
+fn category_name_table(locale: &str) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        "zh-CN" => &CATEGORY_NAMES_ZH,
+        "zh-TW" => &CATEGORY_NAMES_ZH_TW,
+        "ja" => &CATEGORY_NAMES_JA,
+        "ko" => &CATEGORY_NAMES_KO,
+        _ => &[],
+    }
+}
+
+fn lookup_localized_name(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table
         .iter()
-        .find(|(name, _)| *name == agent_name)
+        .find(|(name, _)| *name == key)
         .map(|(_, localized)| *localized)
-        .unwrap_or(agent_name);
+}
 
-    format!("{} · {}", english_name, localized_name)
+/// 用户自定义 agent 别名文件: ~/.config/OMO-Switch/agent-aliases.json
+fn get_agent_aliases_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("OMO-Switch")
+            .join("agent-aliases.json"),
+    )
 }
 
-fn build_category_display_name(name: &str, locale: &str) -> String {
-    if locale == "zh-CN" {
-        if let Some((_, cn)) = CATEGORY_NAMES_ZH.iter().find(|(en, _)| *en == name) {
-            return format!("{} · {}", name, cn);
+/// 读取 agent_id → 别名映射，文件不存在或格式错误时返回空映射
+fn load_agent_aliases() -> HashMap<String, String> {
+    let Some(path) = get_agent_aliases_path() else {
+        return HashMap::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return HashMap::new();
+    };
+
+    match serde_json::from_str::<serde_json::Map<String, Value>>(&content) {
+        Ok(map) => map
+            .into_iter()
+            .filter_map(|(agent, alias)| {
+                let alias = alias.as_str()?.trim();
+                (!alias.is_empty()).then(|| (agent, alias.to_string()))
+            })
+            .collect(),
+        Err(e) => {
+            eprintln!("解析 agent 别名文件 {:?} 失败，已忽略: {}", path, e);
+            HashMap::new()
         }
     }
-    name.to_string()
+}
+
+/// agent 显示名：英文名 · 用户别名，无别名时使用当前 locale 的译名，均缺失时仅显示英文名
+fn build_agent_display_name(
+    agent_name: &str,
+    locale: &str,
+    aliases: &HashMap<String, String>,
+) -> String {
+    let english_name = format_agent_english_name(agent_name);
+
+    let display_name = aliases
+        .get(agent_name)
+        .map(String::as_str)
+        .or_else(|| lookup_localized_name(agent_name_table(locale), agent_name));
+    match display_name {
+        Some(name) => format!("{} · {}", english_name, name),
+        None => english_name,
+    }
+}
+
+fn build_category_display_name(name: &str, locale: &str) -> String {
+    match lookup_localized_name(category_name_table(locale), name) {
+        Some(localized_name) => format!("{} · {}", name, localized_name),
+        None => name.to_string(),
+    }
 }
 
 fn format_agent_english_name(agent_name: &str) -> String {
@@ -448,17 +820,32 @@ fn short_model_label(model: &str) -> &str {
     model.rsplit('/').next().unwrap_or(model)
 }
 
-fn build_action_id(agent: &str, provider: &str, model: &str) -> String {
-    format!(
+/// 编码托盘动作 id：前缀:agent:provider:model[:variant]，各段使用十六进制避免分隔符冲突
+fn build_action_id(agent: &str, provider: &str, model: &str, variant: Option<&str>) -> String {
+    let mut id = format!(
         "{}:{}:{}:{}",
         ACTION_PREFIX,
         hex_encode(agent),
         hex_encode(provider),
         hex_encode(model)
-    )
+    );
+    if let Some(variant) = variant {
+        id.push(':');
+        id.push_str(&hex_encode(variant));
+    }
+    id
 }
 
-fn parse_action_id(id: &str) -> Option<(String, String)> {
+/// 托盘模型菜单项携带的切换动作
+#[derive(Debug, PartialEq, Eq)]
+struct TrayModelAction {
+    agent: String,
+    provider: String,
+    model: String,
+    variant: Option<String>,
+}
+
+fn parse_action_id(id: &str) -> Option<TrayModelAction> {
     let mut parts = id.split(':');
     let prefix = parts.next()?;
     if prefix != ACTION_PREFIX {
@@ -466,10 +853,19 @@ fn parse_action_id(id: &str) -> Option<(String, String)> {
     }
 
     let agent = hex_decode(parts.next()?)?;
-    let _provider = hex_decode(parts.next()?)?;
+    let provider = hex_decode(parts.next()?)?;
     let model = hex_decode(parts.next()?)?;
+    let variant = match parts.next() {
+        Some(part) => Some(hex_decode(part)?),
+        None => None,
+    };
 
-    Some((agent, model))
+    Some(TrayModelAction {
+        agent,
+        provider,
+        model,
+        variant,
+    })
 }
 
 fn hex_encode(input: &str) -> String {
@@ -507,18 +903,255 @@ mod tests {
 
     #[test]
     fn test_action_id_roundtrip() {
-        let id = build_action_id("sisyphus", "aicodewith", "aicodewith/gpt-5.3-codex");
+        let id = build_action_id("sisyphus", "aicodewith", "aicodewith/gpt-5.3-codex", None);
+        let parsed = parse_action_id(&id).unwrap();
+        assert_eq!(parsed.agent, "sisyphus");
+        assert_eq!(parsed.provider, "aicodewith");
+        assert_eq!(parsed.model, "aicodewith/gpt-5.3-codex");
+        assert_eq!(parsed.variant, None);
+    }
+
+    #[test]
+    fn test_action_id_keeps_provider_for_bare_model_ids() {
+        // 自定义模型与已验证模型列表中的 ID 不带 provider 前缀
+        let bare =
+            parse_action_id(&build_action_id("oracle", "my-gateway", "gpt-5", None)).unwrap();
+        assert_eq!(bare.provider, "my-gateway");
+        assert_eq!(
+            model_service::qualify_model_id(&bare.provider, &bare.model),
+            "my-gateway/gpt-5"
+        );
+
+        // openrouter 风格的 ID 本身含有 '/'，不能按第一个 '/' 拆分 provider
+        let routed = parse_action_id(&build_action_id(
+            "oracle",
+            "openrouter",
+            "anthropic/claude-x",
+            None,
+        ))
+        .unwrap();
+        assert_eq!(
+            model_service::qualify_model_id(&routed.provider, &routed.model),
+            "openrouter/anthropic/claude-x"
+        );
+    }
+
+    #[test]
+    fn test_action_id_roundtrip_with_variant() {
+        let id = build_action_id("cat:quick", "openai", "openai/gpt-5.3-codex", Some("xhigh"));
         let parsed = parse_action_id(&id).unwrap();
-        assert_eq!(parsed.0, "sisyphus");
-        assert_eq!(parsed.1, "aicodewith/gpt-5.3-codex");
+        assert_eq!(parsed.agent, "cat:quick");
+        assert_eq!(parsed.model, "openai/gpt-5.3-codex");
+        assert_eq!(parsed.variant.as_deref(), Some("xhigh"));
+
+        let none_id = build_action_id("oracle", "openai", "openai/gpt-5", Some("none"));
+        assert_eq!(
+            parse_action_id(&none_id).unwrap().variant.as_deref(),
+            Some("none")
+        );
+
+        let plain_id = build_action_id("oracle", "openai", "openai/gpt-5", None);
+        assert!(parse_action_id(&format!("{}:zz", plain_id)).is_none());
     }
 
     #[test]
     fn test_agent_display_name_in_non_english_locale() {
-        let title = build_agent_display_name("sisyphus", "zh-CN");
+        let title = build_agent_display_name("sisyphus", "zh-CN", &HashMap::new());
         assert_eq!(title, "Sisyphus · 西西弗斯");
     }
 
+    #[test]
+    fn test_display_names_in_zh_tw_locale() {
+        // 繁体中文使用独立译名表，不显示简体
+        assert_eq!(
+            build_agent_display_name("librarian", "zh-TW", &HashMap::new()),
+            "Librarian · 圖書管理員"
+        );
+        assert_eq!(
+            build_category_display_name("quick", "zh-TW"),
+            "quick · 快速任務"
+        );
+    }
+
+    #[test]
+    fn test_display_names_in_ja_ko_locale() {
+        assert_eq!(
+            build_agent_display_name("sisyphus", "ja", &HashMap::new()),
+            "Sisyphus · シーシュポス"
+        );
+        assert_eq!(
+            build_agent_display_name("oracle", "ko", &HashMap::new()),
+            "Oracle · 신탁자"
+        );
+        assert_eq!(
+            build_category_display_name("quick", "ja"),
+            "quick · クイックタスク"
+        );
+        assert_eq!(
+            build_category_display_name("visual-engineering", "ko"),
+            "visual-engineering · 비주얼 엔지니어링"
+        );
+
+        // 表中缺失或 locale 无译名表时回退英文
+        assert_eq!(
+            build_agent_display_name("custom-agent", "ja", &HashMap::new()),
+            "Custom Agent"
+        );
+        assert_eq!(
+            build_agent_display_name("sisyphus", "fr", &HashMap::new()),
+            "Sisyphus"
+        );
+        assert_eq!(build_category_display_name("writing", "ko"), "writing");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_agent_alias_overrides_display_name() {
+        let temp_dir = std::env::temp_dir().join("omo_tray_agent_alias_test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let config_dir = temp_dir.join(".config").join("OMO-Switch");
+        std::fs::create_dir_all(&config_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        let without_file = load_agent_aliases();
+        std::fs::write(
+            config_dir.join("agent-aliases.json"),
+            r#"{"sisyphus": "老西", "oracle": "  ", "metis": 1}"#,
+        )
+        .unwrap();
+        let aliases = load_agent_aliases();
+
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert!(without_file.is_empty());
+        assert_eq!(
+            build_agent_display_name("sisyphus", "zh-CN", &without_file),
+            "Sisyphus · 西西弗斯"
+        );
+        assert_eq!(
+            build_agent_display_name("sisyphus", "zh-CN", &aliases),
+            "Sisyphus · 老西"
+        );
+        assert_eq!(
+            build_agent_display_name("sisyphus", "en", &aliases),
+            "Sisyphus · 老西"
+        );
+        // 空白或非字符串别名被忽略，回退到本地化名
+        assert_eq!(
+            build_agent_display_name("oracle", "zh-CN", &aliases),
+            "Oracle · 神谕者"
+        );
+        assert_eq!(build_agent_display_name("metis", "en", &aliases), "Metis");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_presets_header_label() {
+        assert_eq!(build_presets_header_label(None, "en"), "💾 Presets");
+        assert_eq!(
+            build_presets_header_label(Some("work"), "en"),
+            "💾 Presets (Current: work)"
+        );
+        assert_eq!(
+            build_presets_header_label(Some("__builtin__economy"), "zh-CN"),
+            "💾 预设（当前预设：经济模式）"
+        );
+        assert_eq!(
+            build_presets_header_label(Some("__builtin__custom"), "en"),
+            "💾 Presets (Current: custom)"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_tray_locale_follows_i18n_locale() {
+        let temp_dir = std::env::temp_dir().join("omo_tray_locale_test");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        let original_locale = crate::i18n::get_locale();
+        unsafe {
+            std::env::set_var("HOME", &temp_dir);
+        }
+
+        crate::i18n::set_locale("en").unwrap();
+        let en_label = build_presets_header_label(None, &tray_locale());
+        crate::i18n::set_locale("zh-CN").unwrap();
+        let zh_label = build_presets_header_label(None, &tray_locale());
+
+        let _ = crate::i18n::set_locale(&original_locale);
+        unsafe {
+            if let Some(home) = original_home {
+                std::env::set_var("HOME", home);
+            } else {
+                std::env::remove_var("HOME");
+            }
+        }
+
+        assert_eq!(en_label, "💾 Presets");
+        assert_eq!(zh_label, "💾 预设");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_split_tray_models() {
+        let models: Vec<String> = (0..20).map(|i| format!("openai/model-{}", i)).collect();
+
+        let (pinned, rest) = split_tray_models("openai", &models[..3], "", &[], 15, 5);
+        assert_eq!(pinned.len(), 3);
+        assert!(rest.is_empty());
+
+        let recent = vec![
+            "anthropic/claude".to_string(),
+            "openai/model-7".to_string(),
+            "openai/model-12".to_string(),
+        ];
+        let (pinned, rest) =
+            split_tray_models("openai", &models, "openai/model-12", &recent, 15, 5);
+        let pinned: Vec<&str> = pinned.iter().map(|m| m.as_str()).collect();
+        assert_eq!(
+            pinned,
+            vec![
+                "openai/model-12",
+                "openai/model-7",
+                "openai/model-0",
+                "openai/model-1",
+                "openai/model-2"
+            ]
+        );
+        assert_eq!(rest.len(), 15);
+        assert!(!rest.iter().any(|m| m.as_str() == "openai/model-12"));
+    }
+
+    #[test]
+    fn test_split_tray_models_matches_bare_cache_ids() {
+        // provider-models.json 中的模型 ID 通常不带 provider 前缀，而最近使用记录总是带前缀
+        let models: Vec<String> = (0..20).map(|i| format!("model-{}", i)).collect();
+        let recent = vec![
+            "openai/model-9".to_string(),
+            "anthropic/model-3".to_string(),
+        ];
+
+        let (pinned, rest) =
+            split_tray_models("openai", &models, "openai/model-15", &recent, 15, 3);
+        let pinned: Vec<&str> = pinned.iter().map(|m| m.as_str()).collect();
+        assert_eq!(pinned, vec!["model-15", "model-9", "model-0"]);
+        assert_eq!(rest.len(), 17);
+    }
+
     #[test]
     fn test_short_model_label() {
         assert_eq!(
@@ -6,6 +6,7 @@ import { Modal } from '../common/Modal';
 import {
   exportOmoConfig,
   importOmoConfig,
+  importOmoConfigMerge,
   validateImport,
   getImportExportHistory,
   restoreBackup,
@@ -14,8 +15,11 @@ import {
   clearBackupHistory,
   getBackupHistoryLimit,
   setBackupHistoryLimit,
+  getBackupCompression,
+  setBackupCompression,
   BackupInfo,
   OmoConfig,
+  type RestoreBackupError,
 } from '../../services/tauri';
 import { open, save } from '@tauri-apps/plugin-dialog';
 import { usePreloadStore } from '../../store/preloadStore';
@@ -33,7 +37,9 @@ export function ImportExportPanel() {
   const [previewConfig, setPreviewConfig] = useState<OmoConfig | null>(null);
   const [importPath, setImportPath] = useState<string | null>(null);
   const [recordExportHistory, setRecordExportHistory] = useState(false);
+  const [redactExport, setRedactExport] = useState(false);
   const [maxHistoryLimit, setMaxHistoryLimitState] = useState(10);
+  const [compressBackups, setCompressBackups] = useState(false);
   const [maxHistoryInput, setMaxHistoryInput] = useState('10');
 
   useEffect(() => {
@@ -42,13 +48,15 @@ export function ImportExportPanel() {
       void (async () => {
         try {
           setHistoryLoading(true);
-          const [data, limit] = await Promise.all([
+          const [data, limit, compress] = await Promise.all([
             getImportExportHistory(),
             getBackupHistoryLimit(),
+            getBackupCompression(),
           ]);
           if (cancelled) return;
           setHistory(data);
           setMaxHistoryLimitState(limit);
+          setCompressBackups(compress);
           setMaxHistoryInput(String(limit));
         } catch (err) {
           if (cancelled) return;
@@ -94,7 +102,7 @@ export function ImportExportPanel() {
         return;
       }
 
-      await exportOmoConfig(filePath, recordExportHistory);
+      await exportOmoConfig(filePath, recordExportHistory, redactExport);
       setSuccess(t('importExport.exportSuccess', { path: filePath }));
       await loadHistory();
     } catch (err) {
@@ -131,7 +139,7 @@ export function ImportExportPanel() {
     }
   };
 
-  const handleConfirmImport = async () => {
+  const handleConfirmImport = async (merge = false) => {
     if (!importPath) return;
 
     try {
@@ -140,7 +148,11 @@ export function ImportExportPanel() {
       setSuccess(null);
       setPreviewModal(false);
 
-      await importOmoConfig(importPath);
+      if (merge) {
+        await importOmoConfigMerge(importPath);
+      } else {
+        await importOmoConfig(importPath);
+      }
       setSuccess(t('importExport.importSuccess'));
       await loadHistory();
       await loadOmoConfig().catch(() => {
@@ -186,7 +198,13 @@ export function ImportExportPanel() {
         // 恢复成功后尽力刷新 UI，刷新失败不影响恢复结果
       });
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      const restoreError = err as Partial<RestoreBackupError> | null;
+      if (restoreError && typeof restoreError.message === 'string') {
+        setError(restoreError.message);
+        await loadHistory();
+      } else {
+        setError(err instanceof Error ? err.message : String(err));
+      }
     } finally {
       setActionLoading(false);
     }
@@ -273,6 +291,17 @@ export function ImportExportPanel() {
     }
   };
 
+  const handleToggleCompression = async (enabled: boolean) => {
+    try {
+      setActionLoading(true);
+      setCompressBackups(await setBackupCompression(enabled));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
   const commitHistoryLimit = async () => {
     const parsed = Number.parseInt(maxHistoryInput, 10);
     const safeValue = Number.isFinite(parsed) ? parsed : maxHistoryLimit;
@@ -373,6 +402,17 @@ export function ImportExportPanel() {
             />
             {t('importExport.recordExportHistory', { defaultValue: '导出同时记录到备份历史' })}
           </label>
+
+          <label className="flex items-center gap-2 mt-2 text-sm text-slate-600">
+            <input
+              type="checkbox"
+              checked={redactExport}
+              onChange={(e) => setRedactExport(e.target.checked)}
+              disabled={actionLoading}
+              className="rounded border-slate-300"
+            />
+            {t('importExport.redactExport', { defaultValue: '导出时隐藏 baseURL、apiKey 等敏感字段' })}
+          </label>
         </div>
       </div>
 
@@ -413,6 +453,16 @@ export function ImportExportPanel() {
             <span className="text-xs text-slate-500">
               {t('importExport.itemsSuffix', { defaultValue: '条' })}
             </span>
+            <label className="flex items-center gap-1 text-xs text-slate-500">
+              <input
+                type="checkbox"
+                checked={compressBackups}
+                onChange={(e) => void handleToggleCompression(e.target.checked)}
+                disabled={actionLoading}
+                className="rounded border-slate-300"
+              />
+              {t('importExport.compressBackups', { defaultValue: '压缩备份' })}
+            </label>
             <Button
               variant="ghost"
               size="sm"
@@ -460,7 +510,9 @@ export function ImportExportPanel() {
                     <span className="text-xs px-2 py-0.5 rounded bg-slate-200 text-slate-600">
                       {backup.operation === 'export'
                         ? t('importExport.operationExport', { defaultValue: '导出快照' })
-                        : t('importExport.operationImport', { defaultValue: '导入备份' })}
+                        : backup.operation === 'preset'
+                          ? t('importExport.operationPreset', { defaultValue: '应用预设前备份' })
+                          : t('importExport.operationImport', { defaultValue: '导入备份' })}
                     </span>
                   </div>
                 </div>
@@ -543,10 +595,18 @@ export function ImportExportPanel() {
             >
               {t('importExport.cancel')}
             </Button>
+            <Button
+              variant="secondary"
+              className="flex-1"
+              onClick={() => handleConfirmImport(true)}
+              disabled={actionLoading}
+            >
+              {t('importExport.mergeImport')}
+            </Button>
             <Button
               variant="primary"
               className="flex-1"
-              onClick={handleConfirmImport}
+              onClick={() => handleConfirmImport()}
               disabled={actionLoading}
             >
               {actionLoading ? t('importExport.importing') : t('importExport.confirmImport')}
@@ -328,11 +328,27 @@ export function ApplyModelModal({
       const currentEditablePreset = activePreset || null;
 
       if (currentEditablePreset && targetPreset === currentEditablePreset) {
-        await updateAgentsBatch(updates);
+        const { skipped } = await updateAgentsBatch(updates);
+        if (skipped.length > 0) {
+          toast.warning(
+            t('applyModel.skipped', {
+              names: skipped.join(', '),
+              defaultValue: `以下项目在配置中不存在，已跳过: ${skipped.join(', ')}`,
+            })
+          );
+        }
         await saveConfigSnapshot();
         await updatePreset(targetPreset);
       } else {
-        await applyUpdatesToPreset(targetPreset, updates);
+        const rejected = await applyUpdatesToPreset(targetPreset, updates);
+        if (rejected.length > 0) {
+          toast.warning(
+            t('applyModel.rejected', {
+              details: rejected.join('; '),
+              defaultValue: `以下项目的模型格式无效，已跳过: ${rejected.join('; ')}`,
+            })
+          );
+        }
       }
 
       toast.success(
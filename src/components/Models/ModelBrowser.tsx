@@ -14,7 +14,8 @@ import { cn } from '../common/cn';
 import { SearchInput } from '../common/SearchInput';
 import { Select } from '../common/Select';
 import {
-  ModelInfo
+  ModelInfo,
+  invalidateModelsCache,
 } from '../../services/tauri';
 import { usePreloadStore } from '../../store/preloadStore';
 
@@ -360,6 +361,12 @@ export function ModelBrowser({
   const models = usePreloadStore(s => s.models);
   const refreshModels = usePreloadStore(s => s.refreshModels);
 
+  // 手动刷新时先让后端 `opencode models` 缓存失效，确保拿到最新结果
+  const handleRefreshModels = useCallback(async () => {
+    await invalidateModelsCache().catch(() => undefined);
+    await refreshModels();
+  }, [refreshModels]);
+
   const [searchQuery, setSearchQuery] = useState('');
   const [selectedProvider, setSelectedProvider] = useState<string>('all');
   const [expandedProviders, setExpandedProviders] = useState<Set<string>>(new Set());
@@ -527,7 +534,7 @@ export function ModelBrowser({
             {models.error}
           </p>
           <button
-            onClick={() => refreshModels()}
+            onClick={() => handleRefreshModels()}
             className="px-4 py-2 bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium rounded-lg transition-colors"
           >
             {t('modelBrowser.retry')}
@@ -545,7 +552,7 @@ export function ModelBrowser({
             {t('modelBrowser.noDataHint')}
           </p>
           <button
-            onClick={() => refreshModels()}
+            onClick={() => handleRefreshModels()}
             className="px-4 py-2 bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium rounded-lg transition-colors"
           >
             {t('modelBrowser.retry')}
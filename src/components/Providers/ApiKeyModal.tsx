@@ -71,13 +71,16 @@ export function ApiKeyModal({ provider, onClose, onSuccess }: ApiKeyModalProps)
 
     setIsLoading(true);
     try {
-      await setProviderApiKey(
+      const result = await setProviderApiKey(
         provider.id,
         apiKey.trim(),
         provider.supports_base_url ? (baseUrl.trim() || null) : null,
         providerType,
       );
       toast.success(t('provider.saveSuccess'));
+      if (result.warning) {
+        toast.warning(result.warning);
+      }
       onSuccess();
       onClose();
     } catch (err) {
@@ -1,10 +1,16 @@
 import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { X, Plus, Globe, Key, User, Loader2, CheckCircle, AlertCircle } from 'lucide-react';
+import { X, Plus, Globe, Key, User, Loader2, CheckCircle, AlertCircle, Boxes, ChevronDown } from 'lucide-react';
 import { Button } from '../common/Button';
 import { toast } from '../common/Toast';
 import { addCustomProvider, testProviderConnection } from '../../services/tauri';
 
+const CUSTOM_PROVIDER_TYPE_OPTIONS = [
+  { value: '@ai-sdk/openai-compatible', labelKey: 'provider.typeOptions.openaiCompatible' },
+  { value: '@ai-sdk/anthropic', labelKey: 'provider.typeOptions.anthropic' },
+  { value: '@ai-sdk/google', labelKey: 'provider.typeOptions.google' },
+] as const;
+
 interface CustomProviderModalProps {
   onClose: () => void;
   onSuccess: () => void;
@@ -15,6 +21,7 @@ export function CustomProviderModal({ onClose, onSuccess }: CustomProviderModalP
   const [name, setName] = useState('');
   const [apiKey, setApiKey] = useState('');
   const [baseUrl, setBaseUrl] = useState('');
+  const [providerType, setProviderType] = useState<string>(CUSTOM_PROVIDER_TYPE_OPTIONS[0].value);
   const [isLoading, setIsLoading] = useState(false);
   const [isTesting, setIsTesting] = useState(false);
   const [testStatus, setTestStatus] = useState<'idle' | 'success' | 'error'>('idle');
@@ -35,7 +42,7 @@ export function CustomProviderModal({ onClose, onSuccess }: CustomProviderModalP
 
     setIsLoading(true);
     try {
-      await addCustomProvider(name.trim(), apiKey.trim(), baseUrl.trim());
+      await addCustomProvider(name.trim(), apiKey.trim(), baseUrl.trim(), providerType);
       toast.success(t('provider.addCustomSuccess'));
       onSuccess();
       onClose();
@@ -60,7 +67,7 @@ export function CustomProviderModal({ onClose, onSuccess }: CustomProviderModalP
     setIsTesting(true);
     setTestStatus('idle');
     try {
-      const result = await testProviderConnection('', baseUrl.trim(), apiKey.trim());
+      const result = await testProviderConnection(providerType, baseUrl.trim(), apiKey.trim());
       if (result.success) {
         setTestStatus('success');
         toast.success(t('provider.testSuccess'));
@@ -160,6 +167,33 @@ export function CustomProviderModal({ onClose, onSuccess }: CustomProviderModalP
             </p>
           </div>
 
+          <div>
+            <label className="block text-sm font-medium text-slate-700 mb-2">
+              <Boxes className="w-4 h-4 inline mr-1" />
+              {t('provider.type')}
+            </label>
+            <div className="relative">
+              <select
+                value={providerType}
+                onChange={(e) => {
+                  setProviderType(e.target.value);
+                  setTestStatus('idle');
+                }}
+                className="w-full px-4 py-2.5 pr-11 bg-slate-50 border border-slate-200 rounded-xl
+                         text-slate-700 appearance-none cursor-pointer
+                         focus:outline-none focus:ring-2 focus:ring-indigo-500/20 focus:border-indigo-500
+                         transition-all duration-200"
+              >
+                {CUSTOM_PROVIDER_TYPE_OPTIONS.map((option) => (
+                  <option key={option.value} value={option.value}>
+                    {t(option.labelKey)}
+                  </option>
+                ))}
+              </select>
+              <ChevronDown className="w-4 h-4 text-slate-400 absolute right-4 top-1/2 -translate-y-1/2 pointer-events-none" />
+            </div>
+          </div>
+
           {testStatus === 'success' && (
             <div className="flex items-center gap-2 p-3 bg-emerald-50 text-emerald-700 rounded-lg text-sm">
               <CheckCircle className="w-4 h-4" />
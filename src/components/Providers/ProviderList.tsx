@@ -163,6 +163,7 @@ export interface ProviderInfo {
   supports_base_url: boolean;
   supports_connection_test: boolean;
   can_delete_auth: boolean;
+  is_disabled: boolean;
 }
 
 export interface ProviderPreset {
@@ -5,6 +5,7 @@
  * - 检测 OMO 配置与缓存快照之间的差异
  * - 提供 500ms 防抖，避免频繁检测
  * - 检测前自动确保快照存在（通过 ensure_snapshot_exists）
+ * - 收到后端 omo://config-changed 事件时自动重新检测
  * 
  * 使用示例：
  * ```tsx
@@ -14,7 +15,12 @@
  * ```
  */
 import { useState, useCallback, useRef, useEffect } from 'react';
-import { compareWithSnapshot, ensureSnapshotExists, saveConfigSnapshot } from '../services/tauri';
+import {
+  compareWithSnapshot,
+  ensureSnapshotExists,
+  onConfigChanged,
+  saveConfigSnapshot,
+} from '../services/tauri';
 
 export interface ConfigChange {
   path: string;
@@ -82,6 +88,31 @@ export function useConfigChangeDetection(): UseConfigChangeDetectionReturn {
     });
   }, [performCheck]);
 
+  // 配置文件被外部修改时由后端推送事件，无需轮询修改时间
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+    onConfigChanged(() => {
+      void checkChanges();
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch((err) => {
+        if (import.meta.env.DEV) {
+          console.error('[ConfigChangeDetection] 订阅配置变更事件失败:', err);
+        }
+      });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [checkChanges]);
+
   const ignoreChanges = useCallback(async () => {
     setLoading(true);
     setError(null);
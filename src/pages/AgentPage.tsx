@@ -3,7 +3,12 @@ import { useTranslation } from 'react-i18next';
 import { Bot, RefreshCw, ChevronDown, AlertCircle } from 'lucide-react';
 import { useShallow } from 'zustand/react/shallow';
 import { usePreloadStore } from '../store/preloadStore';
-import { getOmoConfig, mergeAndSave, acceptExternalChanges } from '../services/tauri';
+import {
+  getOmoConfig,
+  mergeAndSave,
+  acceptExternalChanges,
+  type ExternalChangeError,
+} from '../services/tauri';
 import { AgentList } from '../components/AgentList';
 import { PresetSelector } from '../components/Presets';
 import { ConfigChangeAlert } from '../components/ConfigChangeAlert';
@@ -107,7 +112,25 @@ export function AgentPage() {
 
   // 接受外部变更：以磁盘配置为准，原子同步快照和当前预设，并立即刷新 UI
   const handleAcceptChanges = useCallback(async () => {
-    const result = await acceptExternalChanges();
+    let result;
+    try {
+      result = await acceptExternalChanges();
+    } catch (error) {
+      const failure = error as ExternalChangeError;
+      toast.error(
+        failure.recoverable && failure.backup_path
+          ? t('configChange.acceptFailedRestoreBackup', {
+              message: failure.message,
+              path: failure.backup_path,
+              defaultValue: `外部配置无效：${failure.message}。建议从最近备份恢复：${failure.backup_path}`,
+            })
+          : t('configChange.acceptFailedEditManually', {
+              message: failure.message,
+              defaultValue: `外部配置无效：${failure.message}。没有可用备份，请手动修正配置文件`,
+            })
+      );
+      return;
+    }
 
     usePreloadStore.setState((state) => ({
       omoConfig: {
@@ -70,7 +70,8 @@ export function ProviderPage() {
   const handleDeleteConfirm = async () => {
     if (!deleteConfirm) return;
     try {
-      await deleteProviderAuth(deleteConfirm.id);
+      // 自定义供应商连同 opencode.json 中的配置块一起删除，避免残留无 key 的条目
+      await deleteProviderAuth(deleteConfirm.id, !deleteConfirm.is_builtin);
       toast.success(t('provider.deleteSuccess'));
       await loadData({ silent: configLoaded });
       await refreshModels();
@@ -103,7 +103,7 @@ export function SettingsPage() {
   }, []);
 
   const handleRefreshVersions = () => {
-    refreshVersions();
+    refreshVersions(true);
   };
 
   // 处理语言切换
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type { AgentVariant } from '../utils/modelCapabilities';
 
 export interface AgentConfig {
@@ -51,6 +52,14 @@ export interface ModelInfo {
   name?: string;
   description?: string;
   pricing?: ModelPricing;
+  context_limit?: number | null;
+  modalities?: string[] | null;
+  supports_tools?: boolean | null;
+}
+
+export interface ModelInfoWithCurrency extends ModelInfo {
+  converted_pricing?: ModelPricing | null;
+  currency_converted: boolean;
 }
 
 export interface OllamaModel {
@@ -115,8 +124,17 @@ export async function pullModel(modelName: string): Promise<void> {
   return invoke<void>('pull_model', { modelName });
 }
 
-export async function getAvailableModels(): Promise<Record<string, string[]>> {
-  return invoke<Record<string, string[]>>('get_available_models');
+export async function getAvailableModels(
+  excludeDisabled = false
+): Promise<Record<string, string[]>> {
+  return invoke<Record<string, string[]>>('get_available_models', { excludeDisabled });
+}
+
+/**
+ * 按子串（不区分大小写）搜索模型 ID，只返回有命中的供应商分组
+ */
+export async function searchModels(query: string): Promise<Record<string, string[]>> {
+  return invoke<Record<string, string[]>>('search_models', { query });
 }
 
 export async function getVerifiedAvailableModels(): Promise<Record<string, string[]>> {
@@ -134,10 +152,38 @@ export async function getAvailableModelsWithStatus(): Promise<AvailableModelsWit
   return invoke<AvailableModelsWithStatus>('get_available_models_with_status');
 }
 
+export async function invalidateModelsCache(): Promise<void> {
+  return invoke<void>('invalidate_models_cache');
+}
+
+export async function getRecentModels(): Promise<string[]> {
+  return invoke<string[]>('get_recent_models');
+}
+
 export async function getConnectedProviders(): Promise<string[]> {
   return invoke<string[]>('get_connected_providers');
 }
 
+export async function getProviderModelCounts(): Promise<Record<string, number>> {
+  return invoke<Record<string, number>>('get_provider_model_counts');
+}
+
+export interface ModelsChunkEvent {
+  providerId: string;
+  index: number;
+  models: string[];
+}
+
+export interface ModelsDoneEvent {
+  providerId: string;
+  total: number;
+}
+
+/** 分块推送模型列表：通过 omo://models-chunk / omo://models-done 事件接收 */
+export async function streamProviderModels(providerId: string): Promise<number> {
+  return invoke<number>('stream_provider_models', { providerId });
+}
+
 /**
  * 添加自定义模型到指定提供商
  * @param providerId 提供商ID
@@ -164,18 +210,59 @@ export async function getCustomModels(): Promise<Record<string, string[]>> {
   return invoke<Record<string, string[]>>('get_custom_models');
 }
 
+export async function refreshAllCustomProviderModels(): Promise<Record<string, string[]>> {
+  return invoke<Record<string, string[]>>('refresh_all_custom_provider_models');
+}
+
 export async function fetchModelsDev(): Promise<ModelInfo[]> {
   return invoke<ModelInfo[]>('fetch_models_dev');
 }
 
+/**
+ * 只获取指定供应商的 models.dev 模型信息（按 id 的 provider 前缀过滤）
+ */
+export async function fetchModelsDevFor(providers: string[]): Promise<ModelInfo[]> {
+  return invoke<ModelInfo[]>('fetch_models_dev_for', { providers });
+}
+
+/**
+ * 获取 models.dev 模型信息，并按 ~/.config/OMO-Switch/fx-rates.json 换算为目标货币
+ * 无汇率的模型 currency_converted 为 false，仅保留原始 pricing
+ */
+export async function fetchModelsDevWithCurrency(target: string): Promise<ModelInfoWithCurrency[]> {
+  return invoke<ModelInfoWithCurrency[]>('fetch_models_dev_with_currency', { target });
+}
+
+export async function refreshModelsDev(): Promise<ModelInfo[]> {
+  return invoke<ModelInfo[]>('refresh_models_dev');
+}
+
 export async function getOmoConfig(): Promise<OmoConfig> {
   return invoke<OmoConfig>('read_omo_config');
 }
 
+/**
+ * 配置文件不存在时初始化一份最小配置
+ * 返回是否新建了配置文件
+ */
+export async function ensureOmoConfigExists(): Promise<boolean> {
+  return invoke<boolean>('ensure_omo_config_exists');
+}
+
 export async function getOmoCacheDir(): Promise<string> {
   return invoke<string>('get_omo_cache_dir');
 }
 
+export interface ResolvedCachePath {
+  subsystem: string;
+  path: string;
+  exists: boolean;
+}
+
+export async function getResolvedCacheDirs(): Promise<ResolvedCachePath[]> {
+  return invoke<ResolvedCachePath[]>('get_resolved_cache_dirs');
+}
+
 export async function updateAgentModel(
   agentName: string,
   model: string,
@@ -184,10 +271,54 @@ export async function updateAgentModel(
   return invoke<OmoConfig>('update_agent_model', { agentName, model, variant });
 }
 
+export interface BatchUpdateResult {
+  config: OmoConfig;
+  applied: string[];
+  skipped: string[];
+}
+
+/**
+ * 批量更新 agent/category 模型
+ * skipped 为在 agents 与 categories 中都不存在的名称
+ */
 export async function updateAgentsBatch(
   updates: AgentUpdateRequest[]
-): Promise<OmoConfig> {
-  return invoke<OmoConfig>('update_agents_batch', { updates });
+): Promise<BatchUpdateResult> {
+  return invoke<BatchUpdateResult>('update_agents_batch', { updates });
+}
+
+export async function setCategoryVariants(variant: AgentVariant): Promise<number> {
+  return invoke<number>('set_category_variants', { variant });
+}
+
+export interface ProviderRemapResult {
+  changed: string[];
+  skipped: string[];
+}
+
+/**
+ * 将使用 fromProvider 的 agent/category 一键切换到 toProvider 的等价模型
+ * modelMap 的键为源模型 ID，值为目标模型 ID（均不含 provider 前缀）
+ */
+export async function remapProvider(
+  fromProvider: string,
+  toProvider: string,
+  modelMap: Record<string, string>
+): Promise<ProviderRemapResult> {
+  return invoke<ProviderRemapResult>('remap_provider', { fromProvider, toProvider, modelMap });
+}
+
+export async function findAgentsSharingModel(): Promise<Record<string, string[]>> {
+  return invoke<Record<string, string[]>>('find_agents_sharing_model');
+}
+
+export interface AgentSourceReconciliation {
+  only_in_config: string[];
+  only_in_app_table: string[];
+}
+
+export async function reconcileAgentSources(): Promise<AgentSourceReconciliation> {
+  return invoke<AgentSourceReconciliation>('reconcile_agent_sources');
 }
 
 export interface PresetInfo {
@@ -221,10 +352,72 @@ export async function renamePreset(oldName: string, newName: string): Promise<vo
   return invoke<void>('rename_preset', { oldName, newName });
 }
 
+export async function exportPreset(name: string, targetPath: string): Promise<void> {
+  return invoke<void>('export_preset', { name, targetPath });
+}
+
+export async function importPreset(sourcePath: string, newName: string): Promise<void> {
+  return invoke<void>('import_preset', { sourcePath, newName });
+}
+
+export async function duplicatePreset(source: string, newName: string): Promise<void> {
+  return invoke<void>('duplicate_preset', { source, newName });
+}
+
+export async function diffPresets(a: string, b: string): Promise<ConfigChange[]> {
+  return invoke<ConfigChange[]>('diff_presets', { a, b });
+}
+
+export async function diffPresetWithCurrent(name: string): Promise<ConfigChange[]> {
+  return invoke<ConfigChange[]>('diff_preset_with_current', { name });
+}
+
+export async function previewApplyPreset(name: string): Promise<ConfigChange[]> {
+  return invoke<ConfigChange[]>('preview_apply_preset', { name });
+}
+
+export interface AgentCostEstimate {
+  agent: string;
+  model: string | null;
+  monthly_min: number | null;
+  monthly_max: number | null;
+  currency: string | null;
+  /** false 表示缺少定价，成本未知 */
+  pricing_known: boolean;
+}
+
+export interface CurrencyCostTotal {
+  currency: string;
+  total_min: number;
+  total_max: number;
+}
+
+export interface PresetCostEstimate {
+  agents: AgentCostEstimate[];
+  /** 不同币种分别合计 */
+  totals: CurrencyCostTotal[];
+  unknown_agents: string[];
+  assumed_tokens_per_day: number;
+}
+
+/**
+ * 按每个 agent 的日均 token 用量粗估预设的月成本区间
+ */
+export async function estimatePresetCost(
+  name: string,
+  assumedTokensPerDay: number
+): Promise<PresetCostEstimate> {
+  return invoke<PresetCostEstimate>('estimate_preset_cost', { name, assumedTokensPerDay });
+}
+
 export async function getPresetInfo(name: string): Promise<[number, number, string]> {
   return invoke<[number, number, string]>('get_preset_info', { name });
 }
 
+export async function findPresetsUsingModel(model: string): Promise<string[]> {
+  return invoke<string[]>('find_presets_using_model', { model });
+}
+
 /**
  * 获取预设元数据（创建时间、更新时间、版本）
  */
@@ -232,21 +425,35 @@ export interface PresetMeta {
   created_at: number;
   updated_at: number;
   version: number;
+  description?: string;
+  source?: 'manual' | 'builtin' | 'imported';
 }
 
 export async function getPresetMeta(name: string): Promise<PresetMeta> {
   return invoke<PresetMeta>('get_preset_meta', { name });
 }
 
+export async function setPresetDescription(name: string, description?: string): Promise<void> {
+  return invoke<void>('set_preset_description', { name, description });
+}
+
 export async function updatePreset(name: string): Promise<void> {
   return invoke<void>('update_preset', { name });
 }
 
+export async function listPresetHistory(name: string): Promise<number[]> {
+  return invoke<number[]>('list_preset_history', { name });
+}
+
+export async function restorePresetVersion(name: string, ts: number): Promise<void> {
+  return invoke<void>('restore_preset_version', { name, ts });
+}
+
 export async function applyUpdatesToPreset(
   name: string,
   updates: AgentUpdateRequest[]
-): Promise<void> {
-  return invoke<void>('apply_updates_to_preset', { name, updates });
+): Promise<string[]> {
+  return invoke<string[]>('apply_updates_to_preset', { name, updates });
 }
 
 export async function setActivePreset(name: string): Promise<void> {
@@ -257,6 +464,10 @@ export async function getActivePreset(): Promise<string | null> {
   return invoke<string | null>('get_active_preset');
 }
 
+export async function clearActivePreset(): Promise<void> {
+  return invoke<void>('clear_active_preset');
+}
+
 export interface BackupInfo {
   filename: string;
   path: string;
@@ -264,6 +475,7 @@ export interface BackupInfo {
   created_at_ts: number;
   size: number;
   operation: string;
+  note?: string | null;
 }
 
 export interface ConfigMetadata {
@@ -287,6 +499,12 @@ export interface ProviderInfo {
   supports_base_url: boolean;
   supports_connection_test: boolean;
   can_delete_auth: boolean;
+  is_disabled: boolean;
+}
+
+export interface ApiKeySaveResult {
+  saved: boolean;
+  warning: string | null;
 }
 
 export interface ProviderConfigSnapshot {
@@ -308,14 +526,68 @@ export interface ConfigChange {
   new_value: unknown;
 }
 
-export async function exportOmoConfig(path: string, recordHistory = false): Promise<void> {
-  return invoke<void>('export_omo_config', { path, recordHistory });
+export interface ConfigSnapshot {
+  cached_at: number;
+  config: Record<string, unknown>;
+}
+
+export async function exportOmoConfig(
+  path: string,
+  recordHistory = false,
+  redact = false
+): Promise<void> {
+  return invoke<void>('export_omo_config', { path, recordHistory, redact });
+}
+
+export async function exportMinimalConfig(stripExtra = false): Promise<OmoConfig> {
+  return invoke<OmoConfig>('export_minimal_config', { stripExtra });
+}
+
+export async function exportConfigRedacted(): Promise<OmoConfig> {
+  return invoke<OmoConfig>('export_config_redacted');
+}
+
+export type ConfigReportFormat = 'markdown';
+
+/**
+ * 导出可读配置报告（每个 agent/category 的模型、variant 与 provider 连接状态）
+ */
+export async function exportConfigReport(
+  path: string,
+  format: ConfigReportFormat = 'markdown'
+): Promise<void> {
+  return invoke<void>('export_config_report', { path, format });
+}
+
+export interface AppBundleImportResult {
+  locale: string;
+  presets: number;
+  restored_secrets: boolean;
+}
+
+/**
+ * 导出应用迁移包（zip：配置、预设、导入导出设置、语言）
+ * auth.json 含敏感 key，仅在 includeSecrets 为 true 时打包
+ */
+export async function exportAppBundle(path: string, includeSecrets = false): Promise<void> {
+  return invoke<void>('export_app_bundle', { path, includeSecrets });
+}
+
+/**
+ * 校验并还原应用迁移包，返回包内记录的语言以便前端切换
+ */
+export async function importAppBundle(path: string): Promise<AppBundleImportResult> {
+  return invoke<AppBundleImportResult>('import_app_bundle', { path });
 }
 
 export async function importOmoConfig(path: string): Promise<void> {
   return invoke<void>('import_omo_config', { path });
 }
 
+export async function importOmoConfigMerge(path: string): Promise<void> {
+  return invoke<void>('import_omo_config_merge', { path });
+}
+
 export async function validateImport(path: string): Promise<OmoConfig> {
   return invoke<OmoConfig>('validate_import', { path });
 }
@@ -324,6 +596,28 @@ export async function getImportExportHistory(): Promise<BackupInfo[]> {
   return invoke<BackupInfo[]>('get_import_export_history');
 }
 
+/**
+ * 按操作类型与创建时间区间（毫秒，含边界）筛选历史记录
+ */
+export async function getImportExportHistoryFiltered(
+  operation?: BackupInfo['operation'],
+  sinceTs?: number,
+  untilTs?: number
+): Promise<BackupInfo[]> {
+  return invoke<BackupInfo[]>('get_import_export_history_filtered', {
+    operation,
+    sinceTs,
+    untilTs,
+  });
+}
+
+/** 恢复备份失败时后端返回的结构化错误 */
+export interface RestoreBackupError {
+  step: 'locate' | 'read' | 'parse' | 'validate' | 'backup' | 'write';
+  message: string;
+  rolledBack: boolean;
+}
+
 export async function restoreBackup(path: string): Promise<void> {
   return invoke<void>('restore_backup', { path });
 }
@@ -332,6 +626,13 @@ export async function deleteBackup(path: string): Promise<void> {
   return invoke<void>('delete_backup', { path });
 }
 
+/**
+ * 为备份设置备注，传空字符串清除
+ */
+export async function setBackupNote(path: string, note: string): Promise<void> {
+  return invoke<void>('set_backup_note', { path, note });
+}
+
 export async function exportBackup(path: string, targetPath: string): Promise<void> {
   return invoke<void>('export_backup', { path, targetPath });
 }
@@ -340,6 +641,13 @@ export async function clearBackupHistory(): Promise<number> {
   return invoke<number>('clear_backup_history');
 }
 
+/**
+ * 清理旧备份，仅保留最近 keep 条（keep 为 0 时全部清空），返回删除数量
+ */
+export async function clearBackupHistoryKeeping(keep: number): Promise<number> {
+  return invoke<number>('clear_backup_history_keeping', { keep });
+}
+
 export async function getBackupHistoryLimit(): Promise<number> {
   return invoke<number>('get_backup_history_limit');
 }
@@ -348,6 +656,14 @@ export async function setBackupHistoryLimit(limit: number): Promise<number> {
   return invoke<number>('set_backup_history_limit', { limit });
 }
 
+export async function getBackupCompression(): Promise<boolean> {
+  return invoke<boolean>('get_backup_compression');
+}
+
+export async function setBackupCompression(enabled: boolean): Promise<boolean> {
+  return invoke<boolean>('set_backup_compression', { enabled });
+}
+
 export async function getConfigPath(): Promise<string> {
   return invoke<string>('get_config_path');
 }
@@ -360,8 +676,10 @@ export async function validateConfig(config: OmoConfig): Promise<void> {
   return invoke<void>('validate_config', { config });
 }
 
-export async function getProviderStatus(): Promise<ProviderInfo[]> {
-  return invoke<ProviderInfo[]>('get_provider_status');
+export type ProviderSortMode = 'name' | 'configured_first';
+
+export async function getProviderStatus(sort: ProviderSortMode = 'name'): Promise<ProviderInfo[]> {
+  return invoke<ProviderInfo[]>('get_provider_status', { sort });
 }
 
 export async function getProviderConfig(providerId: string): Promise<ProviderConfigSnapshot> {
@@ -373,34 +691,120 @@ export async function setProviderApiKey(
   apiKey: string,
   baseUrl: string | null,
   providerType?: string
+): Promise<ApiKeySaveResult> {
+  return invoke<ApiKeySaveResult>('set_provider_api_key', {
+    providerId,
+    apiKey,
+    baseUrl,
+    providerType,
+  });
+}
+
+export interface EnvKeyImportResult {
+  imported: string[];
+  skipped: string[];
+}
+
+/**
+ * 从 `{prefix}_OPENAI`、`{prefix}_ANTHROPIC` 等环境变量批量导入 API Key
+ */
+export async function importKeysFromEnv(prefix: string): Promise<EnvKeyImportResult> {
+  return invoke<EnvKeyImportResult>('import_keys_from_env', { prefix });
+}
+
+/**
+ * 删除供应商凭据
+ * removeFromConfig 为 true 时同时移除 opencode.json 中的自定义供应商配置（内置供应商不受影响）
+ */
+export async function deleteProviderAuth(
+  providerId: string,
+  removeFromConfig = false
 ): Promise<void> {
-  return invoke<void>('set_provider_api_key', { providerId, apiKey, baseUrl, providerType });
+  return invoke<void>('delete_provider_auth', { providerId, removeFromConfig });
+}
+
+export async function disableProvider(providerId: string): Promise<void> {
+  return invoke<void>('disable_provider', { providerId });
+}
+
+export async function enableProvider(providerId: string): Promise<void> {
+  return invoke<void>('enable_provider', { providerId });
+}
+
+export async function listDisabledProviders(): Promise<string[]> {
+  return invoke<string[]>('list_disabled_providers');
 }
 
-export async function deleteProviderAuth(providerId: string): Promise<void> {
-  return invoke<void>('delete_provider_auth', { providerId });
+export type ProviderDiagnosticStatus =
+  | 'healthy'
+  | 'auth_not_connected'
+  | 'missing_models'
+  | 'connected_without_auth'
+  | 'stale_models';
+
+export interface ProviderDiagnostic {
+  provider_id: string;
+  in_auth: boolean;
+  in_connected: boolean;
+  in_models: boolean;
+  status: ProviderDiagnosticStatus;
+  suggestion: string;
+}
+
+/**
+ * 诊断各供应商在 auth.json、connected-providers.json、provider-models.json 中的状态
+ */
+export async function diagnoseProviders(): Promise<ProviderDiagnostic[]> {
+  return invoke<ProviderDiagnostic[]>('diagnose_providers');
 }
 
 export async function addCustomProvider(
+  name: string,
+  apiKey: string,
+  baseUrl: string,
+  npm?: string
+): Promise<ProviderInfo> {
+  return invoke<ProviderInfo>('add_custom_provider', { name, apiKey, baseUrl, npm });
+}
+
+export async function updateCustomProvider(
+  providerId: string,
   name: string,
   apiKey: string,
   baseUrl: string
 ): Promise<ProviderInfo> {
-  return invoke<ProviderInfo>('add_custom_provider', { name, apiKey, baseUrl });
+  return invoke<ProviderInfo>('update_custom_provider', { providerId, name, apiKey, baseUrl });
 }
 
+/**
+ * 测试供应商连接，timeoutSecs 可选（1–60 秒，默认 10，越界值会被收敛到范围内）
+ */
 export async function testProviderConnection(
   npm: string,
   baseUrl: string | null,
-  apiKey: string
+  apiKey: string,
+  timeoutSecs?: number
 ): Promise<ConnectionTestResult> {
-  return invoke<ConnectionTestResult>('test_provider_connection', { npm, baseUrl, apiKey });
+  return invoke<ConnectionTestResult>('test_provider_connection', {
+    npm,
+    baseUrl,
+    apiKey,
+    timeoutSecs,
+  });
 }
 
 export async function getProviderIcon(providerId: string): Promise<string | null> {
   return invoke<string | null>('get_provider_icon', { providerId });
 }
 
+export async function clearProviderIconCache(): Promise<number> {
+  return invoke<number>('clear_provider_icon_cache');
+}
+
+export async function getProviderIconCacheSize(): Promise<number> {
+  return invoke<number>('get_provider_icon_cache_size');
+}
+
 // ==================== 配置快照相关接口 ====================
 
 /**
@@ -416,10 +820,29 @@ export async function ensureSnapshotExists(): Promise<boolean> {
   return invoke<boolean>('ensure_snapshot_exists');
 }
 
+/**
+ * 列出历史配置快照的时间戳（Unix 毫秒，从新到旧）
+ */
+export async function listConfigSnapshots(): Promise<number[]> {
+  return invoke<number[]>('list_config_snapshots');
+}
+
+export async function loadConfigSnapshotAt(cachedAt: number): Promise<ConfigSnapshot | null> {
+  return invoke<ConfigSnapshot | null>('load_config_snapshot_at', { cachedAt });
+}
+
 export async function compareWithSnapshot(): Promise<ConfigChange[]> {
   return invoke<ConfigChange[]>('compare_with_snapshot');
 }
 
+export async function getSnapshotIgnoredPaths(): Promise<string[]> {
+  return invoke<string[]>('get_snapshot_ignored_paths');
+}
+
+export async function setSnapshotIgnoredPaths(paths: string[]): Promise<void> {
+  return invoke<void>('set_snapshot_ignored_paths', { paths });
+}
+
 /**
  * 获取配置文件的修改时间
  * 返回 Unix 时间戳（毫秒）
@@ -428,12 +851,23 @@ export async function getConfigModificationTime(): Promise<number | null> {
   return invoke<number | null>('get_config_modification_time');
 }
 
+/** 配置文件被修改时后端推送的事件 */
+export const CONFIG_CHANGED_EVENT = 'omo://config-changed';
+
+/**
+ * 订阅配置文件变更事件（替代轮询修改时间），返回取消订阅函数
+ */
+export async function onConfigChanged(handler: () => void): Promise<UnlistenFn> {
+  return listen(CONFIG_CHANGED_EVENT, () => handler());
+}
+
 /**
  * 合并缓存快照与当前配置并保存
  * 用于"从缓存恢复"功能
+ * deletedPaths 为本地已删除的字段路径（如 "agents.metis"），合并时不会被快照补回
  */
-export async function mergeAndSave(): Promise<void> {
-  return invoke<void>('merge_and_save');
+export async function mergeAndSave(deletedPaths?: string[]): Promise<void> {
+  return invoke<void>('merge_and_save', { deletedPaths });
 }
 
 export interface AcceptExternalChangesResult {
@@ -443,8 +877,19 @@ export interface AcceptExternalChangesResult {
   preset_sync_error: string | null;
 }
 
+/**
+ * 接受外部变更失败时的结构化指引
+ */
+export interface ExternalChangeError {
+  message: string;
+  recoverable: boolean;
+  suggested_action: 'restore_backup' | 'edit_manually';
+  backup_path: string | null;
+}
+
 /**
  * 接受外部配置变更并同步快照/当前预设
+ * 失败时以 ExternalChangeError 拒绝
  */
 export async function acceptExternalChanges(): Promise<AcceptExternalChangesResult> {
   return invoke<AcceptExternalChangesResult>('accept_external_changes');
@@ -469,6 +914,76 @@ export async function checkVersions(): Promise<VersionInfo[]> {
   return invoke<VersionInfo[]>('check_versions');
 }
 
+export async function checkVersionsForce(): Promise<VersionInfo[]> {
+  return invoke<VersionInfo[]>('check_versions_force');
+}
+
+export type ConfigCompatibilityStatus =
+  | 'compatible'
+  | 'needs_migration'
+  | 'plugin_outdated'
+  | 'unknown';
+
+export interface ConfigCompatibility {
+  status: ConfigCompatibilityStatus;
+  plugin_version: string | null;
+  config_version: string | null;
+  hint: string;
+}
+
+/**
+ * 检查插件版本与 oh-my-opencode.json schema 版本是否匹配，找不到版本信息时返回 unknown
+ */
+export async function checkConfigCompatibility(): Promise<ConfigCompatibility> {
+  return invoke<ConfigCompatibility>('check_config_compatibility');
+}
+
+export interface RepairReport {
+  actions: string[];
+  backup_path: string | null;
+}
+
+export async function repairAll(): Promise<RepairReport> {
+  return invoke<RepairReport>('repair_all');
+}
+
+export interface AgentRename {
+  from: string;
+  to: string;
+}
+
+export interface MigrationReport {
+  migrated: AgentRename[];
+  skipped: AgentRename[];
+  backup_path: string | null;
+}
+
+/**
+ * 将已废弃的 agent 名迁移到新名（新名已存在时跳过），迁移前自动备份
+ */
+export async function migrateConfig(): Promise<MigrationReport> {
+  return invoke<MigrationReport>('migrate_config');
+}
+
+export type HealthSeverity = 'error' | 'warning' | 'info';
+
+export interface HealthIssue {
+  severity: HealthSeverity;
+  path: string;
+  message: string;
+}
+
+export interface HealthReport {
+  issues: HealthIssue[];
+}
+
+/**
+ * 只读检查配置健康状况（根结构、模型可用性、供应商连接、空 model）
+ */
+export async function configHealthCheck(): Promise<HealthReport> {
+  return invoke<HealthReport>('config_health_check');
+}
+
 // ==================== 默认导出 ====================
 
 const tauriService = {
@@ -517,6 +1032,8 @@ const tauriService = {
   // 配置快照
   saveConfigSnapshot,
   ensureSnapshotExists,
+  listConfigSnapshots,
+  loadConfigSnapshotAt,
   compareWithSnapshot,
   mergeAndSave,
   acceptExternalChanges,
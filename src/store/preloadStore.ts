@@ -6,6 +6,7 @@ import {
   getConnectedProviders,
   fetchModelsDev,
   checkVersions,
+  checkVersionsForce,
   getOmoConfig,
   getActivePreset as loadActivePreset,
   listPresets,
@@ -63,7 +64,7 @@ interface PreloadState {
   startPreload: () => void;
   loadOmoConfig: () => Promise<void>;
   refreshModels: () => Promise<void>;
-  refreshVersions: () => Promise<void>;
+  refreshVersions: (force?: boolean) => Promise<void>;
   softRefreshAll: () => void;
   retryAll: () => void;
   // 更新 omoConfig 中特定 agent 或 category 的配置
@@ -324,7 +325,7 @@ refreshModels: async () => {
   }
 },
 
-refreshVersions: async () => {
+refreshVersions: async (force = false) => {
   const state = get();
 
   // 防止重复请求
@@ -343,7 +344,8 @@ refreshVersions: async () => {
   }
 
   try {
-    const data = await checkVersions();
+    // 手动刷新时跳过后端的版本检查缓存
+    const data = force ? await checkVersionsForce() : await checkVersions();
     set({
       versions: { data, loading: false, error: null },
       _versionsRefreshing: false,